@@ -0,0 +1,37 @@
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 借支记录，员工预支的工资通过后续工资单的借支还款明细扣回
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "advance")]
+pub struct Model {
+    /// 借支唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 借支员工的用户 ID
+    pub user_id: Uuid,
+    /// 出借老板的用户 ID
+    pub boss_id: Uuid,
+    /// 借支金额（元）
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub amount: Decimal,
+    /// 借支时间
+    pub advanced_at: DateTimeUtc,
+    /// 备注说明
+    pub note: Option<String>,
+    /// 凭证图片 URL
+    pub image: Option<String>,
+    /// 创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "user_id", to = "id")]
+    pub user: HasOne<super::user::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,61 @@
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 考勤记录，按员工的时薪或日薪计算计时工资，每人每天一条
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "attendance")]
+pub struct Model {
+    /// 考勤唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 员工的用户 ID
+    #[sea_orm(unique_key = "user_work_date")]
+    pub user_id: Uuid,
+    /// 所属老板的用户 ID
+    pub boss_id: Uuid,
+    /// 出勤日期
+    #[sea_orm(unique_key = "user_work_date")]
+    pub work_date: Date,
+    /// 上班打卡时间，老板补录时为空
+    pub clock_in: Option<DateTimeUtc>,
+    /// 下班打卡时间，已上班未下班时为空
+    pub clock_out: Option<DateTimeUtc>,
+    /// 工时（小时）
+    #[sea_orm(column_type = "Decimal(Some((6, 2)))")]
+    pub hours: Decimal,
+    /// 出勤天数，全天为1，半天为0.5
+    #[sea_orm(column_type = "Decimal(Some((4, 2)))")]
+    pub days: Decimal,
+    /// 计时工资（元），按记录时的时薪或日薪计算
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub amount: Decimal,
+    /// 备注
+    pub note: Option<String>,
+    /// 结算的工资单 ID，未结算时为空
+    pub payroll_id: Option<Uuid>,
+    /// 创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "user_id", to = "id")]
+    pub user: HasOne<super::user::Entity>,
+}
+
+impl Model {
+    /// 已上班打卡但尚未下班
+    pub fn is_open(&self) -> bool {
+        self.clock_in.is_some() && self.clock_out.is_none()
+    }
+
+    /// 是否已结算到工资单
+    pub fn is_settled(&self) -> bool {
+        self.payroll_id.is_some()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,37 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 扎（裁片捆扎），裁床按尺码/颜色分扎编号，随扎流转各道工序
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "bundle")]
+pub struct Model {
+    /// 扎唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 所属订单 ID
+    #[sea_orm(unique_key = "order_bundle_no")]
+    pub order_id: Uuid,
+    /// 订单所属老板的用户 ID
+    pub boss_id: Uuid,
+    /// 扎号，同一订单内从 1 开始递增
+    #[sea_orm(unique_key = "order_bundle_no")]
+    pub bundle_no: i32,
+    /// 尺码
+    pub size: Option<String>,
+    /// 颜色
+    pub color: Option<String>,
+    /// 本扎件数
+    pub quantity: i32,
+    /// 创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "order_id", to = "id", on_delete = "Cascade")]
+    pub order: HasOne<super::order::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
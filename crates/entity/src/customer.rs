@@ -15,7 +15,7 @@ pub struct Model {
     pub user_id: Uuid,
     /// 客户名称
     pub name: String,
-    /// 客户联系电话
+    /// 客户联系电话，多个联系人见客户联系人
     pub phone: Option<String>,
     /// 客户备注说明
     pub description: Option<String>,
@@ -33,6 +33,14 @@ pub struct Model {
     #[serde(skip)]
     #[sea_orm(has_many)]
     pub orders: HasMany<super::order::Entity>,
+
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub contacts: HasMany<super::customer_contact::Entity>,
+
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub addresses: HasMany<super::customer_address::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,65 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 客户地址类型
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    DeriveValueType,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+#[sea_orm(value_type = "String")]
+pub enum CustomerAddressKind {
+    /// 收货地址，出货时送达
+    Delivery,
+    /// 账单地址，开票和对账使用
+    Billing,
+}
+
+/// 客户地址，区分收货地址和账单地址
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "customer_address")]
+pub struct Model {
+    /// 地址唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 所属客户 ID
+    pub customer_id: Uuid,
+    /// 客户所属老板的用户 ID
+    pub boss_id: Uuid,
+    /// 地址类型
+    pub kind: CustomerAddressKind,
+    /// 地址名称，如总部、二号仓
+    pub label: Option<String>,
+    /// 详细地址
+    pub address: String,
+    /// 收件人
+    pub receiver: Option<String>,
+    /// 收件人电话
+    pub phone: Option<String>,
+    /// 是否为该类型的默认地址，新建订单时默认使用默认收货地址
+    #[sea_orm(default_value = false)]
+    pub is_default: bool,
+    /// 创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "customer_id", to = "id", on_delete = "Cascade")]
+    pub customer: HasOne<super::customer::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
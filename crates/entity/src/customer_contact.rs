@@ -0,0 +1,38 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 客户联系人，如跟单、质检
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "customer_contact")]
+pub struct Model {
+    /// 联系人唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 所属客户 ID
+    pub customer_id: Uuid,
+    /// 客户所属老板的用户 ID
+    pub boss_id: Uuid,
+    /// 姓名
+    pub name: String,
+    /// 职务/角色，如跟单、质检、财务
+    pub role: Option<String>,
+    /// 联系电话
+    pub phone: Option<String>,
+    /// 备注
+    pub note: Option<String>,
+    /// 是否为默认联系人，新建订单时默认选中
+    #[sea_orm(default_value = false)]
+    pub is_default: bool,
+    /// 创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "customer_id", to = "id", on_delete = "Cascade")]
+    pub customer: HasOne<super::customer::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
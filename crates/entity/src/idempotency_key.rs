@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 幂等键，记录客户端带 Idempotency-Key 的写请求及其响应，重试时直接返回原响应
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "idempotency_key")]
+pub struct Model {
+    /// 唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 发起请求的用户 ID
+    #[sea_orm(unique_key = "user_key")]
+    pub user_id: Uuid,
+    /// 客户端提供的幂等键
+    #[sea_orm(unique_key = "user_key")]
+    pub key: String,
+    /// 接口标识，如 "piece_record.create"
+    pub scope: String,
+    /// 请求内容哈希，用于识别同一幂等键被用于不同请求
+    pub request_hash: String,
+    /// 首次请求的响应，处理中为空
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub response: Option<Json>,
+    /// 创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,9 +1,20 @@
+pub mod advance;
+pub mod attendance;
+pub mod bundle;
 pub mod customer;
+pub mod customer_address;
+pub mod customer_contact;
+pub mod idempotency_key;
 pub mod order;
+pub mod pay_run;
+pub mod pay_run_entry;
 pub mod payroll;
+pub mod payroll_item;
 pub mod payroll_record;
 pub mod piece_record;
+pub mod piece_record_log;
 pub mod process;
+pub mod process_price;
 pub mod register_code;
 pub mod share;
 pub mod user;
@@ -12,12 +23,23 @@ pub mod chat_thread;
 pub mod chat_message;
 
 pub mod prelude {
+    pub use super::advance::Entity as Advance;
+    pub use super::attendance::Entity as Attendance;
+    pub use super::bundle::Entity as Bundle;
     pub use super::customer::Entity as Customer;
+    pub use super::customer_address::Entity as CustomerAddress;
+    pub use super::customer_contact::Entity as CustomerContact;
+    pub use super::idempotency_key::Entity as IdempotencyKey;
     pub use super::order::Entity as Order;
+    pub use super::pay_run::Entity as PayRun;
+    pub use super::pay_run_entry::Entity as PayRunEntry;
     pub use super::payroll::Entity as Payroll;
+    pub use super::payroll_item::Entity as PayrollItem;
     pub use super::payroll_record::Entity as PayrollRecord;
     pub use super::piece_record::Entity as PieceRecord;
+    pub use super::piece_record_log::Entity as PieceRecordLog;
     pub use super::process::Entity as Process;
+    pub use super::process_price::Entity as ProcessPrice;
     pub use super::register_code::Entity as RegisterCode;
     pub use super::share::Entity as Share;
     pub use super::user::Entity as User;
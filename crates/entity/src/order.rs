@@ -72,12 +72,19 @@ pub struct Model {
     /// 单价（元）
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub unit_price: Decimal,
+    /// 加急系数，计件金额按此倍数计算，为空表示不加急
+    #[sea_orm(column_type = "Decimal(Some((4, 2)))", nullable)]
+    pub urgency_multiplier: Option<Decimal>,
     /// 订单状态
     pub status: OrderStatus,
     /// 收货/拿货时间
     pub received_at: DateTimeUtc,
     /// 出货时间
     pub delivered_at: Option<DateTimeUtc>,
+    /// 收货地址 ID，为客户的收货地址
+    pub delivery_address_id: Option<Uuid>,
+    /// 客户联系人 ID
+    pub contact_id: Option<Uuid>,
     /// 订单最后更新时间
     #[sea_orm(auto_update, default_expr = "Expr::current_timestamp()")]
     pub updated_at: DateTimeUtc,
@@ -89,6 +96,10 @@ pub struct Model {
     #[serde(skip)]
     #[sea_orm(has_many)]
     pub processes: HasMany<super::process::Entity>,
+
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub bundles: HasMany<super::bundle::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,60 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 批量发薪状态
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    DeriveValueType,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+#[sea_orm(value_type = "String")]
+pub enum PayRunStatus {
+    /// 草稿，可调整各员工的工资草稿
+    Draft,
+    /// 已发放，已为全部员工生成工资单
+    Finalized,
+}
+
+/// 批量发薪：按周期汇总全部员工已批准的计件，审核后一次性生成工资单
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "pay_run")]
+pub struct Model {
+    /// 批量发薪唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 发薪老板的用户 ID
+    pub boss_id: Uuid,
+    /// 周期开始日期（含）
+    pub start_date: Date,
+    /// 周期结束日期（含）
+    pub end_date: Date,
+    /// 状态
+    pub status: PayRunStatus,
+    /// 备注，发放时写入各工资单
+    pub note: Option<String>,
+    /// 创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+    /// 发放时间
+    pub finalized_at: Option<DateTimeUtc>,
+
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub entries: HasMany<super::pay_run_entry::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,64 @@
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 批量发薪中单个员工的工资草稿
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "pay_run_entry")]
+pub struct Model {
+    /// 草稿唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 所属批量发薪 ID
+    #[sea_orm(unique_key = "pay_run_user")]
+    pub pay_run_id: Uuid,
+    /// 员工的用户 ID
+    #[sea_orm(unique_key = "pay_run_user")]
+    pub user_id: Uuid,
+    /// 结算的计件记录 ID 列表
+    #[sea_orm(column_type = "JsonBinary")]
+    pub record_ids: Json,
+    /// 结算的考勤记录 ID 列表
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub attendance_ids: Option<Json>,
+    /// 奖金、扣款、手填的借支还款等明细
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub items: Option<Json>,
+    /// 是否扣回借支，金额在发放时按重新计价后的应发金额和当时的未还借支计算
+    #[sea_orm(default_value = false)]
+    pub repay_advance: bool,
+    /// 手填发放金额，为空则按明细合计
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))", nullable)]
+    pub amount: Option<Decimal>,
+    /// 是否排除，排除的员工不生成工资单
+    #[sea_orm(default_value = false)]
+    pub excluded: bool,
+    /// 备注
+    pub note: Option<String>,
+    /// 发放后生成的工资单 ID
+    pub payroll_id: Option<Uuid>,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "pay_run_id", to = "id", on_delete = "Cascade")]
+    pub pay_run: HasOne<super::pay_run::Entity>,
+}
+
+impl Model {
+    /// 解析计件记录 ID 列表
+    pub fn record_ids(&self) -> Vec<Uuid> {
+        serde_json::from_value(self.record_ids.clone()).unwrap_or_default()
+    }
+
+    /// 解析考勤记录 ID 列表
+    pub fn attendance_ids(&self) -> Vec<Uuid> {
+        self.attendance_ids
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -2,6 +2,35 @@ use rust_decimal::Decimal;
 use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 员工对工资单的确认状态
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    DeriveValueType,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+#[sea_orm(value_type = "String")]
+pub enum PayrollAckStatus {
+    /// 待员工确认
+    Pending,
+    /// 员工已确认金额无误
+    Acknowledged,
+    /// 员工有异议，待老板处理
+    Disputed,
+    /// 老板已处理异议，待员工再次确认
+    Resolved,
+}
 
 /// 工资发放记录
 #[sea_orm::model]
@@ -16,7 +45,7 @@ pub struct Model {
     pub user_id: Uuid,
     /// 发放工资的老板用户 ID
     pub boss_id: Uuid,
-    /// 发放金额（元）
+    /// 发放金额（元），等于工资明细合计
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub amount: Decimal,
     /// 支付凭证图片 URL
@@ -25,6 +54,21 @@ pub struct Model {
     pub note: Option<String>,
     /// 发放时间
     pub paid_at: DateTimeUtc,
+    /// 转账完成时间，批量转账后标记，为空表示尚未标记
+    pub transferred_at: Option<DateTimeUtc>,
+    /// 员工确认状态
+    #[sea_orm(default_value = "pending")]
+    pub ack_status: PayrollAckStatus,
+    /// 员工确认或提出异议时的说明
+    pub ack_comment: Option<String>,
+    /// 员工确认或提出异议的时间
+    pub acked_at: Option<DateTimeUtc>,
+    /// 老板对异议的处理说明
+    pub resolution: Option<String>,
+    /// 作废时间，未作废时为空
+    pub voided_at: Option<DateTimeUtc>,
+    /// 作废原因
+    pub void_reason: Option<String>,
 
     #[serde(skip)]
     #[sea_orm(belongs_to, from = "user_id", to = "id")]
@@ -33,6 +77,17 @@ pub struct Model {
     #[serde(skip)]
     #[sea_orm(has_many, via = "payroll_record")]
     pub price_records: HasMany<super::piece_record::Entity>,
+
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub items: HasMany<super::payroll_item::Entity>,
+}
+
+impl Model {
+    /// 是否已作废
+    pub fn is_voided(&self) -> bool {
+        self.voided_at.is_some()
+    }
 }
 
 impl ActiveModelBehavior for ActiveModel {}
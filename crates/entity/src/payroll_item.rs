@@ -0,0 +1,71 @@
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 工资明细类型
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    DeriveValueType,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+#[sea_orm(value_type = "String")]
+pub enum PayrollItemKind {
+    /// 计件工资，由所选计件记录金额汇总
+    PieceWork,
+    /// 计时工资，由所选考勤记录金额汇总
+    TimeWork,
+    /// 奖金
+    Bonus,
+    /// 扣款
+    Deduction,
+    /// 借支还款
+    AdvanceRepayment,
+    /// 抹零/凑整
+    Rounding,
+    /// 手动调整，发放金额与明细合计不一致时的差额
+    ManualAdjustment,
+}
+
+/// 工资明细，工资单金额为全部明细金额之和
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "payroll_item")]
+pub struct Model {
+    /// 明细唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 所属工资单 ID
+    pub payroll_id: Uuid,
+    /// 明细类型
+    pub kind: PayrollItemKind,
+    /// 金额（元），扣款和借支还款为负数
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub amount: Decimal,
+    /// 说明
+    pub note: Option<String>,
+    /// 在工资单中的显示顺序
+    #[sea_orm(default_value = 0)]
+    pub position: i32,
+    /// 创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "payroll_id", to = "id", on_delete = "Cascade")]
+    pub payroll: HasOne<super::payroll::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
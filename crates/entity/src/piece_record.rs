@@ -73,15 +73,37 @@ pub struct Model {
     pub boss_id: Uuid,
     /// 完成数量
     pub quantity: i32,
-    /// 计件金额（元），数量 × 工序单价
+    /// 计件金额（元），数量 × 计件单价，加上阶梯和加急加价
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub amount: Decimal,
+    /// 计件时采用的单价（元），按录入时间取生效的单价版本；早期记录为空
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))", nullable)]
+    pub piece_price: Option<Decimal>,
+    /// 阶梯单价带来的加价（元）
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))", nullable)]
+    pub tier_bonus: Option<Decimal>,
+    /// 订单加急带来的加价（元）
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))", nullable)]
+    pub urgency_bonus: Option<Decimal>,
     /// 计件记录状态
     pub status: PieceRecordStatus,
     /// 记录者：员工自己或老板代录
     pub recorded_by: RecordedBy,
     /// 录入时间
     pub recorded_at: DateTimeUtc,
+    /// 计件凭证照片的文件 key 列表
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub images: Option<Json>,
+    /// 结算后调整记录对应的原计件 ID，普通计件为空；调整记录的数量和金额为差额，可为负数
+    pub adjusts_record_id: Option<Uuid>,
+    /// 调整原因
+    pub adjust_reason: Option<String>,
+    /// 扫码录入时对应的扎 ID
+    pub bundle_id: Option<Uuid>,
+    /// 命中自动审核规则时记录规则名称，人工审核的记录为空
+    pub auto_approve_rule: Option<String>,
+    /// 最近一次驳回原因，重新提交后清空
+    pub reject_reason: Option<String>,
     /// 记录最后更新时间（如审核状态变更）
     #[sea_orm(auto_update, default_expr = "Expr::current_timestamp()")]
     pub updated_at: DateTimeUtc,
@@ -97,6 +119,46 @@ pub struct Model {
     #[serde(skip)]
     #[sea_orm(has_one, via = "payroll_record")]
     pub payroll: HasOne<super::payroll::Entity>,
+
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub logs: HasMany<super::piece_record_log::Entity>,
+}
+
+impl Model {
+    /// 是否为结算后调整记录
+    pub fn is_adjustment(&self) -> bool {
+        self.adjusts_record_id.is_some()
+    }
+
+    /// 计入产量的件数，结算后调整只影响金额
+    pub fn produced_quantity(&self) -> i64 {
+        if self.is_adjustment() {
+            0
+        } else {
+            self.quantity as i64
+        }
+    }
+
+    /// 计件凭证照片的文件 key 列表
+    pub fn image_keys(&self) -> Vec<String> {
+        image_keys(&self.images)
+    }
+}
+
+impl ModelEx {
+    /// 计件凭证照片的文件 key 列表
+    pub fn image_keys(&self) -> Vec<String> {
+        image_keys(&self.images)
+    }
+}
+
+/// 解析照片字段中的文件 key 列表
+fn image_keys(images: &Option<Json>) -> Vec<String> {
+    images
+        .as_ref()
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,66 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 计件记录操作类型
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    DeriveValueType,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+#[sea_orm(value_type = "String")]
+pub enum PieceRecordAction {
+    /// 老板驳回
+    Rejected,
+    /// 员工修改后重新提交
+    Resubmitted,
+    /// 修改数量
+    Edited,
+    /// 结算后调整，调整差额计入下次工资
+    Adjusted,
+    /// 工资单作废，退回已批准
+    Unsettled,
+}
+
+/// 计件记录操作历史，保留驳回原因以及修改、重新提交前后的数量
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "piece_record_log")]
+pub struct Model {
+    /// 历史记录唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 计件记录 ID
+    pub piece_record_id: Uuid,
+    /// 操作人用户 ID
+    pub operator_id: Uuid,
+    /// 操作类型
+    pub action: PieceRecordAction,
+    /// 操作前的数量
+    pub previous_quantity: i32,
+    /// 操作后的数量
+    pub quantity: i32,
+    /// 原因/说明，驳回时为驳回原因
+    pub reason: Option<String>,
+    /// 操作时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "piece_record_id", to = "id", on_delete = "Cascade")]
+    pub piece_record: HasOne<super::piece_record::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -2,6 +2,41 @@ use rust_decimal::Decimal;
 use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 阶梯单价的累计周期
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    DeriveValueType,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+#[sea_orm(value_type = "String")]
+pub enum TierPeriod {
+    /// 按天累计
+    Daily,
+    /// 按自然月累计
+    Monthly,
+}
+
+/// 阶梯单价：员工在累计周期内本工序完成超过 threshold 件后，超出部分按 piece_price 计
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RateTier {
+    /// 起算件数（不含）
+    pub threshold: i32,
+    /// 超出部分的计件单价（元）
+    pub piece_price: Decimal,
+}
 
 /// 加工工序，订单中的一道加工步骤
 #[sea_orm::model]
@@ -23,6 +58,29 @@ pub struct Model {
     /// 计件单价（元），员工每完成一件可获得的报酬
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub piece_price: Decimal,
+    /// 工序顺序，数字越小越靠前
+    #[sea_orm(default_value = 0)]
+    pub sequence: i32,
+    /// 上游工序 ID 列表（同一订单内），本工序的计件数量不能超过上游工序的完成数量
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub depends_on: Option<Json>,
+    /// 指定可做该工序的员工 ID 列表，与技能分组均为空时所有员工可做
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub assigned_user_ids: Option<Json>,
+    /// 指定可做该工序的技能分组，员工具备其中任一技能即可
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub assigned_skills: Option<Json>,
+    /// 阶梯单价列表，为空表示固定单价
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub rate_tiers: Option<Json>,
+    /// 阶梯单价的累计周期
+    #[sea_orm(default_value = "daily")]
+    pub tier_period: TierPeriod,
+    /// 员工提交计件时是否必须上传照片
+    #[sea_orm(default_value = false)]
+    pub photo_required: bool,
+    /// 计件数量达到该值时必须上传照片，为空表示不按数量要求
+    pub photo_threshold: Option<i32>,
     /// 工序最后更新时间
     #[sea_orm(auto_update, default_expr = "Expr::current_timestamp()")]
     pub updated_at: DateTimeUtc,
@@ -34,6 +92,68 @@ pub struct Model {
     #[serde(skip)]
     #[sea_orm(has_many)]
     pub piece_records: HasMany<super::piece_record::Entity>,
+
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub prices: HasMany<super::process_price::Entity>,
+}
+
+impl Model {
+    /// 上游工序 ID 列表
+    pub fn upstream_ids(&self) -> Vec<Uuid> {
+        self.depends_on
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// 阶梯单价，按起算件数升序
+    pub fn tiers(&self) -> Vec<RateTier> {
+        let mut tiers: Vec<RateTier> = self
+            .rate_tiers
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        tiers.sort_by_key(|t| t.threshold);
+        tiers
+    }
+
+    /// 指定的员工 ID 列表
+    pub fn assigned_users(&self) -> Vec<Uuid> {
+        self.assigned_user_ids
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// 指定的技能分组
+    pub fn assigned_skill_tags(&self) -> Vec<String> {
+        self.assigned_skills
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// 该数量的计件是否需要附照片
+    pub fn requires_photo(&self, quantity: i32) -> bool {
+        self.photo_required || self.photo_threshold.is_some_and(|t| quantity >= t)
+    }
+
+    /// 是否未限定员工
+    pub fn is_open(&self) -> bool {
+        self.assigned_users().is_empty() && self.assigned_skill_tags().is_empty()
+    }
+
+    /// 员工是否可以做该工序
+    pub fn is_assigned_to(&self, staff: &super::user::Model) -> bool {
+        if self.is_open() || self.assigned_users().contains(&staff.id) {
+            return true;
+        }
+        let skills = staff.skill_tags();
+        self.assigned_skill_tags()
+            .iter()
+            .any(|s| skills.contains(s))
+    }
 }
 
 impl ActiveModelBehavior for ActiveModel {}
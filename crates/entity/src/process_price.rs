@@ -0,0 +1,35 @@
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 工序单价版本，记录计件单价的历史变更及生效时间
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "process_price")]
+pub struct Model {
+    /// 单价版本唯一标识符
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// 所属工序 ID
+    pub process_id: Uuid,
+    /// 工序所属老板的用户 ID
+    pub boss_id: Uuid,
+    /// 计件单价（元）
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub piece_price: Decimal,
+    /// 生效时间，录入时间不早于该时间的计件使用此单价
+    pub effective_from: DateTimeUtc,
+    /// 调价说明
+    pub note: Option<String>,
+    /// 版本创建时间
+    #[sea_orm(default_expr = "Expr::current_timestamp()")]
+    pub created_at: DateTimeUtc,
+
+    #[serde(skip)]
+    #[sea_orm(belongs_to, from = "process_id", to = "id", on_delete = "Cascade")]
+    pub process: HasOne<super::process::Entity>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,3 +1,4 @@
+use rust_decimal::Decimal;
 use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -27,6 +28,32 @@ pub enum Role {
     Staff,
 }
 
+/// 收款方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PayoutMethod {
+    /// 银行卡
+    Bank,
+    /// 支付宝
+    Alipay,
+    /// 微信
+    Wechat,
+}
+
+/// 工资收款账户
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutAccount {
+    /// 收款方式
+    pub method: PayoutMethod,
+    /// 收款人姓名（需与账户实名一致）
+    pub account_name: String,
+    /// 银行卡号/支付宝账号/微信号
+    pub account_no: String,
+    /// 开户行，银行卡收款时填写
+    pub bank_name: Option<String>,
+}
+
 /// 系统用户，包括老板和员工
 #[sea_orm::model]
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, JsonSchema)]
@@ -55,6 +82,21 @@ pub struct Model {
     /// 是否为超级管理员
     #[sea_orm(default_value = "false")]
     pub is_super_admin: bool,
+    /// 员工技能分组，如"车工"、"熨烫"，用于工序分配
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub skills: Option<Json>,
+    /// 工资收款账户，仅本人和所属工坊老板可见
+    #[serde(skip)]
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub payout_account: Option<Json>,
+    /// 时薪（元/小时），计时员工按工时计算工资
+    #[serde(skip)]
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))", nullable)]
+    pub hourly_rate: Option<Decimal>,
+    /// 日薪（元/天），未设置时薪时按出勤天数计算工资
+    #[serde(skip)]
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))", nullable)]
+    pub daily_rate: Option<Decimal>,
     /// 用户创建时间
     #[sea_orm(default_expr = "Expr::current_timestamp()")]
     pub created_at: DateTimeUtc,
@@ -74,6 +116,14 @@ pub struct Model {
     #[sea_orm(has_many)]
     pub payrolls: HasMany<super::payroll::Entity>,
 
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub advances: HasMany<super::advance::Entity>,
+
+    #[serde(skip)]
+    #[sea_orm(has_many)]
+    pub attendances: HasMany<super::attendance::Entity>,
+
     #[serde(skip)]
     #[sea_orm(
         belongs_to,
@@ -92,4 +142,30 @@ pub struct Model {
     pub threads: HasMany<super::chat_thread::Entity>,
 }
 
+impl Model {
+    /// 技能分组列表
+    pub fn skill_tags(&self) -> Vec<String> {
+        self.skills
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// 工资收款账户
+    pub fn payout(&self) -> Option<PayoutAccount> {
+        self.payout_account
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// 计时工资：设置了时薪按工时计算，否则按日薪和出勤天数计算
+    pub fn time_wage(&self, hours: Decimal, days: Decimal) -> Decimal {
+        match (self.hourly_rate, self.daily_rate) {
+            (Some(rate), _) => (rate * hours).round_dp(2),
+            (None, Some(rate)) => (rate * days).round_dp(2),
+            (None, None) => Decimal::ZERO,
+        }
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
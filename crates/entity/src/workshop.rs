@@ -1,6 +1,76 @@
 use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+/// 超产策略：计件总数超过订单数量时的处理方式
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    DeriveValueType,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+#[sea_orm(value_type = "String")]
+pub enum OverproductionPolicy {
+    /// 禁止超出（含容差）
+    Block,
+    /// 允许超出，但给出提醒
+    Warn,
+    /// 不做限制
+    Allow,
+}
+
+/// 工资单发放金额与明细合计不一致时的处理方式
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    Display,
+    DeriveValueType,
+    JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+#[sea_orm(value_type = "String")]
+pub enum PayrollDivergencePolicy {
+    /// 禁止不一致
+    Block,
+    /// 允许，差额记为手动调整并给出提醒
+    Warn,
+}
+
+/// 自动审核规则：员工提交的计件满足规则内全部条件时直接通过
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoApproveRule {
+    /// 规则名称，记录在自动通过的计件上
+    pub name: String,
+    /// 信任的员工 ID，为空表示不限员工
+    #[serde(default)]
+    pub user_ids: Vec<Uuid>,
+    /// 适用的工序 ID，为空表示不限工序
+    #[serde(default)]
+    pub process_ids: Vec<Uuid>,
+    /// 单条计件数量上限，为空表示不限
+    pub max_quantity: Option<i32>,
+    /// 是否要求工序累计数量不超过订单数量
+    #[serde(default)]
+    pub within_remaining: bool,
+}
 
 /// 工坊/车间，服装加工的工作单位
 #[sea_orm::model]
@@ -27,6 +97,20 @@ pub struct Model {
     /// 业务标签，如"工坊"、"车间"等
     #[sea_orm(default_value = "工坊")]
     pub business_label: String,
+    /// 超产策略
+    #[sea_orm(default_value = "warn")]
+    pub overproduction_policy: OverproductionPolicy,
+    /// 超产容差（%），订单数量 × (1 + 容差) 以内不视为超产
+    #[sea_orm(default_value = 0)]
+    pub overproduction_tolerance: i32,
+    /// 工资单金额与明细合计不一致时的处理方式
+    #[sea_orm(default_value = "warn")]
+    pub payroll_divergence_policy: PayrollDivergencePolicy,
+    /// 计件自动审核规则列表，按顺序匹配，为空表示全部人工审核
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub auto_approve_rules: Option<Json>,
+    /// 最近一次发送自动审核日报的日期（本地），用于多实例和补发时去重
+    pub digest_sent_on: Option<Date>,
     /// 工坊创建时间
     #[sea_orm(default_expr = "Expr::current_timestamp()")]
     pub created_at: DateTimeUtc,
@@ -42,4 +126,14 @@ pub struct Model {
     pub boss: HasOne<super::user::Entity>,
 }
 
+impl Model {
+    /// 计件自动审核规则
+    pub fn approve_rules(&self) -> Vec<AutoApproveRule> {
+        self.auto_approve_rules
+            .as_ref()
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -51,3 +51,44 @@ pub async fn init_super_admin(db: &DbConn) -> Result<(), Box<dyn std::error::Err
 
     Ok(())
 }
+
+/// 为没有单价版本的旧工序补初始版本，避免新调价被套用到历史计件
+pub async fn backfill_price_versions(db: &DbConn) -> Result<(), Box<dyn std::error::Error>> {
+    use entity::{process, process_price};
+    use std::collections::HashSet;
+
+    let versioned: HashSet<Uuid> = process_price::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|v| v.process_id)
+        .collect();
+    let missing: Vec<process::Model> = process::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|p| !versioned.contains(&p.id))
+        .collect();
+
+    for proc in &missing {
+        service::process::service::ensure_initial_version(db, proc).await?;
+    }
+    if !missing.is_empty() {
+        tracing::info!("Backfilled price versions for {} processes", missing.len());
+    }
+    Ok(())
+}
+
+/// 创建 schema 同步无法表达的部分唯一索引
+pub async fn ensure_partial_indexes(db: &DbConn) -> Result<(), Box<dyn std::error::Error>> {
+    use sea_orm::ConnectionTrait;
+
+    // 同一扎的同一工序只能有一条未驳回的扫码计件
+    db.execute_unprepared(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_piece_record_bundle_process \
+         ON piece_record (bundle_id, process_id) \
+         WHERE bundle_id IS NOT NULL AND status <> 'rejected'",
+    )
+    .await?;
+    Ok(())
+}
@@ -4,7 +4,7 @@ use chrono::{NaiveDate, NaiveDateTime};
 use sea_orm::{ColumnTrait, QueryFilter, Select};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiResponse<T> {
     pub code: i32,
@@ -53,6 +53,15 @@ impl<T: Serialize> ApiResponse<T> {
             data,
         }
     }
+
+    /// 成功响应，附带提示信息（如超产提醒）；没有提示时与 `ok` 相同
+    pub fn ok_with_warning(data: T, warning: Option<String>) -> Self {
+        Self {
+            code: 0,
+            message: warning.unwrap_or_else(|| "success".to_string()),
+            data,
+        }
+    }
 }
 
 impl<T: Serialize> IntoResponse for ApiResponse<T> {
@@ -61,6 +70,15 @@ impl<T: Serialize> IntoResponse for ApiResponse<T> {
     }
 }
 
+/// 反序列化可清空字段：缺省为 None，null 为 Some(None)，需配合 `#[serde(default)]`
+pub fn deserialize_nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 // ============ Date Range Filter Helpers ============
 
 /// Parse date string to NaiveDateTime at start of day (00:00:00)
@@ -1,5 +1,5 @@
 use axum::extract::rejection::JsonRejection;
-use axum::extract::FromRequest;
+use axum::extract::{FromRequest, OptionalFromRequest, Request};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
@@ -22,6 +22,9 @@ pub enum AppError {
     #[error("Forbidden")]
     Forbidden,
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     #[error("Database error: {0}")]
     Database(#[from] sea_orm::DbErr),
 
@@ -39,6 +42,7 @@ impl IntoResponse for AppError {
             AppError::Unauthorized => (StatusCode::UNAUTHORIZED, 401, "Unauthorized".to_string()),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, 404, msg.clone()),
             AppError::Forbidden => (StatusCode::FORBIDDEN, 403, "Forbidden".to_string()),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, 409, msg.clone()),
             AppError::Database(err) => {
                 tracing::error!(error = %err, "Database error");
                 (
@@ -97,6 +101,20 @@ pub type Result<T> = std::result::Result<T, AppError>;
 #[from_request(via(axum::Json), rejection(AppError))]
 pub struct AppJson<T>(pub T);
 
+/// 请求体可省略时使用 `Option<AppJson<T>>`
+impl<T, S> OptionalFromRequest<S> for AppJson<T>
+where
+    T: serde::de::DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Option<Self>> {
+        let json = <Json<T> as OptionalFromRequest<S>>::from_request(req, state).await?;
+        Ok(json.map(|Json(value)| AppJson(value)))
+    }
+}
+
 impl From<JsonRejection> for AppError {
     fn from(rejection: JsonRejection) -> Self {
         let message = match &rejection {
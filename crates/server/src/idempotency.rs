@@ -0,0 +1,165 @@
+//! Idempotency-Key 支持
+//!
+//! 客户端在网络不稳定时会重试 POST 请求。带 `Idempotency-Key` 请求头的写请求会记录请求哈希和响应：
+//! 同一用户用同一个键重试相同请求时直接返回首次响应，用于不同请求时返回 409。
+
+use std::future::Future;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use chrono::{Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, QueryFilter, Set, SqlErr,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::common::ApiResponse;
+use crate::error::{AppError, Result};
+use entity::idempotency_key::{self, Column};
+
+/// 请求头名称
+pub const HEADER: &str = "idempotency-key";
+
+/// 幂等键保留时长，过期后同一个键可重新使用
+const TTL_HOURS: i64 = 24;
+
+/// 从请求头提取的幂等键，未携带时为 None
+pub struct IdempotencyKey(pub Option<String>);
+
+impl<S: Send + Sync> FromRequestParts<S> for IdempotencyKey {
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self> {
+        let Some(value) = parts.headers.get(HEADER) else {
+            return Ok(Self(None));
+        };
+        let key = value
+            .to_str()
+            .map_err(|_| AppError::BadRequest("Idempotency-Key 格式不正确".to_string()))?
+            .trim();
+        if key.is_empty() || key.len() > 255 {
+            return Err(AppError::BadRequest(
+                "Idempotency-Key 长度需在 1-255 之间".to_string(),
+            ));
+        }
+        Ok(Self(Some(key.to_string())))
+    }
+}
+
+fn request_hash<R: Serialize>(scope: &str, request: &R) -> Result<String> {
+    let body = serde_json::to_vec(request).map_err(|e| AppError::Internal(e.to_string()))?;
+    let mut hasher = Sha256::new();
+    hasher.update(scope.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(&body);
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+impl IdempotencyKey {
+    /// 以幂等方式执行 `handler`
+    ///
+    /// 未携带幂等键时直接执行。首次请求成功后保存响应；失败时释放幂等键，允许客户端修正后重试。
+    /// `handler` 内的通知等副作用只会在首次执行时发生。
+    pub async fn run<T, R, F, Fut>(
+        self,
+        db: &DbConn,
+        user_id: Uuid,
+        scope: &str,
+        request: &R,
+        handler: F,
+    ) -> Result<ApiResponse<T>>
+    where
+        T: Serialize + DeserializeOwned,
+        R: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<ApiResponse<T>>>,
+    {
+        let Some(key) = self.0 else {
+            return handler().await;
+        };
+        let hash = request_hash(scope, request)?;
+
+        // 清理该键已过期的记录；处理中的键不提前回收，避免首次请求仍在执行时重复执行
+        let now = Utc::now();
+        idempotency_key::Entity::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Key.eq(&key))
+            .filter(Column::CreatedAt.lt(now - Duration::hours(TTL_HOURS)))
+            .exec(db)
+            .await?;
+
+        // 先占用幂等键，唯一约束保证并发重试只有一个会执行
+        let id = Uuid::new_v4();
+        let claimed = idempotency_key::ActiveModel {
+            id: Set(id),
+            user_id: Set(user_id),
+            key: Set(key.clone()),
+            scope: Set(scope.to_string()),
+            request_hash: Set(hash.clone()),
+            response: Set(None),
+            created_at: Set(now),
+        }
+        .insert(db)
+        .await;
+
+        match claimed {
+            Ok(_) => {}
+            Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                return replay(db, user_id, &key, scope, &hash).await;
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        let result = handler().await;
+        match &result {
+            Ok(response) => {
+                let value = serde_json::to_value(response)
+                    .map_err(|e| AppError::Internal(e.to_string()))?;
+                idempotency_key::ActiveModel {
+                    id: Set(id),
+                    response: Set(Some(value)),
+                    ..Default::default()
+                }
+                .update(db)
+                .await?;
+            }
+            Err(_) => {
+                idempotency_key::Entity::delete_by_id(id).exec(db).await?;
+            }
+        }
+        result
+    }
+}
+
+/// 返回已保存的响应；请求内容不一致或首次请求仍在处理时返回冲突
+async fn replay<T: DeserializeOwned>(
+    db: &DbConn,
+    user_id: Uuid,
+    key: &str,
+    scope: &str,
+    hash: &str,
+) -> Result<ApiResponse<T>> {
+    let existing = idempotency_key::Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::Key.eq(key))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::Conflict("请求处理中，请稍后重试".to_string()))?;
+
+    if existing.scope != scope || existing.request_hash != hash {
+        return Err(AppError::Conflict(
+            "Idempotency-Key 已用于其他请求".to_string(),
+        ));
+    }
+    let response = existing
+        .response
+        .ok_or_else(|| AppError::Conflict("相同请求正在处理中，请稍后重试".to_string()))?;
+    serde_json::from_value(response).map_err(|e| AppError::Internal(e.to_string()))
+}
@@ -5,6 +5,7 @@ pub mod bootstrap;
 pub mod chat;
 pub mod common;
 pub mod error;
+pub mod idempotency;
 pub mod mcp;
 pub mod s3;
 pub mod service;
@@ -12,7 +13,7 @@ pub mod state;
 pub mod traits;
 
 // Re-exports
-pub use bootstrap::init_super_admin;
+pub use bootstrap::{backfill_price_versions, ensure_partial_indexes, init_super_admin};
 pub use s3::S3Client;
 pub use service::notification::{Notification, Notifier};
 pub use state::{anthropic, AppState, InviteCodes};
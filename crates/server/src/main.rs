@@ -1,7 +1,8 @@
 use axum::{routing::get, Router};
 use sea_orm::Database;
 use stitchwork_server::{
-    anthropic, init_super_admin, s3::S3Config, service, AppState, S3Client,
+    anthropic, backfill_price_versions, ensure_partial_indexes, init_super_admin, s3::S3Config,
+    service, AppState, S3Client,
 };
 use tower_http::cors::{Any, CorsLayer};
 use tracing::level_filters::LevelFilter;
@@ -26,6 +27,14 @@ async fn main() {
         .sync(&db)
         .await
         .expect("Failed to sync schema");
+    if let Err(e) = ensure_partial_indexes(&db).await {
+        tracing::error!("Failed to create partial indexes: {}", e);
+    }
+
+    // 旧工序补单价版本
+    if let Err(e) = backfill_price_versions(&db).await {
+        tracing::error!("Failed to backfill price versions: {}", e);
+    }
 
     // 初始化超级管理员
     if let Err(e) = init_super_admin(&db).await {
@@ -45,9 +54,20 @@ async fn main() {
         .build()
         .unwrap();
 
+    // PDF 工资条需要中文字体，启动时加载
+    if service::payroll::payslip::font().is_none() {
+        tracing::warn!("PAYSLIP_FONT_PATH is not set and no system CJK font was found, payslip download is disabled");
+    }
+
     // 应用状态
     let state = AppState::new(db, s3, rig_client);
 
+    // 自动审核日报定时任务
+    service::piece_record::digest::spawn(state.clone());
+
+    // 单价版本到期同步
+    service::process::price_sync::spawn(state.clone());
+
     // CORS
     let cors = CorsLayer::new()
         .allow_origin(Any)
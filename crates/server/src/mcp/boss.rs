@@ -3,13 +3,20 @@ use crate::common::QueryParams;
 use crate::service::{
     home::{dto::BossOverview, service as home_service},
     order::{dto::OrderQueryParams, service as order_service},
+    payroll::{
+        dto::{PayrollPreviewParams, PayrollPreviewResponse},
+        service as payroll_service,
+    },
     piece_record::{dto::PieceRecordResponse, service as piece_record_service},
     stats::{
-        dto::{OrderProgressList, OrderStatsParams, WorkerProductionList, WorkerStatsParams},
+        dto::{
+            OrderProgressList, OrderStatsParams, ProfitParams, ProfitSummary,
+            WorkerProductionList, WorkerStatsParams,
+        },
         service as stats_service,
     },
 };
-use crate::{error::AppError, service::auth::Claims};
+use crate::service::auth::Claims;
 use rmcp::{
     handler::server::{tool::ToolRouter, wrapper::Parameters},
     model::{ErrorData, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo},
@@ -19,7 +26,6 @@ use schemars::JsonSchema;
 use sea_orm::DbConn;
 use serde::Deserialize;
 use tracing::instrument;
-use uuid::Uuid;
 
 // ============ 工具参数定义 ============
 
@@ -95,6 +101,26 @@ pub struct GetUnpaidSummaryParams {
     /// 员工姓名关键词，不传则查询所有员工
     #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
     pub user_name: Option<String>,
+    /// 开始日期（按计件时间和考勤日期），格式 YYYY-MM-DD。不指定则不限制
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub start_date: Option<String>,
+    /// 结束日期（按计件时间和考勤日期），格式 YYYY-MM-DD。不指定则不限制
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub end_date: Option<String>,
+}
+
+/// 获取利润汇总参数
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProfitSummaryParams {
+    /// 开始日期（按接单时间），格式 YYYY-MM-DD。不指定则不限制
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub start_date: Option<String>,
+    /// 结束日期（按接单时间），格式 YYYY-MM-DD。不指定则不限制
+    #[serde(default, deserialize_with = "deserialize_empty_string_as_none")]
+    pub end_date: Option<String>,
+    /// 是否把待审核计件计入人工成本，默认 false
+    pub include_pending: Option<bool>,
 }
 
 // ============ 响应类型 ============
@@ -119,28 +145,6 @@ pub struct PieceRecordListResponse {
     pub total: u64,
 }
 
-/// 待发工资汇总项
-#[derive(Debug, serde::Serialize, JsonSchema)]
-#[serde(rename_all = "camelCase")]
-pub struct UnpaidSummaryItem {
-    /// 员工ID
-    pub user_id: Uuid,
-    /// 员工姓名
-    pub user_name: String,
-    /// 待发数量（件）
-    pub total_quantity: i64,
-    /// 待发金额（元）
-    pub total_amount: rust_decimal::Decimal,
-}
-
-/// 待发工资汇总响应
-#[derive(Debug, serde::Serialize, JsonSchema)]
-#[serde(rename_all = "camelCase")]
-pub struct UnpaidSummaryResponse {
-    /// 各员工待发工资汇总列表
-    pub list: Vec<UnpaidSummaryItem>,
-}
-
 // ============ MCP 工具实现 ============
 
 pub struct BossMcp {
@@ -249,69 +253,36 @@ impl BossMcp {
     }
 
     /// 获取待发工资汇总
-    #[tool(description = "获取待发工资汇总，统计已批准但未结算的计件记录")]
+    #[tool(description = "获取待发工资汇总，按员工统计已批准但未结算的计件（按订单和工序分组）和未结算的考勤，并给出扣除借支后的建议发放金额")]
     pub async fn get_unpaid_summary(
         &self,
         Parameters(params): Parameters<GetUnpaidSummaryParams>,
-    ) -> Result<Json<UnpaidSummaryResponse>, ErrorData> {
-        use entity::piece_record::{self, PieceRecordStatus};
-        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
-        use std::collections::HashMap;
-
-        let query = piece_record::Entity::find()
-            .filter(piece_record::Column::BossId.eq(self.claims.sub))
-            .filter(piece_record::Column::Status.eq(PieceRecordStatus::Approved));
-
-        let records = query.all(&self.db).await.map_err(AppError::from)?;
-
-        // 按用户分组统计
-        let mut user_stats: HashMap<Uuid, (i64, rust_decimal::Decimal)> = HashMap::new();
-        for rec in &records {
-            let entry = user_stats
-                .entry(rec.user_id)
-                .or_insert((0, rust_decimal::Decimal::ZERO));
-            entry.0 += rec.quantity as i64;
-            entry.1 += rec.amount;
-        }
-
-        // 批量获取用户信息
-        let user_ids: Vec<Uuid> = user_stats.keys().copied().collect();
-        let users: HashMap<Uuid, entity::user::Model> = entity::user::Entity::find()
-            .filter(entity::user::Column::Id.is_in(user_ids))
-            .all(&self.db)
-            .await
-            .map_err(AppError::from)?
-            .into_iter()
-            .map(|u| (u.id, u))
-            .collect();
-
-        // 按名称筛选
-        let name_filter = params.user_name.map(|n| n.to_lowercase());
+    ) -> Result<Json<PayrollPreviewResponse>, ErrorData> {
+        let preview_params = PayrollPreviewParams {
+            start_date: params.start_date,
+            end_date: params.end_date,
+            user_id: None,
+            user_name: params.user_name,
+        };
 
-        let list = user_stats
-            .into_iter()
-            .filter_map(|(user_id, (qty, amt))| {
-                let user = users.get(&user_id)?;
-                let name = user
-                    .display_name
-                    .clone()
-                    .unwrap_or_else(|| user.username.clone());
+        let result = payroll_service::preview(&self.db, self.claims.sub, preview_params).await?;
+        Ok(Json(result))
+    }
 
-                // 如果有名称筛选，检查是否匹配
-                if let Some(ref filter) = name_filter
-                    && !name.to_lowercase().contains(filter) {
-                        return None;
-                    }
+    /// 获取订单利润汇总
+    #[tool(description = "获取订单利润汇总：收入（单价×数量）对比人工成本（计件金额），包括每个订单和每个客户的毛利及毛利率，用于回答“这个订单/客户赚不赚钱”")]
+    pub async fn get_profit_summary(
+        &self,
+        Parameters(params): Parameters<GetProfitSummaryParams>,
+    ) -> Result<Json<ProfitSummary>, ErrorData> {
+        let profit_params = ProfitParams {
+            start_date: params.start_date,
+            end_date: params.end_date,
+            include_pending: params.include_pending.unwrap_or(false),
+        };
 
-                Some(UnpaidSummaryItem {
-                    user_id,
-                    user_name: name,
-                    total_quantity: qty,
-                    total_amount: amt,
-                })
-            })
-            .collect();
-        Ok(Json(UnpaidSummaryResponse { list }))
+        let result = stats_service::profit_summary(&self.db, self.claims.sub, profit_params).await?;
+        Ok(Json(result))
     }
 }
 
@@ -330,7 +301,8 @@ impl ServerHandler for BossMcp {
                 - get_worker_stats: 获取员工产量统计\n\
                 - get_overview: 获取首页概览数据\n\
                 - get_order_progress: 获取订单进度\n\
-                - get_unpaid_summary: 获取待发工资汇总"
+                - get_unpaid_summary: 获取待发工资汇总\n\
+                - get_profit_summary: 获取订单/客户利润汇总"
                     .into(),
             ),
         }
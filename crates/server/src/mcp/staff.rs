@@ -1,8 +1,13 @@
 use super::deserialize_empty_string_as_none;
 use crate::common::QueryParams;
 use crate::service::{
+    advance::service as advance_service,
     payroll::service as payroll_service,
-    piece_record::{dto::PieceRecordResponse, service as piece_record_service},
+    piece_record::{
+        dto::{PieceRecordResponse, UpdatePieceRecordDto},
+        service as piece_record_service,
+    },
+    process::service as process_service,
     stats::{dto::WorkerStatsParams, service as stats_service},
 };
 use crate::{error::AppError, service::auth::Claims};
@@ -69,6 +74,19 @@ pub struct GetMyPayrollsParams {
     pub page_size: Option<u64>,
 }
 
+/// 修改或撤回我的计件记录参数
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EditMyRecordParams {
+    /// 计件记录ID，可通过 get_my_records 获取，只能修改待审核的记录
+    pub record_id: Uuid,
+    /// 修改后的数量，撤回时无需填写
+    pub quantity: Option<i32>,
+    /// 是否撤回（删除）该记录，默认 false
+    #[serde(default)]
+    pub withdraw: bool,
+}
+
 // ============ 响应类型 ============
 
 /// 我的计件记录列表响应
@@ -105,6 +123,8 @@ pub struct PayrollListResponse {
     pub list: Vec<entity::payroll::Model>,
     /// 总记录数
     pub total: u64,
+    /// 未还借支（元），会从后续工资中扣回
+    pub advance_outstanding: Decimal,
 }
 
 /// 可接工序任务
@@ -127,6 +147,18 @@ pub struct AvailableTask {
     pub remaining_quantity: i32,
 }
 
+/// 修改或撤回计件记录结果
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EditMyRecordResponse {
+    /// 是否已撤回
+    pub withdrawn: bool,
+    /// 修改后的记录，撤回时为空
+    pub record: Option<PieceRecordResponse>,
+    /// 超产等提醒
+    pub warning: Option<String>,
+}
+
 /// 可接工序列表响应
 #[derive(Debug, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -221,7 +253,7 @@ impl StaffMcp {
     }
 
     /// 查询我的工资单
-    #[tool(description = "查询我的工资单列表")]
+    #[tool(description = "查询我的工资单列表，同时返回未还借支余额")]
     pub async fn get_my_payrolls(
         &self,
         Parameters(params): Parameters<GetMyPayrollsParams>,
@@ -236,14 +268,20 @@ impl StaffMcp {
 
         let result =
             payroll_service::list(&self.db, query_params, Some(self.claims.sub), None).await?;
+        let advance_outstanding = advance_service::balances(&self.db, &self.claims)
+            .await?
+            .into_iter()
+            .map(|b| b.outstanding)
+            .sum();
         Ok(Json(PayrollListResponse {
             list: result.list,
             total: result.total,
+            advance_outstanding,
         }))
     }
 
     /// 查看可接工序
-    #[tool(description = "查看工坊内进行中订单的可接工序列表")]
+    #[tool(description = "查看工坊内进行中订单中分配给我的可接工序列表")]
     pub async fn get_available_tasks(&self) -> Result<Json<AvailableTasksResponse>, ErrorData> {
         use std::collections::HashMap;
 
@@ -280,9 +318,11 @@ impl StaffMcp {
         let orders_map: HashMap<Uuid, order::Model> =
             orders.into_iter().map(|o| (o.id, o)).collect();
 
-        // 查询这些订单的工序
+        // 查询这些订单中分配给本人的工序
         let processes = process::Entity::find()
             .filter(process::Column::OrderId.is_in(order_ids.clone()))
+            .filter(process_service::assigned_condition(&staff))
+            .order_by_asc(process::Column::Sequence)
             .order_by_asc(process::Column::Name)
             .all(&self.db)
             .await
@@ -299,6 +339,7 @@ impl StaffMcp {
                 piece_record::Column::Status
                     .is_in([PieceRecordStatus::Approved, PieceRecordStatus::Settled]),
             )
+            .filter(piece_record::Column::AdjustsRecordId.is_null())
             .group_by(piece_record::Column::ProcessId)
             .into_tuple()
             .all(&self.db)
@@ -343,6 +384,42 @@ impl StaffMcp {
 
         Ok(Json(AvailableTasksResponse { list }))
     }
+
+    /// 修改或撤回我的计件记录
+    #[tool(description = "修改或撤回我提交的待审核计件记录。修改时传入新的数量；撤回时设置 withdraw 为 true。已审核的记录不能修改，请联系老板处理。")]
+    pub async fn edit_my_record(
+        &self,
+        Parameters(params): Parameters<EditMyRecordParams>,
+    ) -> Result<Json<EditMyRecordResponse>, ErrorData> {
+        if params.withdraw {
+            piece_record_service::delete(&self.db, params.record_id, &self.claims).await?;
+            return Ok(Json(EditMyRecordResponse {
+                withdrawn: true,
+                record: None,
+                warning: None,
+            }));
+        }
+
+        let quantity = params
+            .quantity
+            .ok_or_else(|| AppError::BadRequest("请提供修改后的数量".into()))?;
+        let (record, warning) = piece_record_service::update(
+            &self.db,
+            params.record_id,
+            UpdatePieceRecordDto {
+                quantity: Some(quantity),
+                images: None,
+            },
+            &self.claims,
+        )
+        .await?;
+        let record = piece_record_service::get_one(&self.db, record.id, &self.claims).await?;
+        Ok(Json(EditMyRecordResponse {
+            withdrawn: false,
+            record: Some(record),
+            warning,
+        }))
+    }
 }
 
 #[tool_handler]
@@ -357,8 +434,9 @@ impl ServerHandler for StaffMcp {
                 可用工具：\n\
                 - get_my_records: 查询我的计件记录\n\
                 - get_my_earnings: 查询我的收入统计\n\
-                - get_my_payrolls: 查询我的工资单\n\
+                - get_my_payrolls: 查询我的工资单和未还借支\n\
                 - get_available_tasks: 查看可接工序\n\
+                - edit_my_record: 修改或撤回待审核的计件记录\n\
                 \n\
                 注意：这些查询工具的结果会通过专用的 UI 组件在界面中展示，你无需在回复中重复列出详细数据。只需简要确认查询结果，或者提供简单的总结/分析即可。"
                     .into(),
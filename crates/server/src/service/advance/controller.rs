@@ -0,0 +1,87 @@
+use axum::extract::{Query, State};
+use axum::{Extension, Router};
+use axum_extra::routing::{RouterExt, TypedPath};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::dto::{AdvanceBalance, AdvanceFilter, CreateAdvanceDto};
+use crate::common::{ApiResponse, ListData, QueryParams};
+use crate::error::{AppJson, Result};
+use crate::service::auth::Claims;
+use crate::service::notification::Notification;
+use crate::AppState;
+use entity::advance::Model;
+
+use super::service;
+
+#[derive(TypedPath)]
+#[typed_path("/advances")]
+pub struct AdvancesPath;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/advances/{id}")]
+pub struct AdvancePath {
+    id: Uuid,
+}
+
+#[derive(TypedPath)]
+#[typed_path("/advances/balances")]
+pub struct AdvanceBalancesPath;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .typed_get(list)
+        .typed_post(create)
+        .typed_delete(delete)
+        .typed_get(balances)
+}
+
+async fn list(
+    _: AdvancesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<QueryParams>,
+    Query(filter): Query<AdvanceFilter>,
+) -> Result<ApiResponse<ListData<Model>>> {
+    Ok(ApiResponse::ok(
+        service::list(&state.db, params, filter, &claims).await?,
+    ))
+}
+
+async fn create(
+    _: AdvancesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<CreateAdvanceDto>,
+) -> Result<ApiResponse<Model>> {
+    claims.require_boss()?;
+    let advance = service::create(&state.db, dto, claims.sub).await?;
+
+    state.notifier.send(
+        advance.user_id,
+        Notification::AdvanceRecorded {
+            amount: advance.amount.to_string(),
+        },
+    );
+
+    Ok(ApiResponse::ok(advance))
+}
+
+async fn delete(
+    AdvancePath { id }: AdvancePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<()>> {
+    claims.require_boss()?;
+    service::delete(&state.db, id, claims.sub).await?;
+    Ok(ApiResponse::ok(()))
+}
+
+async fn balances(
+    _: AdvanceBalancesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Vec<AdvanceBalance>>> {
+    Ok(ApiResponse::ok(service::balances(&state.db, &claims).await?))
+}
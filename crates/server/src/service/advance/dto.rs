@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 登记借支请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAdvanceDto {
+    /// 员工ID
+    pub user_id: Uuid,
+    /// 借支金额（元）
+    pub amount: Decimal,
+    /// 借支时间，不传则为当前时间
+    pub advanced_at: Option<DateTime<Utc>>,
+    /// 备注
+    pub note: Option<String>,
+    /// 凭证图片URL
+    pub image: Option<String>,
+}
+
+/// 借支列表筛选
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdvanceFilter {
+    /// 员工ID，仅老板可用
+    pub user_id: Option<Uuid>,
+}
+
+/// 员工借支余额
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdvanceBalance {
+    /// 员工ID
+    pub user_id: Uuid,
+    /// 员工姓名（关联字段）
+    pub user_name: Option<String>,
+    /// 累计借支（元）
+    pub advanced: Decimal,
+    /// 累计已还（元），来自未作废工资单的借支还款明细
+    pub repaid: Decimal,
+    /// 未还借支（元）
+    pub outstanding: Decimal,
+}
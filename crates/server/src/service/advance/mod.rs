@@ -0,0 +1,5 @@
+mod controller;
+pub mod dto;
+pub mod service;
+
+pub use controller::router;
@@ -0,0 +1,189 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set,
+};
+use std::collections::{BTreeSet, HashMap};
+use uuid::Uuid;
+
+use super::dto::{AdvanceBalance, AdvanceFilter, CreateAdvanceDto};
+use crate::common::{apply_date_filter, ListData, QueryParams};
+use crate::error::{AppError, Result};
+use crate::service::auth::Claims;
+use crate::traits::OwnedByBoss;
+use entity::advance::{self, Column, Model};
+use entity::payroll_item::{self, PayrollItemKind};
+use entity::user::{self, Role};
+use entity::{payroll, workshop};
+
+pub async fn list(
+    db: &DbConn,
+    params: QueryParams,
+    filter: AdvanceFilter,
+    claims: &Claims,
+) -> Result<ListData<Model>> {
+    let mut query = advance::Entity::find();
+    query = match claims.role {
+        Role::Staff => query.filter(Column::UserId.eq(claims.sub)),
+        Role::Boss => {
+            let query = query.filter(Column::BossId.eq(claims.sub));
+            match filter.user_id {
+                Some(uid) => query.filter(Column::UserId.eq(uid)),
+                None => query,
+            }
+        }
+    };
+    query = apply_date_filter(
+        query,
+        Column::AdvancedAt,
+        params.start_date.as_deref(),
+        params.end_date.as_deref(),
+    );
+
+    let paginator = query
+        .order_by_desc(Column::AdvancedAt)
+        .paginate(db, params.page_size);
+    let total = paginator.num_items().await?;
+    let list = paginator.fetch_page(params.page.saturating_sub(1)).await?;
+    Ok(ListData { list, total })
+}
+
+/// 登记借支，员工需属于本工坊
+pub async fn create(db: &DbConn, dto: CreateAdvanceDto, boss_id: Uuid) -> Result<Model> {
+    if dto.amount <= Decimal::ZERO {
+        return Err(AppError::BadRequest("借支金额必须大于0".to_string()));
+    }
+    let ws = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("请先创建工坊".to_string()))?;
+    user::Entity::find_by_id(dto.user_id)
+        .filter(user::Column::WorkshopId.eq(ws.id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("员工不属于本工坊".to_string()))?;
+
+    let model = advance::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(dto.user_id),
+        boss_id: Set(boss_id),
+        amount: Set(dto.amount.round_dp(2)),
+        advanced_at: Set(dto.advanced_at.unwrap_or_else(Utc::now)),
+        note: Set(dto.note.filter(|s| !s.trim().is_empty())),
+        image: Set(dto.image.filter(|s| !s.trim().is_empty())),
+        created_at: Set(Utc::now()),
+    };
+    Ok(model.insert(db).await?)
+}
+
+/// 删除借支，已被工资单扣回的部分不能删除
+pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
+    let adv = advance::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("借支记录不存在".to_string()))?;
+    adv.verify_owner(boss_id)?;
+
+    if outstanding(db, boss_id, adv.user_id).await? < adv.amount {
+        return Err(AppError::BadRequest(
+            "该借支已通过工资单扣回，无法删除".to_string(),
+        ));
+    }
+    advance::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// 借支余额：老板查看本工坊有借支的全部员工，员工查看自己
+pub async fn balances(db: &DbConn, claims: &Claims) -> Result<Vec<AdvanceBalance>> {
+    let (boss_id, user_ids) = match claims.role {
+        Role::Staff => (None, vec![claims.sub]),
+        Role::Boss => {
+            let ids: BTreeSet<Uuid> = advance::Entity::find()
+                .filter(Column::BossId.eq(claims.sub))
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|a| a.user_id)
+                .collect();
+            (Some(claims.sub), ids.into_iter().collect())
+        }
+    };
+
+    let totals = totals(db, boss_id, &user_ids).await?;
+    let names: HashMap<Uuid, String> = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.display_name.unwrap_or(u.username)))
+        .collect();
+
+    Ok(user_ids
+        .into_iter()
+        .map(|uid| {
+            let (advanced, repaid) = totals.get(&uid).copied().unwrap_or_default();
+            AdvanceBalance {
+                user_id: uid,
+                user_name: names.get(&uid).cloned(),
+                advanced,
+                repaid,
+                outstanding: advanced - repaid,
+            }
+        })
+        .collect())
+}
+
+/// 员工在该老板处的未还借支
+pub(crate) async fn outstanding<C: ConnectionTrait>(
+    conn: &C,
+    boss_id: Uuid,
+    user_id: Uuid,
+) -> Result<Decimal> {
+    let totals = totals(conn, Some(boss_id), &[user_id]).await?;
+    let (advanced, repaid) = totals.get(&user_id).copied().unwrap_or_default();
+    Ok(advanced - repaid)
+}
+
+/// 各员工的累计借支和累计已还
+pub(crate) async fn totals<C: ConnectionTrait>(
+    conn: &C,
+    boss_id: Option<Uuid>,
+    user_ids: &[Uuid],
+) -> Result<HashMap<Uuid, (Decimal, Decimal)>> {
+    let mut totals: HashMap<Uuid, (Decimal, Decimal)> = HashMap::new();
+
+    let mut query = advance::Entity::find().filter(Column::UserId.is_in(user_ids.to_vec()));
+    if let Some(bid) = boss_id {
+        query = query.filter(Column::BossId.eq(bid));
+    }
+    for adv in query.all(conn).await? {
+        totals.entry(adv.user_id).or_default().0 += adv.amount;
+    }
+
+    let mut query = payroll::Entity::find()
+        .filter(payroll::Column::UserId.is_in(user_ids.to_vec()))
+        .filter(payroll::Column::VoidedAt.is_null());
+    if let Some(bid) = boss_id {
+        query = query.filter(payroll::Column::BossId.eq(bid));
+    }
+    let payroll_users: HashMap<Uuid, Uuid> = query
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.user_id))
+        .collect();
+    let repayments = payroll_item::Entity::find()
+        .filter(payroll_item::Column::PayrollId.is_in(payroll_users.keys().copied()))
+        .filter(payroll_item::Column::Kind.eq(PayrollItemKind::AdvanceRepayment))
+        .all(conn)
+        .await?;
+    for item in repayments {
+        if let Some(uid) = payroll_users.get(&item.payroll_id) {
+            // 借支还款明细为负数
+            totals.entry(*uid).or_default().1 -= item.amount;
+        }
+    }
+    Ok(totals)
+}
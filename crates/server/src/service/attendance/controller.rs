@@ -0,0 +1,114 @@
+use axum::extract::{Query, State};
+use axum::{Extension, Router};
+use axum_extra::routing::{RouterExt, TypedPath};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::dto::{AttendanceFilter, CreateAttendanceDto, UpdateAttendanceDto};
+use crate::common::{ApiResponse, ListData, QueryParams};
+use crate::error::{AppError, AppJson, Result};
+use crate::service::auth::Claims;
+use crate::AppState;
+use entity::attendance::Model;
+use entity::user::Role;
+
+use super::service;
+
+#[derive(TypedPath)]
+#[typed_path("/attendance")]
+pub struct AttendancesPath;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/attendance/{id}")]
+pub struct AttendancePath {
+    id: Uuid,
+}
+
+#[derive(TypedPath)]
+#[typed_path("/attendance/clock-in")]
+pub struct ClockInPath;
+
+#[derive(TypedPath)]
+#[typed_path("/attendance/clock-out")]
+pub struct ClockOutPath;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .typed_get(list)
+        .typed_post(create)
+        .typed_put(update)
+        .typed_delete(delete)
+        .typed_post(clock_in)
+        .typed_post(clock_out)
+}
+
+async fn list(
+    _: AttendancesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<QueryParams>,
+    Query(filter): Query<AttendanceFilter>,
+) -> Result<ApiResponse<ListData<Model>>> {
+    Ok(ApiResponse::ok(
+        service::list(&state.db, params, filter, &claims).await?,
+    ))
+}
+
+async fn create(
+    _: AttendancesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<CreateAttendanceDto>,
+) -> Result<ApiResponse<Model>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::create(&state.db, dto, claims.sub).await?,
+    ))
+}
+
+async fn update(
+    AttendancePath { id }: AttendancePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<UpdateAttendanceDto>,
+) -> Result<ApiResponse<Model>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::update(&state.db, id, dto, claims.sub).await?,
+    ))
+}
+
+async fn delete(
+    AttendancePath { id }: AttendancePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<()>> {
+    claims.require_boss()?;
+    service::delete(&state.db, id, claims.sub).await?;
+    Ok(ApiResponse::ok(()))
+}
+
+/// 员工上班打卡
+async fn clock_in(
+    _: ClockInPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Model>> {
+    if claims.role != Role::Staff {
+        return Err(AppError::Forbidden);
+    }
+    Ok(ApiResponse::ok(service::clock_in(&state.db, claims.sub).await?))
+}
+
+/// 员工下班打卡
+async fn clock_out(
+    _: ClockOutPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Model>> {
+    if claims.role != Role::Staff {
+        return Err(AppError::Forbidden);
+    }
+    Ok(ApiResponse::ok(service::clock_out(&state.db, claims.sub).await?))
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// 补录考勤请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAttendanceDto {
+    /// 员工ID
+    pub user_id: Uuid,
+    /// 出勤日期，格式 YYYY-MM-DD
+    pub work_date: NaiveDate,
+    /// 工时（小时），按时薪计算的员工填写
+    pub hours: Option<Decimal>,
+    /// 出勤天数，全天为1，半天为0.5，按日薪计算的员工填写
+    pub days: Option<Decimal>,
+    /// 备注
+    pub note: Option<String>,
+}
+
+/// 修改考勤请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAttendanceDto {
+    /// 工时（小时）
+    pub hours: Option<Decimal>,
+    /// 出勤天数
+    pub days: Option<Decimal>,
+    /// 备注
+    pub note: Option<String>,
+}
+
+/// 考勤列表筛选
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttendanceFilter {
+    /// 员工ID，仅老板可用
+    pub user_id: Option<Uuid>,
+}
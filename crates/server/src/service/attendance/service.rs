@@ -0,0 +1,264 @@
+use chrono::{Local, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    Set,
+};
+use uuid::Uuid;
+
+use super::dto::{AttendanceFilter, CreateAttendanceDto, UpdateAttendanceDto};
+use crate::common::{apply_date_filter, ListData, QueryParams};
+use crate::error::{AppError, Result};
+use crate::service::auth::Claims;
+use crate::traits::OwnedByBoss;
+use entity::attendance::{self, Column, Model};
+use entity::user::{self, Role};
+use entity::workshop;
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 校验工时和出勤天数，至少填写一项
+fn validate_time(hours: Decimal, days: Decimal) -> Result<()> {
+    if hours < Decimal::ZERO || hours > Decimal::from(24) {
+        return Err(AppError::BadRequest("工时需在0到24小时之间".to_string()));
+    }
+    if days < Decimal::ZERO || days > Decimal::ONE {
+        return Err(AppError::BadRequest("出勤天数需在0到1之间".to_string()));
+    }
+    if hours.is_zero() && days.is_zero() {
+        return Err(AppError::BadRequest("请填写工时或出勤天数".to_string()));
+    }
+    Ok(())
+}
+
+/// 员工所在工坊的老板
+async fn staff_boss(db: &DbConn, user_id: Uuid) -> Result<(user::Model, Uuid)> {
+    let staff = user::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::Forbidden)?;
+    let workshop_id = staff
+        .workshop_id
+        .ok_or_else(|| AppError::BadRequest("请先加入工坊".to_string()))?;
+    let ws = workshop::Entity::find_by_id(workshop_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::Forbidden)?;
+    Ok((staff, ws.owner_id))
+}
+
+/// 本工坊员工
+async fn workshop_staff(db: &DbConn, boss_id: Uuid, user_id: Uuid) -> Result<user::Model> {
+    let ws = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("请先创建工坊".to_string()))?;
+    user::Entity::find_by_id(user_id)
+        .filter(user::Column::WorkshopId.eq(ws.id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("员工不属于本工坊".to_string()))
+}
+
+async fn find_editable(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<Model> {
+    let att = attendance::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("考勤记录不存在".to_string()))?;
+    att.verify_owner(boss_id)?;
+    if att.is_settled() {
+        return Err(AppError::BadRequest(
+            "该考勤已结算到工资单，不能修改".to_string(),
+        ));
+    }
+    Ok(att)
+}
+
+pub async fn list(
+    db: &DbConn,
+    params: QueryParams,
+    filter: AttendanceFilter,
+    claims: &Claims,
+) -> Result<ListData<Model>> {
+    let mut query = attendance::Entity::find();
+    query = match claims.role {
+        Role::Staff => query.filter(Column::UserId.eq(claims.sub)),
+        Role::Boss => {
+            let query = query.filter(Column::BossId.eq(claims.sub));
+            match filter.user_id {
+                Some(uid) => query.filter(Column::UserId.eq(uid)),
+                None => query,
+            }
+        }
+    };
+    query = apply_date_filter(
+        query,
+        Column::WorkDate,
+        params.start_date.as_deref(),
+        params.end_date.as_deref(),
+    );
+
+    let paginator = query
+        .order_by_desc(Column::WorkDate)
+        .paginate(db, params.page_size);
+    let total = paginator.num_items().await?;
+    let list = paginator.fetch_page(params.page.saturating_sub(1)).await?;
+    Ok(ListData { list, total })
+}
+
+/// 员工上班打卡，每天只能打卡一次
+pub async fn clock_in(db: &DbConn, user_id: Uuid) -> Result<Model> {
+    let (_, boss_id) = staff_boss(db, user_id).await?;
+    let today = Local::now().date_naive();
+    let existing = attendance::Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::WorkDate.eq(today))
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        return Err(AppError::Conflict("今天已有考勤记录".to_string()));
+    }
+    // 之前忘记下班打卡的记录须先处理，否则下班打卡会落到旧记录上
+    let open = attendance::Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::ClockIn.is_not_null())
+        .filter(Column::ClockOut.is_null())
+        .one(db)
+        .await?;
+    if let Some(open) = open {
+        return Err(AppError::Conflict(format!(
+            "{} 的打卡还未下班，请先下班打卡或联系老板补录",
+            open.work_date
+        )));
+    }
+
+    let model = attendance::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(user_id),
+        boss_id: Set(boss_id),
+        work_date: Set(today),
+        clock_in: Set(Some(Utc::now())),
+        clock_out: Set(None),
+        hours: Set(Decimal::ZERO),
+        days: Set(Decimal::ZERO),
+        amount: Set(Decimal::ZERO),
+        note: Set(None),
+        payroll_id: Set(None),
+        created_at: Set(Utc::now()),
+    };
+    Ok(model.insert(db).await?)
+}
+
+/// 员工下班打卡，按打卡时长计算工时，记为出勤一天
+pub async fn clock_out(db: &DbConn, user_id: Uuid) -> Result<Model> {
+    let (staff, _) = staff_boss(db, user_id).await?;
+    let att = attendance::Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .filter(Column::ClockIn.is_not_null())
+        .filter(Column::ClockOut.is_null())
+        .order_by_desc(Column::WorkDate)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("没有未下班的打卡记录".to_string()))?;
+
+    let now = Utc::now();
+    let minutes = att
+        .clock_in
+        .map(|t| (now - t).num_minutes().max(0))
+        .unwrap_or_default();
+    let hours = (Decimal::from(minutes) / Decimal::from(60)).round_dp(2);
+    let days = Decimal::ONE;
+    // 忘记下班打卡导致的超长时段不按打卡时长计薪，保持未下班，由老板核对后修改
+    if hours > Decimal::from(24) {
+        return Err(AppError::Conflict(format!(
+            "{} 的打卡已超过 24 小时，请联系老板核对工时后补录",
+            att.work_date
+        )));
+    }
+    validate_time(hours, days)?;
+
+    let mut model: attendance::ActiveModel = att.into();
+    model.clock_out = Set(Some(now));
+    model.hours = Set(hours);
+    model.days = Set(days);
+    model.amount = Set(staff.time_wage(hours, days));
+    Ok(model.update(db).await?)
+}
+
+/// 老板补录考勤，如按天出勤或忘记打卡
+pub async fn create(db: &DbConn, dto: CreateAttendanceDto, boss_id: Uuid) -> Result<Model> {
+    let staff = workshop_staff(db, boss_id, dto.user_id).await?;
+    let hours = dto.hours.unwrap_or_default().round_dp(2);
+    let days = dto.days.unwrap_or_default().round_dp(2);
+    validate_time(hours, days)?;
+
+    let existing = attendance::Entity::find()
+        .filter(Column::UserId.eq(dto.user_id))
+        .filter(Column::WorkDate.eq(dto.work_date))
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        return Err(AppError::Conflict(format!(
+            "{} 已有考勤记录",
+            dto.work_date
+        )));
+    }
+
+    let model = attendance::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        user_id: Set(dto.user_id),
+        boss_id: Set(boss_id),
+        work_date: Set(dto.work_date),
+        clock_in: Set(None),
+        clock_out: Set(None),
+        hours: Set(hours),
+        days: Set(days),
+        amount: Set(staff.time_wage(hours, days)),
+        note: Set(normalize(dto.note)),
+        payroll_id: Set(None),
+        created_at: Set(Utc::now()),
+    };
+    Ok(model.insert(db).await?)
+}
+
+/// 修改考勤工时或天数，按员工当前时薪或日薪重新计算金额
+pub async fn update(
+    db: &DbConn,
+    id: Uuid,
+    dto: UpdateAttendanceDto,
+    boss_id: Uuid,
+) -> Result<Model> {
+    let att = find_editable(db, id, boss_id).await?;
+    let staff = user::Entity::find_by_id(att.user_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("员工不存在".to_string()))?;
+    let hours = dto.hours.map(|h| h.round_dp(2)).unwrap_or(att.hours);
+    let days = dto.days.map(|d| d.round_dp(2)).unwrap_or(att.days);
+    validate_time(hours, days)?;
+
+    let note = dto.note.or(att.note.clone());
+    let open = att.is_open();
+    let mut model: attendance::ActiveModel = att.into();
+    if open {
+        // 未下班时由老板修改，视为已下班
+        model.clock_out = Set(Some(Utc::now()));
+    }
+    model.hours = Set(hours);
+    model.days = Set(days);
+    model.amount = Set(staff.time_wage(hours, days));
+    model.note = Set(normalize(note));
+    Ok(model.update(db).await?)
+}
+
+/// 删除考勤，已结算的不能删除
+pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
+    let att = find_editable(db, id, boss_id).await?;
+    attendance::Entity::delete_by_id(att.id).exec(db).await?;
+    Ok(())
+}
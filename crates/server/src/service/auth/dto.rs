@@ -1,4 +1,4 @@
-use entity::user::Role;
+use entity::user::{PayoutAccount, Role};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -35,6 +35,8 @@ pub struct LoginUser {
     pub is_super_admin: bool,
     /// 所属工坊信息
     pub workshop: Option<WorkshopResponse>,
+    /// 工资收款账户
+    pub payout_account: Option<PayoutAccount>,
 }
 
 /// 登录响应
@@ -71,6 +73,8 @@ pub struct UpdateProfileRequest {
     pub phone: Option<String>,
     /// 头像URL
     pub avatar: Option<String>,
+    /// 工资收款账户，收款人或账号留空表示清除
+    pub payout_account: Option<PayoutAccount>,
 }
 
 /// 修改密码请求
@@ -10,7 +10,7 @@ use uuid::Uuid;
 
 use entity::{
     register_code,
-    user::{self, Role},
+    user::{self, PayoutAccount, PayoutMethod, Role},
     workshop,
 };
 use crate::error::{AppError, Result};
@@ -47,6 +47,7 @@ pub async fn login(db: &DbConn, req: LoginRequest) -> Result<LoginResponse> {
         .map_err(|_| AppError::Internal("Token生成失败".to_string()))?;
 
     let workshop = get_workshop_for_user(db, &user).await?;
+    let payout_account = user.payout();
 
     Ok(LoginResponse {
         token,
@@ -59,6 +60,7 @@ pub async fn login(db: &DbConn, req: LoginRequest) -> Result<LoginResponse> {
             avatar: user.avatar,
             is_super_admin: user.is_super_admin,
             workshop,
+            payout_account,
         },
     })
 }
@@ -223,6 +225,7 @@ pub async fn register_staff(
             avatar: user.avatar,
             is_super_admin: user.is_super_admin,
             workshop: ws.as_ref().map(to_response),
+            payout_account: None,
         },
     })
 }
@@ -256,10 +259,30 @@ pub async fn update_profile(db: &DbConn, user_id: Uuid, req: UpdateProfileReques
     if let Some(v) = req.avatar {
         active.avatar = Set(Some(v));
     }
+    if let Some(v) = req.payout_account {
+        active.payout_account = Set(normalize_payout(v)?.map(|a| serde_json::json!(a)));
+    }
     active.update(db).await?;
     Ok(())
 }
 
+/// 校验收款账户，收款人或账号为空时视为清除
+fn normalize_payout(mut account: PayoutAccount) -> Result<Option<PayoutAccount>> {
+    account.account_name = account.account_name.trim().to_string();
+    account.account_no = account.account_no.trim().to_string();
+    account.bank_name = account
+        .bank_name
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if account.account_name.is_empty() || account.account_no.is_empty() {
+        return Ok(None);
+    }
+    if account.method == PayoutMethod::Bank && account.bank_name.is_none() {
+        return Err(AppError::BadRequest("银行卡收款请填写开户行".to_string()));
+    }
+    Ok(Some(account))
+}
+
 pub async fn change_password(
     db: &DbConn,
     user_id: Uuid,
@@ -294,6 +317,7 @@ pub async fn get_profile(db: &DbConn, user_id: Uuid) -> Result<LoginUser> {
         .ok_or_else(|| AppError::NotFound("用户不存在".to_string()))?;
 
     let workshop = get_workshop_for_user(db, &user).await?;
+    let payout_account = user.payout();
 
     Ok(LoginUser {
         id: user.id,
@@ -304,6 +328,7 @@ pub async fn get_profile(db: &DbConn, user_id: Uuid) -> Result<LoginUser> {
         avatar: user.avatar,
         is_super_admin: user.is_super_admin,
         workshop,
+        payout_account,
     })
 }
 
@@ -0,0 +1,116 @@
+use axum::extract::State;
+use axum::{Extension, Router};
+use axum_extra::routing::{RouterExt, TypedPath};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::dto::{BundleResponse, GenerateBundlesDto, ScanBundleDto};
+use crate::common::ApiResponse;
+use crate::error::{AppJson, Result};
+use crate::idempotency::IdempotencyKey;
+use crate::service::auth::Claims;
+use crate::service::notification::Notification;
+use crate::service::piece_record::service as piece_record_service;
+use crate::AppState;
+use entity::piece_record::{self, PieceRecordStatus};
+
+use super::service;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/orders/{id}/bundles")]
+pub struct OrderBundlesPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/bundles/{id}")]
+pub struct BundlePath {
+    id: Uuid,
+}
+
+#[derive(TypedPath)]
+#[typed_path("/bundles/scan")]
+pub struct BundleScanPath;
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .typed_get(list)
+        .typed_post(generate)
+        .typed_get(get_one)
+        .typed_delete(delete)
+        .typed_post(scan)
+}
+
+async fn list(
+    OrderBundlesPath { id }: OrderBundlesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Vec<BundleResponse>>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::list_by_order(&state.db, id, claims.sub).await?,
+    ))
+}
+
+async fn generate(
+    OrderBundlesPath { id }: OrderBundlesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<GenerateBundlesDto>,
+) -> Result<ApiResponse<Vec<BundleResponse>>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::generate(&state.db, id, claims.sub, dto).await?,
+    ))
+}
+
+async fn get_one(
+    BundlePath { id }: BundlePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<BundleResponse>> {
+    Ok(ApiResponse::ok(
+        service::get_one(&state.db, id, &claims).await?,
+    ))
+}
+
+async fn delete(
+    BundlePath { id }: BundlePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<()>> {
+    claims.require_boss()?;
+    service::delete(&state.db, id, claims.sub).await?;
+    Ok(ApiResponse::ok(()))
+}
+
+async fn scan(
+    _: BundleScanPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    key: IdempotencyKey,
+    AppJson(dto): AppJson<ScanBundleDto>,
+) -> Result<ApiResponse<piece_record::Model>> {
+    key.run(&state.db, claims.sub, "bundle.scan", &dto, || async {
+        let (record, warning) = service::scan(&state.db, dto.clone(), &claims).await?;
+
+        // 员工扫码计件时通知老板，自动通过的计件汇总在每日日报中
+        if record.status == PieceRecordStatus::Pending {
+            let response = piece_record_service::get_one(&state.db, record.id, &claims).await?;
+            let unit = piece_record_service::get_piece_unit(&state.db, record.boss_id).await;
+            state.notifier.send(
+                record.boss_id,
+                Notification::RecordSubmitted {
+                    user_name: response.user_name.unwrap_or_default(),
+                    process_name: response.process_name.unwrap_or_default(),
+                    quantity: record.quantity,
+                    unit,
+                },
+            );
+        }
+
+        Ok(ApiResponse::ok_with_warning(record, warning))
+    })
+    .await
+}
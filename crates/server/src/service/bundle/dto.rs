@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 裁床分扎规格：同一尺码/颜色的裁片按每扎件数拆分
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSpec {
+    /// 尺码
+    pub size: Option<String>,
+    /// 颜色
+    pub color: Option<String>,
+    /// 该尺码/颜色的裁片总件数
+    pub quantity: i32,
+    /// 每扎件数，不传则整体作为一扎
+    pub bundle_size: Option<i32>,
+}
+
+/// 生成扎请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateBundlesDto {
+    /// 分扎规格列表，扎号接着订单已有扎号递增
+    pub items: Vec<BundleSpec>,
+}
+
+/// 扎详情响应
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleResponse {
+    /// 扎ID
+    pub id: Uuid,
+    /// 订单ID
+    pub order_id: Uuid,
+    /// 订单/产品名称（关联字段）
+    pub order_name: Option<String>,
+    /// 扎号
+    pub bundle_no: i32,
+    /// 尺码
+    pub size: Option<String>,
+    /// 颜色
+    pub color: Option<String>,
+    /// 本扎件数
+    pub quantity: i32,
+    /// 二维码内容
+    pub qr_payload: String,
+    /// 已扫码计件的工序ID（不含已驳回）
+    pub scanned_process_ids: Vec<Uuid>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+}
+
+/// 扫码计件请求
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanBundleDto {
+    /// 扫描到的二维码内容，也可直接传扎ID
+    pub payload: String,
+    /// 完成的工序ID
+    pub process_id: Uuid,
+    /// 计件员工ID，老板代扫时必填，员工扫码时忽略
+    pub user_id: Option<Uuid>,
+    /// 计件凭证照片的文件 key 列表
+    pub images: Option<Vec<String>>,
+}
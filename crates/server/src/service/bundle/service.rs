@@ -0,0 +1,289 @@
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::dto::{BundleResponse, GenerateBundlesDto, ScanBundleDto};
+use crate::error::{AppError, Result};
+use crate::service::auth::Claims;
+use crate::service::piece_record::dto::CreatePieceRecordDto;
+use crate::service::piece_record::service as piece_record_service;
+use crate::traits::OwnedByBoss;
+use entity::bundle::{self, Column, Model};
+use entity::piece_record::{self, PieceRecordStatus};
+use entity::user::{self, Role};
+use entity::{order, process, workshop};
+
+/// 二维码内容前缀，与 App 的 stitchwork:// 深链接保持一致
+const QR_PREFIX: &str = "stitchwork://bundle?id=";
+
+/// 单次最多生成的扎数
+const MAX_BUNDLES_PER_REQUEST: usize = 1000;
+
+/// 生成扎的二维码内容
+pub fn qr_payload(id: Uuid) -> String {
+    format!("{}{}", QR_PREFIX, id)
+}
+
+/// 从二维码内容解析扎ID，兼容直接传入的 UUID
+fn parse_payload(payload: &str) -> Result<Uuid> {
+    let payload = payload.trim();
+    let id = payload.strip_prefix(QR_PREFIX).unwrap_or(payload);
+    Uuid::parse_str(id).map_err(|_| AppError::BadRequest("无法识别的扎二维码".to_string()))
+}
+
+fn normalize(value: &Option<String>) -> Option<String> {
+    value
+        .as_ref()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 当前用户所在工坊的老板ID
+async fn owner_id(db: &DbConn, claims: &Claims) -> Result<Uuid> {
+    match claims.role {
+        Role::Boss => Ok(claims.sub),
+        Role::Staff => {
+            let staff = user::Entity::find_by_id(claims.sub)
+                .one(db)
+                .await?
+                .ok_or(AppError::Forbidden)?;
+            let workshop_id = staff.workshop_id.ok_or(AppError::Forbidden)?;
+            let ws = workshop::Entity::find_by_id(workshop_id)
+                .one(db)
+                .await?
+                .ok_or(AppError::Forbidden)?;
+            Ok(ws.owner_id)
+        }
+    }
+}
+
+/// 各扎已扫码计件的工序（不含已驳回）
+async fn scanned_processes(db: &DbConn, ids: Vec<Uuid>) -> Result<HashMap<Uuid, Vec<Uuid>>> {
+    let rows: Vec<(Option<Uuid>, Uuid)> = piece_record::Entity::find()
+        .select_only()
+        .column(piece_record::Column::BundleId)
+        .column(piece_record::Column::ProcessId)
+        .distinct()
+        .filter(piece_record::Column::BundleId.is_in(ids))
+        .filter(piece_record::Column::Status.ne(PieceRecordStatus::Rejected))
+        .into_tuple()
+        .all(db)
+        .await?;
+    let mut map: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (bundle_id, process_id) in rows {
+        if let Some(bundle_id) = bundle_id {
+            map.entry(bundle_id).or_default().push(process_id);
+        }
+    }
+    Ok(map)
+}
+
+async fn to_responses(
+    db: &DbConn,
+    bundles: Vec<Model>,
+    order_name: Option<String>,
+) -> Result<Vec<BundleResponse>> {
+    let mut scanned = scanned_processes(db, bundles.iter().map(|b| b.id).collect()).await?;
+    Ok(bundles
+        .into_iter()
+        .map(|b| BundleResponse {
+            id: b.id,
+            order_id: b.order_id,
+            order_name: order_name.clone(),
+            bundle_no: b.bundle_no,
+            size: b.size,
+            color: b.color,
+            quantity: b.quantity,
+            qr_payload: qr_payload(b.id),
+            scanned_process_ids: scanned.remove(&b.id).unwrap_or_default(),
+            created_at: b.created_at,
+        })
+        .collect())
+}
+
+/// 订单的全部扎，按扎号排序
+pub async fn list_by_order(
+    db: &DbConn,
+    order_id: Uuid,
+    boss_id: Uuid,
+) -> Result<Vec<BundleResponse>> {
+    let ord = order::Entity::find_by_id(order_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("订单不存在".to_string()))?;
+    ord.verify_owner(boss_id)?;
+
+    let bundles = bundle::Entity::find()
+        .filter(Column::OrderId.eq(order_id))
+        .order_by_asc(Column::BundleNo)
+        .all(db)
+        .await?;
+    to_responses(db, bundles, Some(ord.product_name)).await
+}
+
+/// 按分扎规格生成扎，扎号接着订单已有的最大扎号递增
+pub async fn generate(
+    db: &DbConn,
+    order_id: Uuid,
+    boss_id: Uuid,
+    dto: GenerateBundlesDto,
+) -> Result<Vec<BundleResponse>> {
+    let ord = order::Entity::find_by_id(order_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("订单不存在".to_string()))?;
+    ord.verify_owner(boss_id)?;
+
+    if dto.items.is_empty() {
+        return Err(AppError::BadRequest("至少填写一项分扎规格".to_string()));
+    }
+
+    // 先拆分出每一扎的件数
+    let mut planned: Vec<(Option<String>, Option<String>, i32)> = Vec::new();
+    for item in &dto.items {
+        if item.quantity <= 0 {
+            return Err(AppError::BadRequest("裁片件数必须大于0".to_string()));
+        }
+        let size = item.bundle_size.unwrap_or(item.quantity);
+        if size <= 0 {
+            return Err(AppError::BadRequest("每扎件数必须大于0".to_string()));
+        }
+        let spec_size = normalize(&item.size);
+        let spec_color = normalize(&item.color);
+        let mut remaining = item.quantity;
+        while remaining > 0 {
+            let qty = remaining.min(size);
+            planned.push((spec_size.clone(), spec_color.clone(), qty));
+            remaining -= qty;
+            if planned.len() > MAX_BUNDLES_PER_REQUEST {
+                return Err(AppError::BadRequest(format!(
+                    "单次最多生成{}扎",
+                    MAX_BUNDLES_PER_REQUEST
+                )));
+            }
+        }
+    }
+
+    let txn = db.begin().await?;
+    let max_no: Option<Option<i32>> = bundle::Entity::find()
+        .select_only()
+        .column_as(Column::BundleNo.max(), "max")
+        .filter(Column::OrderId.eq(order_id))
+        .into_tuple()
+        .one(&txn)
+        .await?;
+    let start = max_no.flatten().unwrap_or(0) + 1;
+
+    let mut created = Vec::with_capacity(planned.len());
+    for (bundle_no, (size, color, quantity)) in (start..).zip(planned) {
+        let model = bundle::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            order_id: Set(order_id),
+            boss_id: Set(boss_id),
+            bundle_no: Set(bundle_no),
+            size: Set(size),
+            color: Set(color),
+            quantity: Set(quantity),
+            ..Default::default()
+        };
+        created.push(model.insert(&txn).await?);
+    }
+    txn.commit().await?;
+
+    to_responses(db, created, Some(ord.product_name)).await
+}
+
+/// 查看扎详情，老板和本工坊员工可查看
+pub async fn get_one(db: &DbConn, id: Uuid, claims: &Claims) -> Result<BundleResponse> {
+    let b = bundle::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("扎不存在".to_string()))?;
+    b.verify_owner(owner_id(db, claims).await?)?;
+
+    let order_name = order::Entity::find_by_id(b.order_id)
+        .one(db)
+        .await?
+        .map(|o| o.product_name);
+    to_responses(db, vec![b], order_name)
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Internal("扎数据异常".to_string()))
+}
+
+/// 删除扎，已有计件记录的扎不能删除
+pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
+    let b = bundle::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("扎不存在".to_string()))?;
+    b.verify_owner(boss_id)?;
+
+    let records = piece_record::Entity::find()
+        .filter(piece_record::Column::BundleId.eq(id))
+        .count(db)
+        .await?;
+    if records > 0 {
+        return Err(AppError::BadRequest("该扎已有计件记录，无法删除".to_string()));
+    }
+
+    bundle::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// 扫码计件：按扎的件数为扫码工序创建计件记录，同一扎同一工序只能计件一次（已驳回的除外）
+pub async fn scan(
+    db: &DbConn,
+    dto: ScanBundleDto,
+    claims: &Claims,
+) -> Result<(piece_record::Model, Option<String>)> {
+    let bundle_id = parse_payload(&dto.payload)?;
+    let b = bundle::Entity::find_by_id(bundle_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("扎不存在".to_string()))?;
+    b.verify_owner(owner_id(db, claims).await?)?;
+
+    let proc = process::Entity::find_by_id(dto.process_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("工序不存在".to_string()))?;
+    if proc.order_id != b.order_id {
+        return Err(AppError::BadRequest("该扎不属于此工序所在订单".to_string()));
+    }
+
+    let scanned = piece_record::Entity::find()
+        .filter(piece_record::Column::BundleId.eq(b.id))
+        .filter(piece_record::Column::ProcessId.eq(proc.id))
+        .filter(piece_record::Column::Status.ne(PieceRecordStatus::Rejected))
+        .one(db)
+        .await?;
+    if scanned.is_some() {
+        return Err(AppError::Conflict(format!(
+            "第{}扎的「{}」工序已扫码计件",
+            b.bundle_no, proc.name
+        )));
+    }
+
+    let user_id = match claims.role {
+        Role::Staff => claims.sub,
+        Role::Boss => dto
+            .user_id
+            .ok_or_else(|| AppError::BadRequest("请选择计件员工".to_string()))?,
+    };
+    piece_record_service::create_record(
+        db,
+        CreatePieceRecordDto {
+            process_id: proc.id,
+            user_id,
+            quantity: b.quantity,
+            images: dto.images,
+        },
+        claims,
+        Some(b.id),
+    )
+    .await
+}
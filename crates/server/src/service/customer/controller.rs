@@ -5,9 +5,13 @@ use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use super::dto::{CreateCustomerDto, UpdateCustomerDto};
+use super::dto::{
+    CreateCustomerDto, CustomerAddressDto, CustomerContactDto, CustomerDetailResponse,
+    UpdateCustomerDto,
+};
 use crate::common::{ApiResponse, ListData, QueryParams};
 use entity::customer::Model;
+use entity::{customer_address, customer_contact};
 use crate::error::{AppJson, Result};
 use crate::service::auth::Claims;
 use crate::AppState;
@@ -24,6 +28,30 @@ pub struct CustomerPath {
     id: Uuid,
 }
 
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/customers/{id}/contacts")]
+pub struct CustomerContactsPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/customer-contacts/{id}")]
+pub struct CustomerContactPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/customers/{id}/addresses")]
+pub struct CustomerAddressesPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/customer-addresses/{id}")]
+pub struct CustomerAddressPath {
+    id: Uuid,
+}
+
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .typed_get(list)
@@ -31,6 +59,14 @@ pub fn router() -> Router<Arc<AppState>> {
         .typed_get(get_one)
         .typed_put(update)
         .typed_delete(delete)
+        .typed_get(list_contacts)
+        .typed_post(create_contact)
+        .typed_put(update_contact)
+        .typed_delete(delete_contact)
+        .typed_get(list_addresses)
+        .typed_post(create_address)
+        .typed_put(update_address)
+        .typed_delete(delete_address)
 }
 
 async fn list(
@@ -61,10 +97,10 @@ async fn get_one(
     CustomerPath { id }: CustomerPath,
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<Claims>,
-) -> Result<ApiResponse<Model>> {
+) -> Result<ApiResponse<CustomerDetailResponse>> {
     claims.require_boss()?;
     Ok(ApiResponse::ok(
-        service::get_one(&state.db, id, claims.sub).await?,
+        service::get_detail(&state.db, id, claims.sub).await?,
     ))
 }
 
@@ -89,3 +125,93 @@ async fn delete(
     service::delete(&state.db, id, claims.sub).await?;
     Ok(ApiResponse::ok(()))
 }
+
+async fn list_contacts(
+    CustomerContactsPath { id }: CustomerContactsPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Vec<customer_contact::Model>>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::list_contacts(&state.db, id, claims.sub).await?,
+    ))
+}
+
+async fn create_contact(
+    CustomerContactsPath { id }: CustomerContactsPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<CustomerContactDto>,
+) -> Result<ApiResponse<customer_contact::Model>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::create_contact(&state.db, id, dto, claims.sub).await?,
+    ))
+}
+
+async fn update_contact(
+    CustomerContactPath { id }: CustomerContactPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<CustomerContactDto>,
+) -> Result<ApiResponse<customer_contact::Model>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::update_contact(&state.db, id, dto, claims.sub).await?,
+    ))
+}
+
+async fn delete_contact(
+    CustomerContactPath { id }: CustomerContactPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<()>> {
+    claims.require_boss()?;
+    service::delete_contact(&state.db, id, claims.sub).await?;
+    Ok(ApiResponse::ok(()))
+}
+
+async fn list_addresses(
+    CustomerAddressesPath { id }: CustomerAddressesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Vec<customer_address::Model>>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::list_addresses(&state.db, id, claims.sub).await?,
+    ))
+}
+
+async fn create_address(
+    CustomerAddressesPath { id }: CustomerAddressesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<CustomerAddressDto>,
+) -> Result<ApiResponse<customer_address::Model>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::create_address(&state.db, id, dto, claims.sub).await?,
+    ))
+}
+
+async fn update_address(
+    CustomerAddressPath { id }: CustomerAddressPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<CustomerAddressDto>,
+) -> Result<ApiResponse<customer_address::Model>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::update_address(&state.db, id, dto, claims.sub).await?,
+    ))
+}
+
+async fn delete_address(
+    CustomerAddressPath { id }: CustomerAddressPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<()>> {
+    claims.require_boss()?;
+    service::delete_address(&state.db, id, claims.sub).await?;
+    Ok(ApiResponse::ok(()))
+}
@@ -1,5 +1,11 @@
+use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use entity::customer::Model;
+use entity::customer_address::{self, CustomerAddressKind};
+use entity::customer_contact;
 
 /// 创建客户请求
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -24,3 +30,83 @@ pub struct UpdateCustomerDto {
     /// 客户描述/备注
     pub description: Option<String>,
 }
+
+/// 创建或更新客户联系人请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerContactDto {
+    /// 姓名
+    pub name: String,
+    /// 职务/角色，如跟单、质检、财务
+    pub role: Option<String>,
+    /// 联系电话
+    pub phone: Option<String>,
+    /// 备注
+    pub note: Option<String>,
+    /// 是否设为默认联系人
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+/// 创建或更新客户地址请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerAddressDto {
+    /// 地址类型: delivery/billing
+    pub kind: CustomerAddressKind,
+    /// 地址名称，如总部、二号仓
+    pub label: Option<String>,
+    /// 详细地址
+    pub address: String,
+    /// 收件人
+    pub receiver: Option<String>,
+    /// 收件人电话
+    pub phone: Option<String>,
+    /// 是否设为该类型的默认地址
+    #[serde(default)]
+    pub is_default: bool,
+}
+
+/// 客户详情响应，包含联系人和地址
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerDetailResponse {
+    /// 客户ID
+    pub id: Uuid,
+    /// 老板ID
+    pub user_id: Uuid,
+    /// 客户名称
+    pub name: String,
+    /// 客户电话
+    pub phone: Option<String>,
+    /// 客户描述/备注
+    pub description: Option<String>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+    /// 更新时间
+    pub updated_at: DateTime<Utc>,
+    /// 联系人列表，默认联系人在前
+    pub contacts: Vec<customer_contact::Model>,
+    /// 地址列表，默认地址在前
+    pub addresses: Vec<customer_address::Model>,
+}
+
+impl CustomerDetailResponse {
+    pub fn new(
+        model: Model,
+        contacts: Vec<customer_contact::Model>,
+        addresses: Vec<customer_address::Model>,
+    ) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            name: model.name,
+            phone: model.phone,
+            description: model.description,
+            created_at: model.created_at,
+            updated_at: model.updated_at,
+            contacts,
+            addresses,
+        }
+    }
+}
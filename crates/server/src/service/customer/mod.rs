@@ -1,5 +1,5 @@
 mod controller;
-mod dto;
-mod service;
+pub mod dto;
+pub mod service;
 
 pub use controller::router;
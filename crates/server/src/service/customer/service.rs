@@ -1,14 +1,26 @@
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    Set,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
 };
 use uuid::Uuid;
 
-use super::dto::{CreateCustomerDto, UpdateCustomerDto};
+use super::dto::{
+    CreateCustomerDto, CustomerAddressDto, CustomerContactDto, CustomerDetailResponse,
+    UpdateCustomerDto,
+};
 use crate::common::{apply_date_filter, ListData, QueryParams};
 use crate::error::{AppError, Result};
-use crate::traits::OwnedByUser;
+use crate::traits::{OwnedByBoss, OwnedByUser};
 use entity::customer::{self, Column, Model};
+use entity::customer_address::{self, CustomerAddressKind};
+use entity::{customer_contact, order};
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
 
 pub async fn list(db: &DbConn, params: QueryParams, boss_id: Uuid) -> Result<ListData<Model>> {
     let mut query = customer::Entity::find().filter(Column::UserId.eq(boss_id));
@@ -91,3 +103,305 @@ pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
     customer::Entity::delete_by_id(id).exec(db).await?;
     Ok(())
 }
+
+/// 客户详情，包含联系人和地址
+pub async fn get_detail(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<CustomerDetailResponse> {
+    let customer = get_one(db, id, boss_id).await?;
+    let contacts = list_contacts(db, id, boss_id).await?;
+    let addresses = list_addresses(db, id, boss_id).await?;
+    Ok(CustomerDetailResponse::new(customer, contacts, addresses))
+}
+
+// ============ 联系人 ============
+
+pub async fn list_contacts(
+    db: &DbConn,
+    customer_id: Uuid,
+    boss_id: Uuid,
+) -> Result<Vec<customer_contact::Model>> {
+    Ok(customer_contact::Entity::find()
+        .filter(customer_contact::Column::CustomerId.eq(customer_id))
+        .filter(customer_contact::Column::BossId.eq(boss_id))
+        .order_by_desc(customer_contact::Column::IsDefault)
+        .order_by_asc(customer_contact::Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_contact(
+    db: &DbConn,
+    customer_id: Uuid,
+    dto: CustomerContactDto,
+    boss_id: Uuid,
+) -> Result<customer_contact::Model> {
+    get_one(db, customer_id, boss_id).await?;
+    let name = validate_name(&dto.name)?;
+
+    let txn = db.begin().await?;
+    if dto.is_default {
+        clear_default_contact(&txn, customer_id).await?;
+    }
+    let contact = customer_contact::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        customer_id: Set(customer_id),
+        boss_id: Set(boss_id),
+        name: Set(name),
+        role: Set(normalize(dto.role)),
+        phone: Set(normalize(dto.phone)),
+        note: Set(normalize(dto.note)),
+        is_default: Set(dto.is_default),
+        created_at: Set(chrono::Utc::now()),
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+    Ok(contact)
+}
+
+pub async fn update_contact(
+    db: &DbConn,
+    id: Uuid,
+    dto: CustomerContactDto,
+    boss_id: Uuid,
+) -> Result<customer_contact::Model> {
+    let contact = find_contact(db, id, boss_id).await?;
+    let name = validate_name(&dto.name)?;
+
+    let txn = db.begin().await?;
+    if dto.is_default && !contact.is_default {
+        clear_default_contact(&txn, contact.customer_id).await?;
+    }
+    let mut model: customer_contact::ActiveModel = contact.into();
+    model.name = Set(name);
+    model.role = Set(normalize(dto.role));
+    model.phone = Set(normalize(dto.phone));
+    model.note = Set(normalize(dto.note));
+    model.is_default = Set(dto.is_default);
+    let contact = model.update(&txn).await?;
+    txn.commit().await?;
+    Ok(contact)
+}
+
+/// 删除联系人，已被订单使用的不能删除
+pub async fn delete_contact(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
+    let contact = find_contact(db, id, boss_id).await?;
+    let used = order::Entity::find()
+        .filter(order::Column::ContactId.eq(contact.id))
+        .count(db)
+        .await?;
+    if used > 0 {
+        return Err(AppError::BadRequest(format!(
+            "该联系人已用于{}个订单，无法删除",
+            used
+        )));
+    }
+    customer_contact::Entity::delete_by_id(contact.id)
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+async fn find_contact(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<customer_contact::Model> {
+    let contact = customer_contact::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("联系人不存在".to_string()))?;
+    contact.verify_owner(boss_id)?;
+    Ok(contact)
+}
+
+async fn clear_default_contact<C: ConnectionTrait>(conn: &C, customer_id: Uuid) -> Result<()> {
+    customer_contact::Entity::update_many()
+        .col_expr(customer_contact::Column::IsDefault, Expr::value(false))
+        .filter(customer_contact::Column::CustomerId.eq(customer_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+// ============ 地址 ============
+
+pub async fn list_addresses(
+    db: &DbConn,
+    customer_id: Uuid,
+    boss_id: Uuid,
+) -> Result<Vec<customer_address::Model>> {
+    Ok(customer_address::Entity::find()
+        .filter(customer_address::Column::CustomerId.eq(customer_id))
+        .filter(customer_address::Column::BossId.eq(boss_id))
+        .order_by_desc(customer_address::Column::Kind)
+        .order_by_desc(customer_address::Column::IsDefault)
+        .order_by_asc(customer_address::Column::CreatedAt)
+        .all(db)
+        .await?)
+}
+
+pub async fn create_address(
+    db: &DbConn,
+    customer_id: Uuid,
+    dto: CustomerAddressDto,
+    boss_id: Uuid,
+) -> Result<customer_address::Model> {
+    get_one(db, customer_id, boss_id).await?;
+    let address = validate_address(&dto.address)?;
+
+    let txn = db.begin().await?;
+    if dto.is_default {
+        clear_default_address(&txn, customer_id, dto.kind).await?;
+    }
+    let model = customer_address::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        customer_id: Set(customer_id),
+        boss_id: Set(boss_id),
+        kind: Set(dto.kind),
+        label: Set(normalize(dto.label)),
+        address: Set(address),
+        receiver: Set(normalize(dto.receiver)),
+        phone: Set(normalize(dto.phone)),
+        is_default: Set(dto.is_default),
+        created_at: Set(chrono::Utc::now()),
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+    Ok(model)
+}
+
+pub async fn update_address(
+    db: &DbConn,
+    id: Uuid,
+    dto: CustomerAddressDto,
+    boss_id: Uuid,
+) -> Result<customer_address::Model> {
+    let existing = find_address(db, id, boss_id).await?;
+    let address = validate_address(&dto.address)?;
+    if existing.kind != dto.kind && existing.kind == CustomerAddressKind::Delivery {
+        ensure_address_unused(db, existing.id).await?;
+    }
+
+    let txn = db.begin().await?;
+    if dto.is_default && !(existing.is_default && existing.kind == dto.kind) {
+        clear_default_address(&txn, existing.customer_id, dto.kind).await?;
+    }
+    let mut model: customer_address::ActiveModel = existing.into();
+    model.kind = Set(dto.kind);
+    model.label = Set(normalize(dto.label));
+    model.address = Set(address);
+    model.receiver = Set(normalize(dto.receiver));
+    model.phone = Set(normalize(dto.phone));
+    model.is_default = Set(dto.is_default);
+    let updated = model.update(&txn).await?;
+    txn.commit().await?;
+    Ok(updated)
+}
+
+/// 删除地址，已被订单用作收货地址的不能删除
+pub async fn delete_address(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
+    let address = find_address(db, id, boss_id).await?;
+    ensure_address_unused(db, address.id).await?;
+    customer_address::Entity::delete_by_id(address.id)
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+async fn find_address(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<customer_address::Model> {
+    let address = customer_address::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("地址不存在".to_string()))?;
+    address.verify_owner(boss_id)?;
+    Ok(address)
+}
+
+async fn ensure_address_unused(db: &DbConn, id: Uuid) -> Result<()> {
+    let used = order::Entity::find()
+        .filter(order::Column::DeliveryAddressId.eq(id))
+        .count(db)
+        .await?;
+    if used > 0 {
+        return Err(AppError::BadRequest(format!(
+            "该地址已用作{}个订单的收货地址",
+            used
+        )));
+    }
+    Ok(())
+}
+
+async fn clear_default_address<C: ConnectionTrait>(
+    conn: &C,
+    customer_id: Uuid,
+    kind: CustomerAddressKind,
+) -> Result<()> {
+    customer_address::Entity::update_many()
+        .col_expr(customer_address::Column::IsDefault, Expr::value(false))
+        .filter(customer_address::Column::CustomerId.eq(customer_id))
+        .filter(customer_address::Column::Kind.eq(kind))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+fn validate_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::BadRequest("请填写联系人姓名".to_string()));
+    }
+    Ok(name.to_string())
+}
+
+fn validate_address(address: &str) -> Result<String> {
+    let address = address.trim();
+    if address.is_empty() {
+        return Err(AppError::BadRequest("请填写详细地址".to_string()));
+    }
+    Ok(address.to_string())
+}
+
+// ============ 订单收货信息 ============
+
+/// 校验订单的收货地址和联系人属于该客户，收货地址需为收货类型
+pub(crate) async fn verify_delivery<C: ConnectionTrait>(
+    conn: &C,
+    customer_id: Uuid,
+    address_id: Option<Uuid>,
+    contact_id: Option<Uuid>,
+) -> Result<()> {
+    if let Some(id) = address_id {
+        let address = customer_address::Entity::find_by_id(id)
+            .one(conn)
+            .await?
+            .filter(|a| a.customer_id == customer_id)
+            .ok_or_else(|| AppError::BadRequest("收货地址不属于该客户".to_string()))?;
+        if address.kind != CustomerAddressKind::Delivery {
+            return Err(AppError::BadRequest("请选择收货地址".to_string()));
+        }
+    }
+    if let Some(id) = contact_id {
+        customer_contact::Entity::find_by_id(id)
+            .one(conn)
+            .await?
+            .filter(|c| c.customer_id == customer_id)
+            .ok_or_else(|| AppError::BadRequest("联系人不属于该客户".to_string()))?;
+    }
+    Ok(())
+}
+
+/// 客户的默认收货地址和默认联系人，新建订单未指定时使用
+pub(crate) async fn default_delivery<C: ConnectionTrait>(
+    conn: &C,
+    customer_id: Uuid,
+) -> Result<(Option<Uuid>, Option<Uuid>)> {
+    let address = customer_address::Entity::find()
+        .filter(customer_address::Column::CustomerId.eq(customer_id))
+        .filter(customer_address::Column::Kind.eq(CustomerAddressKind::Delivery))
+        .filter(customer_address::Column::IsDefault.eq(true))
+        .one(conn)
+        .await?;
+    let contact = customer_contact::Entity::find()
+        .filter(customer_contact::Column::CustomerId.eq(customer_id))
+        .filter(customer_contact::Column::IsDefault.eq(true))
+        .one(conn)
+        .await?;
+    Ok((address.map(|a| a.id), contact.map(|c| c.id)))
+}
@@ -37,7 +37,7 @@ pub async fn boss_overview(db: &DbConn, boss_id: Uuid) -> Result<BossOverview> {
         .all(db)
         .await?;
 
-    let today_quantity: i64 = today_records.iter().map(|r| r.quantity as i64).sum();
+    let today_quantity: i64 = today_records.iter().map(|r| r.produced_quantity()).sum();
     let today_amount: Decimal = today_records.iter().map(|r| r.amount).sum();
 
     // This month's stats
@@ -53,7 +53,7 @@ pub async fn boss_overview(db: &DbConn, boss_id: Uuid) -> Result<BossOverview> {
         .all(db)
         .await?;
 
-    let month_quantity: i64 = month_records.iter().map(|r| r.quantity as i64).sum();
+    let month_quantity: i64 = month_records.iter().map(|r| r.produced_quantity()).sum();
     let month_amount: Decimal = month_records.iter().map(|r| r.amount).sum();
 
     // Staff count
@@ -96,7 +96,7 @@ pub async fn staff_overview(db: &DbConn, user_id: Uuid) -> Result<StaffOverview>
         .all(db)
         .await?;
 
-    let month_quantity: i64 = month_records.iter().map(|r| r.quantity as i64).sum();
+    let month_quantity: i64 = month_records.iter().map(|r| r.produced_quantity()).sum();
     let month_amount: Decimal = month_records.iter().map(|r| r.amount).sum();
 
     Ok(StaffOverview {
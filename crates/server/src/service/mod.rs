@@ -1,10 +1,14 @@
 pub mod admin;
+pub mod advance;
+pub mod attendance;
 pub mod auth;
+pub mod bundle;
 pub mod chat;
 pub mod customer;
 pub mod home;
 pub mod notification;
 pub mod order;
+pub mod pay_run;
 pub mod payroll;
 pub mod piece_record;
 pub mod process;
@@ -28,8 +32,12 @@ pub fn routes() -> Router<Arc<AppState>> {
         .merge(home::router())
         .merge(order::router())
         .merge(process::router())
+        .merge(bundle::router())
         .merge(piece_record::router())
         .merge(payroll::router())
+        .merge(advance::router())
+        .merge(attendance::router())
+        .merge(pay_run::router())
         .merge(stats::router())
         .merge(share::router())
         .merge(upload::router())
@@ -30,13 +30,67 @@ pub enum Notification {
         process_name: String,
         quantity: i32,
         unit: String,
+        reason: Option<String>,
+    },
+    /// 员工修改后重新提交 → 通知老板
+    RecordResubmitted {
+        user_name: String,
+        process_name: String,
+        quantity: i32,
+        unit: String,
+    },
+    /// 老板调整已结算计件 → 通知员工
+    RecordAdjusted {
+        process_name: String,
+        quantity: i32,
+        unit: String,
+        amount: String,
+        reason: String,
+    },
+    /// 老板批量代录计件 → 通知员工（每人一条汇总）
+    RecordsBatchCreated {
+        count: usize,
+        quantity: i64,
+        unit: String,
+        amount: String,
+    },
+    /// 每日自动审核汇总 → 通知老板
+    AutoApprovedDigest {
+        count: i64,
+        quantity: i64,
+        unit: String,
+        amount: String,
     },
     /// 工资发放 → 通知员工
     PayrollReceived { amount: String },
+    /// 老板登记借支 → 通知员工
+    AdvanceRecorded { amount: String },
+    /// 工资单作废 → 通知员工
+    PayrollVoided { amount: String, reason: String },
+    /// 员工确认工资单 → 通知老板
+    PayrollAcknowledged { user_name: String, amount: String },
+    /// 员工对工资单提出异议 → 通知老板
+    PayrollDisputed {
+        user_name: String,
+        amount: String,
+        comment: String,
+    },
+    /// 老板处理工资单异议 → 通知员工
+    PayrollDisputeResolved { amount: String, resolution: String },
     /// 新用户注册 → 通知超管
     UserRegistered { username: String, phone: String },
     /// 员工加入工坊 → 通知老板
     StaffJoined { username: String, phone: String },
+    /// 工序分配给员工 → 通知员工
+    ProcessAssigned {
+        process_name: String,
+        order_name: String,
+    },
+    /// 工序取消分配 → 通知员工
+    ProcessUnassigned {
+        process_name: String,
+        order_name: String,
+    },
 }
 
 impl Notification {
@@ -45,9 +99,20 @@ impl Notification {
             Self::RecordSubmitted { .. } => "新计件待审核",
             Self::RecordApproved { .. } => "计件已通过",
             Self::RecordRejected { .. } => "计件被驳回",
+            Self::RecordResubmitted { .. } => "计件重新提交",
+            Self::RecordAdjusted { .. } => "计件已调整",
+            Self::RecordsBatchCreated { .. } => "老板已代录计件",
+            Self::AutoApprovedDigest { .. } => "自动通过计件日报",
             Self::PayrollReceived { .. } => "工资已发放",
+            Self::AdvanceRecorded { .. } => "借支已登记",
+            Self::PayrollVoided { .. } => "工资单已作废",
+            Self::PayrollAcknowledged { .. } => "工资已确认",
+            Self::PayrollDisputed { .. } => "工资单有异议",
+            Self::PayrollDisputeResolved { .. } => "工资单异议已处理",
             Self::UserRegistered { .. } => "新用户注册",
             Self::StaffJoined { .. } => "新员工加入",
+            Self::ProcessAssigned { .. } => "新工序分配",
+            Self::ProcessUnassigned { .. } => "工序已取消分配",
         }
     }
 
@@ -69,10 +134,69 @@ impl Notification {
                 process_name,
                 quantity,
                 unit,
-            } => format!("「{}」{}{} 未通过审核", process_name, quantity, unit),
+                reason,
+            } => match reason {
+                Some(r) => format!("「{}」{}{} 未通过审核：{}", process_name, quantity, unit, r),
+                None => format!("「{}」{}{} 未通过审核", process_name, quantity, unit),
+            },
+            Self::RecordResubmitted {
+                user_name,
+                process_name,
+                quantity,
+                unit,
+            } => format!("{} 修改并重新提交了「{}」{}{}", user_name, process_name, quantity, unit),
+            Self::RecordAdjusted {
+                process_name,
+                quantity,
+                unit,
+                amount,
+                reason,
+            } => format!(
+                "「{}」已结算计件调整 {:+}{}，金额 {} 元，将计入下次工资：{}",
+                process_name, quantity, unit, amount, reason
+            ),
+            Self::RecordsBatchCreated {
+                count,
+                quantity,
+                unit,
+                amount,
+            } => format!("老板为您录入了 {} 条计件，共 {}{}，计 ¥{}", count, quantity, unit, amount),
+            Self::AutoApprovedDigest {
+                count,
+                quantity,
+                unit,
+                amount,
+            } => format!("过去一天有 {} 条计件按规则自动通过，共 {}{}，计 ¥{}", count, quantity, unit, amount),
             Self::PayrollReceived { amount } => format!("您收到一笔工资 ¥{}", amount),
+            Self::AdvanceRecorded { amount } => {
+                format!("老板为您登记了一笔借支 ¥{}，将从后续工资中扣回", amount)
+            }
+            Self::PayrollVoided { amount, reason } => format!(
+                "¥{} 的工资单已作废，相关计件将重新结算：{}",
+                amount, reason
+            ),
+            Self::PayrollAcknowledged { user_name, amount } => {
+                format!("{} 已确认 ¥{} 的工资单", user_name, amount)
+            }
+            Self::PayrollDisputed {
+                user_name,
+                amount,
+                comment,
+            } => format!("{} 对 ¥{} 的工资单有异议：{}", user_name, amount, comment),
+            Self::PayrollDisputeResolved { amount, resolution } => format!(
+                "¥{} 的工资单异议已处理，请再次确认：{}",
+                amount, resolution
+            ),
             Self::UserRegistered { username, phone } => format!("{} ({})", username, phone),
             Self::StaffJoined { username, phone } => format!("{} ({}) 已加入工坊", username, phone),
+            Self::ProcessAssigned {
+                process_name,
+                order_name,
+            } => format!("「{}」的「{}」工序已分配给您", order_name, process_name),
+            Self::ProcessUnassigned {
+                process_name,
+                order_name,
+            } => format!("「{}」的「{}」工序已不再分配给您", order_name, process_name),
         }
     }
 }
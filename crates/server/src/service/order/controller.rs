@@ -1,4 +1,4 @@
-use axum::extract::State;
+use axum::extract::{Multipart, State};
 use axum::{Extension, Router};
 use axum_extra::extract::Query;
 use axum_extra::routing::{RouterExt, TypedPath};
@@ -6,15 +6,18 @@ use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use super::dto::{CreateOrderDto, OrderQueryParams, OrderResponse, UpdateOrderDto, UpdateOrderStatusDto};
+use super::dto::{
+    CreateOrderDto, ImportOrdersQuery, ImportOrdersResponse, OrderQueryParams, OrderResponse,
+    UpdateOrderDto, UpdateOrderStatusDto,
+};
 use crate::common::{ApiResponse, ListData, QueryParams};
 use entity::order::Model;
 use entity::user::Role;
-use crate::error::{AppJson, Result};
+use crate::error::{AppError, AppJson, Result};
 use crate::service::auth::Claims;
 use crate::AppState;
 
-use super::service;
+use super::{import, service};
 
 #[derive(TypedPath)]
 #[typed_path("/orders")]
@@ -32,6 +35,10 @@ pub struct OrderStatusPath {
     id: Uuid,
 }
 
+#[derive(TypedPath)]
+#[typed_path("/orders/import")]
+pub struct OrderImportPath;
+
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .typed_get(list)
@@ -40,6 +47,7 @@ pub fn router() -> Router<Arc<AppState>> {
         .typed_put(update)
         .typed_delete(delete)
         .typed_patch(update_status)
+        .typed_post(import_orders)
 }
 
 async fn list(
@@ -74,7 +82,15 @@ async fn get_one(
 ) -> Result<ApiResponse<OrderResponse>> {
     let model = service::get_one(&state.db, id, &claims).await?;
     let hide_price = claims.role == Role::Staff;
-    Ok(ApiResponse::ok(OrderResponse::from_model(model, hide_price)))
+    if hide_price {
+        return Ok(ApiResponse::ok(OrderResponse::from_model(model, true)));
+    }
+    let (delivery_address, contact) = service::delivery_info(&state.db, &model).await?;
+    Ok(ApiResponse::ok(OrderResponse {
+        delivery_address,
+        contact,
+        ..OrderResponse::from_model(model, false)
+    }))
 }
 
 async fn update(
@@ -110,3 +126,36 @@ async fn update_status(
         service::update_status(&state.db, id, dto, claims.sub).await?,
     ))
 }
+
+/// 批量导入订单（multipart，字段名 file，支持 csv/xlsx/xls）
+async fn import_orders(
+    _: OrderImportPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(query): Query<ImportOrdersQuery>,
+    mut multipart: Multipart,
+) -> Result<ApiResponse<ImportOrdersResponse>> {
+    claims.require_boss()?;
+
+    let mut file = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::BadRequest(e.body_text()))?
+    {
+        if field.name() == Some("file") {
+            let file_name = field.file_name().unwrap_or_default().to_string();
+            let bytes = field
+                .bytes()
+                .await
+                .map_err(|e| AppError::BadRequest(e.body_text()))?;
+            file = Some((file_name, bytes));
+        }
+    }
+    let (file_name, bytes) =
+        file.ok_or_else(|| AppError::BadRequest("缺少上传文件 file".to_string()))?;
+
+    Ok(ApiResponse::ok(
+        import::import_orders(&state.db, claims.sub, &file_name, &bytes, query.dry_run).await?,
+    ))
+}
@@ -6,6 +6,7 @@ use serde_json::Value as Json;
 use uuid::Uuid;
 
 use entity::order::{Model, OrderStatus};
+use entity::{customer_address, customer_contact};
 
 /// 创建订单请求
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -23,6 +24,12 @@ pub struct CreateOrderDto {
     pub quantity: i32,
     /// 单价
     pub unit_price: Decimal,
+    /// 加急系数，如 1.2 表示计件金额上浮 20%
+    pub urgency_multiplier: Option<Decimal>,
+    /// 收货地址ID，不传则使用客户的默认收货地址
+    pub delivery_address_id: Option<Uuid>,
+    /// 客户联系人ID，不传则使用客户的默认联系人
+    pub contact_id: Option<Uuid>,
 }
 
 /// 订单查询参数
@@ -55,6 +62,14 @@ pub struct UpdateOrderDto {
     pub quantity: Option<i32>,
     /// 单价
     pub unit_price: Option<Decimal>,
+    /// 加急系数，传 1 表示取消加急
+    pub urgency_multiplier: Option<Decimal>,
+    /// 收货地址ID，传 null 清除
+    #[serde(default, deserialize_with = "crate::common::deserialize_nullable")]
+    pub delivery_address_id: Option<Option<Uuid>>,
+    /// 客户联系人ID，传 null 清除
+    #[serde(default, deserialize_with = "crate::common::deserialize_nullable")]
+    pub contact_id: Option<Option<Uuid>>,
     /// 订单状态: pending/processing/completed/delivered/cancelled
     pub status: Option<String>,
 }
@@ -80,12 +95,24 @@ pub struct OrderResponse {
     /// 单价（员工不可见时为None）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unit_price: Option<Decimal>,
+    /// 加急系数
+    pub urgency_multiplier: Option<Decimal>,
     /// 订单状态: pending/processing/completed/delivered/cancelled
     pub status: OrderStatus,
     /// 接单时间
     pub received_at: DateTime<Utc>,
     /// 出货时间
     pub delivered_at: Option<DateTime<Utc>>,
+    /// 收货地址ID
+    pub delivery_address_id: Option<Uuid>,
+    /// 客户联系人ID
+    pub contact_id: Option<Uuid>,
+    /// 收货地址（仅老板查看订单详情时返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery_address: Option<customer_address::Model>,
+    /// 客户联系人（仅老板查看订单详情时返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<customer_contact::Model>,
 }
 
 impl OrderResponse {
@@ -103,9 +130,83 @@ impl OrderResponse {
             } else {
                 Some(model.unit_price)
             },
+            urgency_multiplier: model.urgency_multiplier,
             status: model.status,
             received_at: model.received_at,
             delivered_at: model.delivered_at,
+            delivery_address_id: model.delivery_address_id,
+            contact_id: model.contact_id,
+            delivery_address: None,
+            contact: None,
         }
     }
 }
+
+/// 批量导入订单查询参数
+#[derive(Debug, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOrdersQuery {
+    /// 仅校验不写入，默认 false
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 导入行错误
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportRowError {
+    /// 行号（与表格中显示的行号一致，表头为第 1 行）
+    pub row: usize,
+    /// 出错的列名
+    pub column: Option<String>,
+    /// 错误信息
+    pub message: String,
+}
+
+/// 导入预览中的订单
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOrderPreview {
+    /// 行号
+    pub row: usize,
+    /// 客户名称
+    pub customer_name: String,
+    /// 是否为新建客户
+    pub new_customer: bool,
+    /// 产品名称
+    pub product_name: String,
+    /// 订单数量
+    pub quantity: i32,
+    /// 单价
+    pub unit_price: Decimal,
+    /// 工序列表
+    pub processes: Vec<ImportProcessPreview>,
+}
+
+/// 导入预览中的工序
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportProcessPreview {
+    /// 工序名称
+    pub name: String,
+    /// 计件单价
+    pub piece_price: Decimal,
+}
+
+/// 批量导入订单结果
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOrdersResponse {
+    /// 是否为预演（未写入数据库）
+    pub dry_run: bool,
+    /// 数据行数（不含表头和空行）
+    pub total_rows: usize,
+    /// 校验错误，存在错误时不会写入任何数据
+    pub errors: Vec<ImportRowError>,
+    /// 解析出的订单
+    pub orders: Vec<ImportOrderPreview>,
+    /// 将要（或已经）新建的客户名称
+    pub new_customers: Vec<String>,
+    /// 已创建的订单ID（预演或校验失败时为空）
+    pub created_order_ids: Vec<Uuid>,
+}
@@ -0,0 +1,410 @@
+//! 订单批量导入（CSV / XLSX）
+//!
+//! 表格第一行为表头，固定列为客户名称、产品名称、数量、单价（备注可选），
+//! 其余每一列视为一道工序：表头是工序名称，单元格是该工序的计件单价，留空表示该订单没有这道工序。
+
+use std::collections::{HashMap, HashSet};
+use std::io::Cursor;
+
+use calamine::{open_workbook_auto_from_rs, Reader};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, QueryFilter, Set, TransactionTrait};
+use uuid::Uuid;
+
+use super::dto::{
+    ImportOrderPreview, ImportOrdersResponse, ImportProcessPreview, ImportRowError,
+};
+use crate::error::{AppError, Result};
+use crate::service::process::service::add_price_version;
+use entity::order::{self, OrderStatus};
+use entity::{customer, process};
+
+const CUSTOMER_HEADERS: &[&str] = &["客户", "客户名称", "customer", "customer name"];
+const PRODUCT_HEADERS: &[&str] = &["产品", "产品名称", "款式", "product", "product name"];
+const QUANTITY_HEADERS: &[&str] = &["数量", "订单数量", "quantity"];
+const UNIT_PRICE_HEADERS: &[&str] = &["单价", "unit price", "unit_price"];
+const DESCRIPTION_HEADERS: &[&str] = &["备注", "描述", "description", "note"];
+
+/// 表头列位置
+struct Columns {
+    customer: usize,
+    product: usize,
+    quantity: usize,
+    unit_price: usize,
+    description: Option<usize>,
+    /// (列位置, 工序名称)
+    processes: Vec<(usize, String)>,
+}
+
+/// 校验通过的一行
+struct ParsedRow {
+    row: usize,
+    customer_name: String,
+    product_name: String,
+    description: Option<String>,
+    quantity: i32,
+    unit_price: Decimal,
+    processes: Vec<(String, Decimal)>,
+}
+
+/// 导入订单和工序
+///
+/// 任意一行校验失败时不写入任何数据；`dry_run` 为 true 时只返回解析结果。
+pub async fn import_orders(
+    db: &DbConn,
+    boss_id: Uuid,
+    file_name: &str,
+    bytes: &[u8],
+    dry_run: bool,
+) -> Result<ImportOrdersResponse> {
+    let rows = read_rows(file_name, bytes)?;
+    let mut iter = rows.into_iter();
+    let header = iter
+        .next()
+        .ok_or_else(|| AppError::BadRequest("文件为空".to_string()))?;
+    let columns = parse_header(&header)?;
+
+    let mut errors = Vec::new();
+    let mut parsed = Vec::new();
+    let mut total_rows = 0;
+    for (idx, cells) in iter.enumerate() {
+        // 表头是第 1 行
+        let row = idx + 2;
+        if cells.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+        total_rows += 1;
+        match parse_row(row, &cells, &columns) {
+            Ok(r) => parsed.push(r),
+            Err(mut e) => errors.append(&mut e),
+        }
+    }
+
+    if total_rows == 0 {
+        return Err(AppError::BadRequest("文件中没有数据行".to_string()));
+    }
+
+    // 按名称匹配已有客户
+    let existing: HashMap<String, Uuid> = customer::Entity::find()
+        .filter(customer::Column::UserId.eq(boss_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|c| (c.name.trim().to_string(), c.id))
+        .collect();
+
+    let mut new_customers: Vec<String> = Vec::new();
+    let mut seen_new: HashSet<String> = HashSet::new();
+    for r in &parsed {
+        if !existing.contains_key(&r.customer_name) && seen_new.insert(r.customer_name.clone()) {
+            new_customers.push(r.customer_name.clone());
+        }
+    }
+
+    let orders = parsed
+        .iter()
+        .map(|r| ImportOrderPreview {
+            row: r.row,
+            customer_name: r.customer_name.clone(),
+            new_customer: !existing.contains_key(&r.customer_name),
+            product_name: r.product_name.clone(),
+            quantity: r.quantity,
+            unit_price: r.unit_price,
+            processes: r
+                .processes
+                .iter()
+                .map(|(name, price)| ImportProcessPreview {
+                    name: name.clone(),
+                    piece_price: *price,
+                })
+                .collect(),
+        })
+        .collect();
+
+    if dry_run || !errors.is_empty() {
+        return Ok(ImportOrdersResponse {
+            dry_run,
+            total_rows,
+            errors,
+            orders,
+            new_customers,
+            created_order_ids: vec![],
+        });
+    }
+
+    let txn = db.begin().await?;
+    let now = chrono::Utc::now();
+
+    let mut customer_ids = existing;
+    for name in &new_customers {
+        let id = Uuid::new_v4();
+        customer::ActiveModel {
+            id: Set(id),
+            user_id: Set(boss_id),
+            name: Set(name.clone()),
+            phone: Set(None),
+            description: Set(None),
+            created_at: Set(now),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        customer_ids.insert(name.clone(), id);
+    }
+
+    let mut created_order_ids = Vec::with_capacity(parsed.len());
+    for r in parsed {
+        let order_id = Uuid::new_v4();
+        order::ActiveModel {
+            id: Set(order_id),
+            customer_id: Set(customer_ids[&r.customer_name]),
+            boss_id: Set(boss_id),
+            product_name: Set(r.product_name),
+            description: Set(r.description),
+            images: Set(None),
+            quantity: Set(r.quantity),
+            unit_price: Set(r.unit_price),
+            status: Set(OrderStatus::Pending),
+            received_at: Set(now),
+            delivered_at: Set(None),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+
+        // 工序顺序与表格列顺序一致
+        for (sequence, (name, piece_price)) in r.processes.into_iter().enumerate() {
+            let proc = process::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                order_id: Set(order_id),
+                boss_id: Set(boss_id),
+                name: Set(name),
+                description: Set(None),
+                piece_price: Set(piece_price),
+                sequence: Set(sequence as i32),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+            add_price_version(&txn, &proc, piece_price, now, None).await?;
+        }
+        created_order_ids.push(order_id);
+    }
+
+    txn.commit().await?;
+
+    Ok(ImportOrdersResponse {
+        dry_run,
+        total_rows,
+        errors,
+        orders,
+        new_customers,
+        created_order_ids,
+    })
+}
+
+/// 将文件读取为字符串二维表
+fn read_rows(file_name: &str, bytes: &[u8]) -> Result<Vec<Vec<String>>> {
+    let ext = file_name
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match ext.as_str() {
+        "csv" => read_csv(bytes),
+        "xlsx" | "xls" => read_workbook(bytes),
+        _ => Err(AppError::BadRequest(
+            "仅支持 .csv、.xlsx、.xls 文件".to_string(),
+        )),
+    }
+}
+
+fn read_csv(bytes: &[u8]) -> Result<Vec<Vec<String>>> {
+    // Excel 导出的 CSV 带有 UTF-8 BOM
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(bytes);
+
+    reader
+        .records()
+        .map(|record| {
+            record
+                .map(|r| r.iter().map(|c| c.to_string()).collect())
+                .map_err(|e| AppError::BadRequest(format!("CSV 解析失败（需为 UTF-8 编码）: {}", e)))
+        })
+        .collect()
+}
+
+fn read_workbook(bytes: &[u8]) -> Result<Vec<Vec<String>>> {
+    let mut workbook = open_workbook_auto_from_rs(Cursor::new(bytes.to_vec()))
+        .map_err(|e| AppError::BadRequest(format!("Excel 解析失败: {}", e)))?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| AppError::BadRequest("Excel 文件没有工作表".to_string()))?
+        .map_err(|e| AppError::BadRequest(format!("Excel 解析失败: {}", e)))?;
+
+    Ok(range
+        .rows()
+        .map(|row| row.iter().map(|c| c.to_string()).collect())
+        .collect())
+}
+
+fn parse_header(header: &[String]) -> Result<Columns> {
+    let find = |names: &[&str]| {
+        header
+            .iter()
+            .position(|h| names.contains(&h.trim().to_lowercase().as_str()))
+    };
+
+    let mut missing = Vec::new();
+    let customer = find(CUSTOMER_HEADERS);
+    let product = find(PRODUCT_HEADERS);
+    let quantity = find(QUANTITY_HEADERS);
+    let unit_price = find(UNIT_PRICE_HEADERS);
+    let description = find(DESCRIPTION_HEADERS);
+    for (col, name) in [
+        (customer, "客户名称"),
+        (product, "产品名称"),
+        (quantity, "数量"),
+        (unit_price, "单价"),
+    ] {
+        if col.is_none() {
+            missing.push(name);
+        }
+    }
+    if !missing.is_empty() {
+        return Err(AppError::BadRequest(format!(
+            "缺少必填列: {}",
+            missing.join("、")
+        )));
+    }
+
+    let fixed = [customer, product, quantity, unit_price, description];
+    let mut seen = HashSet::new();
+    let mut processes = Vec::new();
+    for (idx, h) in header.iter().enumerate() {
+        let name = h.trim();
+        if name.is_empty() || fixed.contains(&Some(idx)) {
+            continue;
+        }
+        if !seen.insert(name.to_string()) {
+            return Err(AppError::BadRequest(format!("工序列重复: {}", name)));
+        }
+        processes.push((idx, name.to_string()));
+    }
+
+    Ok(Columns {
+        customer: customer.unwrap(),
+        product: product.unwrap(),
+        quantity: quantity.unwrap(),
+        unit_price: unit_price.unwrap(),
+        description,
+        processes,
+    })
+}
+
+fn parse_row(
+    row: usize,
+    cells: &[String],
+    columns: &Columns,
+) -> std::result::Result<ParsedRow, Vec<ImportRowError>> {
+    let cell = |idx: usize| cells.get(idx).map(|c| c.trim()).unwrap_or_default();
+    let mut errors = Vec::new();
+    let mut error = |column: &str, message: String| {
+        errors.push(ImportRowError {
+            row,
+            column: Some(column.to_string()),
+            message,
+        })
+    };
+
+    let customer_name = cell(columns.customer).to_string();
+    if customer_name.is_empty() {
+        error("客户名称", "客户名称不能为空".to_string());
+    }
+
+    let product_name = cell(columns.product).to_string();
+    if product_name.is_empty() {
+        error("产品名称", "产品名称不能为空".to_string());
+    }
+
+    let quantity = match parse_decimal(cell(columns.quantity)) {
+        Some(q) if q > Decimal::ZERO && q.fract().is_zero() => match q.to_i32() {
+            Some(q) => q,
+            None => {
+                error("数量", "数量过大".to_string());
+                0
+            }
+        },
+        _ => {
+            error(
+                "数量",
+                format!("数量需为正整数: {}", cell(columns.quantity)),
+            );
+            0
+        }
+    };
+
+    let unit_price = match parse_decimal(cell(columns.unit_price)) {
+        Some(p) if p >= Decimal::ZERO => p,
+        _ => {
+            error(
+                "单价",
+                format!("单价格式不正确: {}", cell(columns.unit_price)),
+            );
+            Decimal::ZERO
+        }
+    };
+
+    let mut processes = Vec::new();
+    for (idx, name) in &columns.processes {
+        let raw = cell(*idx);
+        if raw.is_empty() {
+            continue;
+        }
+        match parse_decimal(raw) {
+            Some(p) if p >= Decimal::ZERO => processes.push((name.clone(), p.round_dp(2))),
+            _ => error(name, format!("计件单价格式不正确: {}", raw)),
+        }
+    }
+
+    let description = columns
+        .description
+        .map(cell)
+        .filter(|d| !d.is_empty())
+        .map(|d| d.to_string());
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(ParsedRow {
+        row,
+        customer_name,
+        product_name,
+        description,
+        quantity,
+        unit_price: unit_price.round_dp(2),
+        processes,
+    })
+}
+
+/// 解析金额/数量，允许千分位逗号和货币符号
+fn parse_decimal(raw: &str) -> Option<Decimal> {
+    let cleaned: String = raw
+        .trim()
+        .trim_start_matches(['¥', '￥'])
+        .chars()
+        .filter(|c| *c != ',')
+        .collect();
+    if cleaned.is_empty() {
+        return None;
+    }
+    cleaned
+        .parse::<Decimal>()
+        .ok()
+        .or_else(|| Decimal::from_scientific(&cleaned).ok())
+}
@@ -1,5 +1,6 @@
 mod controller;
 pub mod dto;
+mod import;
 pub mod service;
 
 pub use controller::router;
@@ -1,4 +1,5 @@
 use chrono::NaiveDate;
+use rust_decimal::Decimal;
 use sea_orm::{
     ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, DbConn, EntityTrait, PaginatorTrait,
     QueryFilter, QueryOrder, Set,
@@ -10,9 +11,10 @@ use super::dto::{CreateOrderDto, OrderQueryParams, UpdateOrderDto, UpdateOrderSt
 use crate::common::{ListData, QueryParams};
 use entity::order::{self, Column, Model, OrderStatus};
 use entity::user::Role;
-use entity::{customer, user, workshop};
+use entity::{customer, customer_address, customer_contact, user, workshop};
 use crate::error::{AppError, Result};
 use crate::service::auth::Claims;
+use crate::service::customer::service::{default_delivery, verify_delivery};
 
 pub async fn list(
     db: &DbConn,
@@ -82,6 +84,17 @@ pub async fn list(
     Ok(ListData { list, total })
 }
 
+/// 加急系数需在 1-10 之间，1 视为不加急
+fn normalize_urgency(multiplier: Option<Decimal>) -> Result<Option<Decimal>> {
+    match multiplier {
+        Some(m) if m < Decimal::ONE || m > Decimal::TEN => Err(AppError::BadRequest(
+            "加急系数需在 1-10 之间".to_string(),
+        )),
+        Some(m) if m == Decimal::ONE => Ok(None),
+        m => Ok(m),
+    }
+}
+
 pub async fn create(db: &DbConn, dto: CreateOrderDto) -> Result<Model> {
     // 获取 customer 的 user_id 作为 boss_id
     let cust = customer::Entity::find_by_id(dto.customer_id)
@@ -89,6 +102,9 @@ pub async fn create(db: &DbConn, dto: CreateOrderDto) -> Result<Model> {
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Customer {} not found", dto.customer_id)))?;
 
+    verify_delivery(db, cust.id, dto.delivery_address_id, dto.contact_id).await?;
+    let (default_address, default_contact) = default_delivery(db, cust.id).await?;
+
     let model = order::ActiveModel {
         id: Set(Uuid::new_v4()),
         customer_id: Set(dto.customer_id),
@@ -98,9 +114,12 @@ pub async fn create(db: &DbConn, dto: CreateOrderDto) -> Result<Model> {
         images: Set(dto.images),
         quantity: Set(dto.quantity),
         unit_price: Set(dto.unit_price),
+        urgency_multiplier: Set(normalize_urgency(dto.urgency_multiplier)?),
         status: Set(OrderStatus::Pending),
         received_at: Set(chrono::Utc::now()),
         delivered_at: Set(None),
+        delivery_address_id: Set(dto.delivery_address_id.or(default_address)),
+        contact_id: Set(dto.contact_id.or(default_contact)),
         ..Default::default()
     };
     Ok(model.insert(db).await?)
@@ -145,6 +164,13 @@ pub async fn update(db: &DbConn, id: Uuid, dto: UpdateOrderDto, boss_id: Uuid) -
     if order.boss_id != boss_id {
         return Err(AppError::Forbidden);
     }
+    verify_delivery(
+        db,
+        order.customer_id,
+        dto.delivery_address_id.flatten(),
+        dto.contact_id.flatten(),
+    )
+    .await?;
 
     let mut model: order::ActiveModel = order.into();
     if let Some(v) = dto.product_name {
@@ -162,6 +188,15 @@ pub async fn update(db: &DbConn, id: Uuid, dto: UpdateOrderDto, boss_id: Uuid) -
     if let Some(v) = dto.unit_price {
         model.unit_price = Set(v);
     }
+    if dto.urgency_multiplier.is_some() {
+        model.urgency_multiplier = Set(normalize_urgency(dto.urgency_multiplier)?);
+    }
+    if let Some(v) = dto.delivery_address_id {
+        model.delivery_address_id = Set(v);
+    }
+    if let Some(v) = dto.contact_id {
+        model.contact_id = Set(v);
+    }
     if let Some(v) = dto.status {
         let status: OrderStatus = v
             .parse()
@@ -225,3 +260,19 @@ pub async fn update_status(
     };
     Ok(model.update(db).await?)
 }
+
+/// 订单的收货地址和联系人
+pub async fn delivery_info(
+    db: &DbConn,
+    order: &Model,
+) -> Result<(Option<customer_address::Model>, Option<customer_contact::Model>)> {
+    let address = match order.delivery_address_id {
+        Some(id) => customer_address::Entity::find_by_id(id).one(db).await?,
+        None => None,
+    };
+    let contact = match order.contact_id {
+        Some(id) => customer_contact::Entity::find_by_id(id).one(db).await?,
+        None => None,
+    };
+    Ok((address, contact))
+}
@@ -0,0 +1,132 @@
+use axum::extract::{Query, State};
+use axum::{Extension, Router};
+use axum_extra::routing::{RouterExt, TypedPath};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::dto::{CreatePayRunDto, PayRunResponse, UpdatePayRunEntryDto};
+use crate::common::{ApiResponse, ListData, QueryParams};
+use crate::error::{AppJson, Result};
+use crate::idempotency::IdempotencyKey;
+use crate::service::auth::Claims;
+use crate::service::notification::Notification;
+use crate::AppState;
+use entity::pay_run::Model;
+
+use super::service;
+
+#[derive(TypedPath)]
+#[typed_path("/pay-runs")]
+pub struct PayRunsPath;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/pay-runs/{id}")]
+pub struct PayRunPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/pay-runs/{id}/entries/{entry_id}")]
+pub struct PayRunEntryPath {
+    id: Uuid,
+    entry_id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/pay-runs/{id}/finalize")]
+pub struct PayRunFinalizePath {
+    id: Uuid,
+}
+
+pub fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .typed_get(list)
+        .typed_post(create)
+        .typed_get(get_one)
+        .typed_delete(delete)
+        .typed_put(update_entry)
+        .typed_post(finalize)
+}
+
+async fn list(
+    _: PayRunsPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<QueryParams>,
+) -> Result<ApiResponse<ListData<Model>>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::list(&state.db, params, claims.sub).await?,
+    ))
+}
+
+async fn create(
+    _: PayRunsPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<CreatePayRunDto>,
+) -> Result<ApiResponse<PayRunResponse>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::create(&state.db, dto, claims.sub).await?,
+    ))
+}
+
+async fn get_one(
+    PayRunPath { id }: PayRunPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<PayRunResponse>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::get_one(&state.db, id, claims.sub).await?,
+    ))
+}
+
+async fn delete(
+    PayRunPath { id }: PayRunPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<()>> {
+    claims.require_boss()?;
+    service::delete(&state.db, id, claims.sub).await?;
+    Ok(ApiResponse::ok(()))
+}
+
+async fn update_entry(
+    PayRunEntryPath { id, entry_id }: PayRunEntryPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<UpdatePayRunEntryDto>,
+) -> Result<ApiResponse<PayRunResponse>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::update_entry(&state.db, id, entry_id, dto, claims.sub).await?,
+    ))
+}
+
+async fn finalize(
+    PayRunFinalizePath { id }: PayRunFinalizePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    key: IdempotencyKey,
+) -> Result<ApiResponse<PayRunResponse>> {
+    claims.require_boss()?;
+    key.run(&state.db, claims.sub, "pay_run.finalize", &id, || async {
+        let (run, payrolls, warning) = service::finalize(&state.db, id, claims.sub).await?;
+
+        // 通知员工收到工资
+        for payroll in payrolls {
+            state.notifier.send(
+                payroll.user_id,
+                Notification::PayrollReceived {
+                    amount: payroll.amount.to_string(),
+                },
+            );
+        }
+
+        Ok(ApiResponse::ok_with_warning(run, warning))
+    })
+    .await
+}
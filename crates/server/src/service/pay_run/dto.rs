@@ -0,0 +1,107 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::service::payroll::dto::PayrollItemDto;
+use entity::pay_run::PayRunStatus;
+
+/// 创建批量发薪请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePayRunDto {
+    /// 周期开始日期（含），格式 YYYY-MM-DD
+    pub start_date: NaiveDate,
+    /// 周期结束日期（含），格式 YYYY-MM-DD
+    pub end_date: NaiveDate,
+    /// 备注，发放时写入各工资单
+    pub note: Option<String>,
+}
+
+/// 调整员工工资草稿请求，整体替换草稿内容
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePayRunEntryDto {
+    /// 结算的计件记录ID列表
+    pub record_ids: Vec<Uuid>,
+    /// 结算的考勤记录ID列表
+    #[serde(default)]
+    pub attendance_ids: Vec<Uuid>,
+    /// 奖金、扣款、借支还款等明细
+    #[serde(default)]
+    pub items: Vec<PayrollItemDto>,
+    /// 是否在发放时扣回借支，不传则保持不变；明细中已填借支还款时以明细为准
+    pub repay_advance: Option<bool>,
+    /// 手填发放金额，不传则按明细合计
+    pub amount: Option<Decimal>,
+    /// 是否排除该员工
+    #[serde(default)]
+    pub excluded: bool,
+    /// 备注，不传则使用批量发薪的备注
+    pub note: Option<String>,
+}
+
+/// 员工工资草稿
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayRunEntryResponse {
+    /// 草稿ID
+    pub id: Uuid,
+    /// 员工ID
+    pub user_id: Uuid,
+    /// 员工姓名（关联字段）
+    pub user_name: Option<String>,
+    /// 结算的计件记录ID列表
+    pub record_ids: Vec<Uuid>,
+    /// 计件总数量
+    pub quantity: i64,
+    /// 计件金额合计（按当前金额，发放时会重新计价）
+    pub piece_amount: Decimal,
+    /// 结算的考勤记录ID列表
+    pub attendance_ids: Vec<Uuid>,
+    /// 考勤总工时（小时）
+    pub hours: Decimal,
+    /// 计时金额合计
+    pub time_amount: Decimal,
+    /// 奖金、扣款、借支还款等明细
+    pub items: Vec<PayrollItemDto>,
+    /// 是否在发放时扣回借支
+    pub repay_advance: bool,
+    /// 按当前未还借支预计扣回的金额，发放时重新计算
+    pub suggested_repayment: Decimal,
+    /// 手填发放金额
+    pub amount: Option<Decimal>,
+    /// 预计发放金额
+    pub estimated_amount: Decimal,
+    /// 是否排除
+    pub excluded: bool,
+    /// 备注
+    pub note: Option<String>,
+    /// 发放后生成的工资单ID
+    pub payroll_id: Option<Uuid>,
+}
+
+/// 批量发薪详情响应
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayRunResponse {
+    /// 批量发薪ID
+    pub id: Uuid,
+    /// 周期开始日期
+    pub start_date: NaiveDate,
+    /// 周期结束日期
+    pub end_date: NaiveDate,
+    /// 状态: draft/finalized
+    pub status: PayRunStatus,
+    /// 备注
+    pub note: Option<String>,
+    /// 创建时间
+    pub created_at: DateTime<Utc>,
+    /// 发放时间
+    pub finalized_at: Option<DateTime<Utc>>,
+    /// 预计发放总额（不含排除的员工）
+    pub total_amount: Decimal,
+    /// 各员工工资草稿
+    pub entries: Vec<PayRunEntryResponse>,
+}
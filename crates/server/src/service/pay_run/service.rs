@@ -0,0 +1,449 @@
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+use super::dto::{CreatePayRunDto, PayRunEntryResponse, PayRunResponse, UpdatePayRunEntryDto};
+use crate::common::{ListData, QueryParams};
+use crate::error::{AppError, Result};
+use crate::service::advance::service as advance_service;
+use crate::service::payroll::dto::{CreatePayrollDto, PayrollItemDto};
+use crate::service::payroll::service as payroll_service;
+use crate::traits::OwnedByBoss;
+use entity::pay_run::{self, Column, Model, PayRunStatus};
+use entity::payroll_item::PayrollItemKind;
+use entity::piece_record::{self, PieceRecordStatus};
+use entity::{attendance, pay_run_entry, payroll, user};
+
+/// 单次批量发薪的最长周期（天）
+const MAX_PERIOD_DAYS: i64 = 366;
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+async fn find_run(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<Model> {
+    let run = pay_run::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("批量发薪不存在".to_string()))?;
+    run.verify_owner(boss_id)?;
+    Ok(run)
+}
+
+fn require_draft(run: &Model) -> Result<()> {
+    if run.status != PayRunStatus::Draft {
+        return Err(AppError::BadRequest("批量发薪已发放，不能修改".to_string()));
+    }
+    Ok(())
+}
+
+fn entry_items(entry: &pay_run_entry::Model) -> Vec<PayrollItemDto> {
+    entry
+        .items
+        .as_ref()
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+pub async fn list(db: &DbConn, params: QueryParams, boss_id: Uuid) -> Result<ListData<Model>> {
+    let paginator = pay_run::Entity::find()
+        .filter(Column::BossId.eq(boss_id))
+        .order_by_desc(Column::CreatedAt)
+        .paginate(db, params.page_size);
+    let total = paginator.num_items().await?;
+    let list = paginator.fetch_page(params.page.saturating_sub(1)).await?;
+    Ok(ListData { list, total })
+}
+
+/// 待结算的工资来源：计件记录和考勤
+#[derive(Default)]
+struct PendingWork {
+    record_ids: Vec<Uuid>,
+    piece_amount: Decimal,
+    attendance_ids: Vec<Uuid>,
+    time_amount: Decimal,
+}
+
+/// 创建批量发薪：汇总周期内全部已批准的计件和未结算的考勤，按员工生成工资草稿
+pub async fn create(db: &DbConn, dto: CreatePayRunDto, boss_id: Uuid) -> Result<PayRunResponse> {
+    if dto.start_date > dto.end_date {
+        return Err(AppError::BadRequest("开始日期不能晚于结束日期".to_string()));
+    }
+    if (dto.end_date - dto.start_date).num_days() >= MAX_PERIOD_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "周期不能超过{}天",
+            MAX_PERIOD_DAYS
+        )));
+    }
+
+    let records = piece_record::Entity::find()
+        .filter(piece_record::Column::BossId.eq(boss_id))
+        .filter(piece_record::Column::Status.eq(PieceRecordStatus::Approved))
+        .filter(piece_record::Column::RecordedAt.gte(day_start(dto.start_date)))
+        .filter(piece_record::Column::RecordedAt.lte(day_end(dto.end_date)))
+        .order_by_asc(piece_record::Column::RecordedAt)
+        .all(db)
+        .await?;
+    let attendances = attendance::Entity::find()
+        .filter(attendance::Column::BossId.eq(boss_id))
+        .filter(attendance::Column::PayrollId.is_null())
+        .filter(attendance::Column::WorkDate.gte(dto.start_date))
+        .filter(attendance::Column::WorkDate.lte(dto.end_date))
+        .order_by_asc(attendance::Column::WorkDate)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|a| !a.is_open())
+        .collect::<Vec<_>>();
+    if records.is_empty() && attendances.is_empty() {
+        return Err(AppError::BadRequest(
+            "该周期内没有待结算的计件或考勤记录".to_string(),
+        ));
+    }
+
+    let mut by_user: BTreeMap<Uuid, PendingWork> = BTreeMap::new();
+    for rec in &records {
+        let entry = by_user.entry(rec.user_id).or_default();
+        entry.record_ids.push(rec.id);
+        entry.piece_amount += rec.amount;
+    }
+    for att in &attendances {
+        let entry = by_user.entry(att.user_id).or_default();
+        entry.attendance_ids.push(att.id);
+        entry.time_amount += att.amount;
+    }
+
+    let txn = db.begin().await?;
+    let run = pay_run::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        boss_id: Set(boss_id),
+        start_date: Set(dto.start_date),
+        end_date: Set(dto.end_date),
+        status: Set(PayRunStatus::Draft),
+        note: Set(normalize(dto.note)),
+        created_at: Set(Utc::now()),
+        finalized_at: Set(None),
+    }
+    .insert(&txn)
+    .await?;
+    for (user_id, work) in by_user {
+        // 默认扣回借支，金额到发放时再按重新计价后的应发金额确定
+        pay_run_entry::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            pay_run_id: Set(run.id),
+            user_id: Set(user_id),
+            record_ids: Set(serde_json::json!(work.record_ids)),
+            attendance_ids: Set(
+                (!work.attendance_ids.is_empty()).then(|| serde_json::json!(work.attendance_ids)),
+            ),
+            items: Set(None),
+            repay_advance: Set(true),
+            amount: Set(None),
+            excluded: Set(false),
+            note: Set(None),
+            payroll_id: Set(None),
+        }
+        .insert(&txn)
+        .await?;
+    }
+    txn.commit().await?;
+
+    to_response(db, run).await
+}
+
+pub async fn get_one(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<PayRunResponse> {
+    let run = find_run(db, id, boss_id).await?;
+    to_response(db, run).await
+}
+
+/// 调整员工工资草稿：增减计件和考勤记录、填写奖金扣款、手填金额或排除
+pub async fn update_entry(
+    db: &DbConn,
+    id: Uuid,
+    entry_id: Uuid,
+    dto: UpdatePayRunEntryDto,
+    boss_id: Uuid,
+) -> Result<PayRunResponse> {
+    let run = find_run(db, id, boss_id).await?;
+    require_draft(&run)?;
+    let entry = pay_run_entry::Entity::find_by_id(entry_id)
+        .one(db)
+        .await?
+        .filter(|e| e.pay_run_id == run.id)
+        .ok_or_else(|| AppError::NotFound("工资草稿不存在".to_string()))?;
+
+    let mut record_ids = dto.record_ids;
+    record_ids.sort();
+    record_ids.dedup();
+    let mut attendance_ids = dto.attendance_ids;
+    attendance_ids.sort();
+    attendance_ids.dedup();
+    if !dto.excluded && record_ids.is_empty() && attendance_ids.is_empty() {
+        return Err(AppError::BadRequest(
+            "至少保留一条计件或考勤记录，或排除该员工".to_string(),
+        ));
+    }
+    let valid = piece_record::Entity::find()
+        .filter(piece_record::Column::Id.is_in(record_ids.clone()))
+        .filter(piece_record::Column::BossId.eq(boss_id))
+        .filter(piece_record::Column::UserId.eq(entry.user_id))
+        .filter(piece_record::Column::Status.eq(PieceRecordStatus::Approved))
+        .count(db)
+        .await?;
+    if valid as usize != record_ids.len() {
+        return Err(AppError::BadRequest(
+            "部分计件记录不存在、不属于该员工或不是已批准状态".to_string(),
+        ));
+    }
+    let valid = attendance::Entity::find()
+        .filter(attendance::Column::Id.is_in(attendance_ids.clone()))
+        .filter(attendance::Column::BossId.eq(boss_id))
+        .filter(attendance::Column::UserId.eq(entry.user_id))
+        .filter(attendance::Column::PayrollId.is_null())
+        .all(db)
+        .await?
+        .iter()
+        .filter(|a| !a.is_open())
+        .count();
+    if valid != attendance_ids.len() {
+        return Err(AppError::BadRequest(
+            "部分考勤记录不存在、不属于该员工或已结算".to_string(),
+        ));
+    }
+    payroll_service::estimate_total(Decimal::ZERO, Decimal::ZERO, &dto.items)?;
+    if let Some(amount) = dto.amount
+        && amount < Decimal::ZERO
+    {
+        return Err(AppError::BadRequest("发放金额不能为负数".to_string()));
+    }
+
+    let mut model: pay_run_entry::ActiveModel = entry.into();
+    model.record_ids = Set(serde_json::json!(record_ids));
+    model.attendance_ids =
+        Set((!attendance_ids.is_empty()).then(|| serde_json::json!(attendance_ids)));
+    model.items = Set((!dto.items.is_empty()).then(|| serde_json::json!(dto.items)));
+    if let Some(v) = dto.repay_advance {
+        model.repay_advance = Set(v);
+    }
+    model.amount = Set(dto.amount);
+    model.excluded = Set(dto.excluded);
+    model.note = Set(normalize(dto.note));
+    model.update(db).await?;
+
+    to_response(db, run).await
+}
+
+/// 发放：在一个事务内为全部未排除的员工生成工资单，任一失败则全部回滚
+///
+/// 返回生成的工资单和金额不一致提醒
+pub async fn finalize(
+    db: &DbConn,
+    id: Uuid,
+    boss_id: Uuid,
+) -> Result<(PayRunResponse, Vec<payroll::Model>, Option<String>)> {
+    let run = find_run(db, id, boss_id).await?;
+    require_draft(&run)?;
+
+    // 先在事务内把草稿占用为已发放，并发发放时只有一次能继续
+    let txn = db.begin().await?;
+    let claimed = pay_run::Entity::update_many()
+        .col_expr(Column::Status, Expr::value(PayRunStatus::Finalized))
+        .col_expr(Column::FinalizedAt, Expr::value(Some(Utc::now())))
+        .filter(Column::Id.eq(run.id))
+        .filter(Column::Status.eq(PayRunStatus::Draft))
+        .exec(&txn)
+        .await?;
+    if claimed.rows_affected == 0 {
+        return Err(AppError::Conflict("批量发薪已发放，请刷新后查看".to_string()));
+    }
+    let entries = pay_run_entry::Entity::find()
+        .filter(pay_run_entry::Column::PayRunId.eq(run.id))
+        .filter(pay_run_entry::Column::Excluded.eq(false))
+        .all(&txn)
+        .await?;
+    if entries.is_empty() {
+        return Err(AppError::BadRequest("没有需要发放的员工".to_string()));
+    }
+    let names = user_names(&txn, entries.iter().map(|e| e.user_id).collect()).await?;
+    let mut payrolls = Vec::with_capacity(entries.len());
+    let mut warnings = Vec::new();
+    for entry in entries {
+        let name = names.get(&entry.user_id).cloned().unwrap_or_default();
+        let dto = CreatePayrollDto {
+            user_id: entry.user_id,
+            amount: entry.amount,
+            record_ids: entry.record_ids(),
+            attendance_ids: entry.attendance_ids(),
+            items: Some(entry_items(&entry)),
+            repay_advance: entry.repay_advance,
+            rounding: None,
+            payment_image: None,
+            note: entry.note.clone().or(run.note.clone()),
+        };
+        let (payroll, warning) = payroll_service::create_in(&txn, dto, boss_id)
+            .await
+            .map_err(|e| match e {
+                AppError::BadRequest(msg) => AppError::BadRequest(format!("{}：{}", name, msg)),
+                e => e,
+            })?;
+        if let Some(w) = warning {
+            warnings.push(format!("{}：{}", name, w));
+        }
+
+        let mut model: pay_run_entry::ActiveModel = entry.into();
+        model.payroll_id = Set(Some(payroll.id));
+        model.update(&txn).await?;
+        payrolls.push(payroll);
+    }
+
+    let run = pay_run::Entity::find_by_id(run.id)
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound("批量发薪不存在".to_string()))?;
+    txn.commit().await?;
+
+    let warning = (!warnings.is_empty()).then(|| warnings.join("；"));
+    Ok((to_response(db, run).await?, payrolls, warning))
+}
+
+/// 删除草稿，已发放的批量发薪不能删除
+pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
+    let run = find_run(db, id, boss_id).await?;
+    require_draft(&run)?;
+    pay_run::Entity::delete_by_id(run.id).exec(db).await?;
+    Ok(())
+}
+
+fn day_start(date: NaiveDate) -> chrono::NaiveDateTime {
+    date.and_hms_opt(0, 0, 0).unwrap()
+}
+
+fn day_end(date: NaiveDate) -> chrono::NaiveDateTime {
+    date.and_hms_opt(23, 59, 59).unwrap()
+}
+
+async fn user_names<C: ConnectionTrait>(
+    db: &C,
+    ids: Vec<Uuid>,
+) -> Result<HashMap<Uuid, String>> {
+    Ok(user::Entity::find()
+        .filter(user::Column::Id.is_in(ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.display_name.unwrap_or(u.username)))
+        .collect())
+}
+
+async fn to_response(db: &DbConn, run: Model) -> Result<PayRunResponse> {
+    let entries = pay_run_entry::Entity::find()
+        .filter(pay_run_entry::Column::PayRunId.eq(run.id))
+        .all(db)
+        .await?;
+    let names = user_names(db, entries.iter().map(|e| e.user_id).collect()).await?;
+
+    let record_ids: HashSet<Uuid> = entries.iter().flat_map(|e| e.record_ids()).collect();
+    let records: HashMap<Uuid, piece_record::Model> = piece_record::Entity::find()
+        .filter(piece_record::Column::Id.is_in(record_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|r| (r.id, r))
+        .collect();
+    let attendance_ids: HashSet<Uuid> =
+        entries.iter().flat_map(|e| e.attendance_ids()).collect();
+    let attendances: HashMap<Uuid, attendance::Model> = attendance::Entity::find()
+        .filter(attendance::Column::Id.is_in(attendance_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|a| (a.id, a))
+        .collect();
+
+    let user_ids: Vec<Uuid> = entries.iter().map(|e| e.user_id).collect();
+    let advances = advance_service::totals(db, Some(run.boss_id), &user_ids).await?;
+
+    let mut total_amount = Decimal::ZERO;
+    let mut responses = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let ids = entry.record_ids();
+        let (quantity, piece_amount) = ids
+            .iter()
+            .filter_map(|id| records.get(id))
+            .fold((0i64, Decimal::ZERO), |(q, a), r| {
+                (q + r.quantity as i64, a + r.amount)
+            });
+        let att_ids = entry.attendance_ids();
+        let (hours, time_amount) = att_ids
+            .iter()
+            .filter_map(|id| attendances.get(id))
+            .fold((Decimal::ZERO, Decimal::ZERO), |(h, a), att| {
+                (h + att.hours, a + att.amount)
+            });
+        let items = entry_items(&entry);
+        // 草稿按当前未还借支预估扣回金额，已发放的以工资单为准
+        let suggestion = if entry.repay_advance
+            && entry.payroll_id.is_none()
+            && !items
+                .iter()
+                .any(|i| i.kind == PayrollItemKind::AdvanceRepayment)
+        {
+            let outstanding = advances
+                .get(&entry.user_id)
+                .map_or(Decimal::ZERO, |(advanced, repaid)| advanced - repaid);
+            let payable = payroll_service::estimate_total(piece_amount, time_amount, &items)?;
+            payroll_service::suggest_repayment(outstanding, payable)
+        } else {
+            None
+        };
+        let suggested_repayment = suggestion.as_ref().map_or(Decimal::ZERO, |i| i.amount);
+        let extras: Vec<PayrollItemDto> = items.iter().cloned().chain(suggestion).collect();
+        let estimated_amount = match entry.amount {
+            Some(amount) => amount,
+            None => payroll_service::estimate_total(piece_amount, time_amount, &extras)?,
+        };
+        if !entry.excluded {
+            total_amount += estimated_amount;
+        }
+        responses.push(PayRunEntryResponse {
+            id: entry.id,
+            user_id: entry.user_id,
+            user_name: names.get(&entry.user_id).cloned(),
+            record_ids: ids,
+            quantity,
+            piece_amount,
+            attendance_ids: att_ids,
+            hours,
+            time_amount,
+            items,
+            repay_advance: entry.repay_advance,
+            suggested_repayment,
+            amount: entry.amount,
+            estimated_amount,
+            excluded: entry.excluded,
+            note: entry.note,
+            payroll_id: entry.payroll_id,
+        });
+    }
+    responses.sort_by(|a, b| a.user_name.cmp(&b.user_name));
+
+    Ok(PayRunResponse {
+        id: run.id,
+        start_date: run.start_date,
+        end_date: run.end_date,
+        status: run.status,
+        note: run.note,
+        created_at: run.created_at,
+        finalized_at: run.finalized_at,
+        total_amount,
+        entries: responses,
+    })
+}
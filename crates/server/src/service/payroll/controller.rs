@@ -1,38 +1,96 @@
 use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
 use axum::{Extension, Router};
+use sea_orm::EntityTrait;
 use axum_extra::routing::{RouterExt, TypedPath};
 use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use super::dto::{CreatePayrollDto, PayrollDetailResponse, UpdatePayrollDto};
+use super::dto::{
+    AcknowledgePayrollDto, CreatePayrollDto, DisputePayrollDto, ExportPayrollsDto,
+    MarkTransferredDto, PayrollDetailResponse, PayrollPreviewParams, PayrollPreviewResponse,
+    ResolvePayrollDto, UpdatePayrollDto, VoidPayrollDto,
+};
 use crate::common::{ApiResponse, ListData, QueryParams};
 use entity::payroll::Model;
-use entity::user::Role;
+use entity::user::{self, Role};
 use crate::error::{AppJson, Result};
+use crate::idempotency::IdempotencyKey;
 use crate::service::auth::Claims;
 use crate::service::notification::Notification;
 use crate::AppState;
 
-use super::service;
+use super::{export as transfer_export, payslip as payslip_pdf, service};
 
 #[derive(TypedPath)]
 #[typed_path("/payrolls")]
 pub struct PayrollsPath;
 
+#[derive(TypedPath)]
+#[typed_path("/payrolls/export")]
+pub struct PayrollExportPath;
+
+#[derive(TypedPath)]
+#[typed_path("/payrolls/mark-transferred")]
+pub struct MarkTransferredPath;
+
+#[derive(TypedPath)]
+#[typed_path("/payrolls/preview")]
+pub struct PayrollPreviewPath;
+
 #[derive(TypedPath, Deserialize)]
 #[typed_path("/payrolls/{id}")]
 pub struct PayrollPath {
     id: Uuid,
 }
 
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/payrolls/{id}/payslip")]
+pub struct PayslipPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/payrolls/{id}/void")]
+pub struct PayrollVoidPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/payrolls/{id}/acknowledge")]
+pub struct PayrollAcknowledgePath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/payrolls/{id}/dispute")]
+pub struct PayrollDisputePath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/payrolls/{id}/resolve")]
+pub struct PayrollResolvePath {
+    id: Uuid,
+}
+
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .typed_get(list)
         .typed_post(create)
+        .typed_get(preview)
         .typed_get(get_one)
         .typed_put(update)
         .typed_delete(delete)
+        .typed_post(void)
+        .typed_post(acknowledge)
+        .typed_post(dispute)
+        .typed_post(resolve)
+        .typed_get(payslip)
+        .typed_post(export)
+        .typed_post(mark_transferred)
 }
 
 async fn list(
@@ -51,24 +109,41 @@ async fn list(
     ))
 }
 
+/// 待发工资预览，按员工列出未结算的计件和考勤及建议发放金额
+async fn preview(
+    _: PayrollPreviewPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<PayrollPreviewParams>,
+) -> Result<ApiResponse<PayrollPreviewResponse>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::preview(&state.db, claims.sub, params).await?,
+    ))
+}
+
 async fn create(
     _: PayrollsPath,
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<Claims>,
+    key: IdempotencyKey,
     AppJson(dto): AppJson<CreatePayrollDto>,
 ) -> Result<ApiResponse<Model>> {
     claims.require_boss()?;
-    let payroll = service::create(&state.db, dto, claims.sub).await?;
+    key.run(&state.db, claims.sub, "payroll.create", &dto, || async {
+        let (payroll, warning) = service::create(&state.db, dto.clone(), claims.sub).await?;
 
-    // 通知员工收到工资
-    state.notifier.send(
-        payroll.user_id,
-        Notification::PayrollReceived {
-            amount: payroll.amount.to_string(),
-        },
-    );
+        // 通知员工收到工资
+        state.notifier.send(
+            payroll.user_id,
+            Notification::PayrollReceived {
+                amount: payroll.amount.to_string(),
+            },
+        );
 
-    Ok(ApiResponse::ok(payroll))
+        Ok(ApiResponse::ok_with_warning(payroll, warning))
+    })
+    .await
 }
 
 async fn get_one(
@@ -93,7 +168,8 @@ async fn update(
     AppJson(dto): AppJson<UpdatePayrollDto>,
 ) -> Result<ApiResponse<Model>> {
     claims.require_boss()?;
-    Ok(ApiResponse::ok(service::update(&state.db, id, dto).await?))
+    let (payroll, warning) = service::update(&state.db, id, dto, claims.sub).await?;
+    Ok(ApiResponse::ok_with_warning(payroll, warning))
 }
 
 async fn delete(
@@ -102,6 +178,160 @@ async fn delete(
     Extension(claims): Extension<Claims>,
 ) -> Result<ApiResponse<()>> {
     claims.require_boss()?;
-    service::delete(&state.db, id).await?;
+    service::delete(&state.db, id, claims.sub).await?;
     Ok(ApiResponse::ok(()))
 }
+
+async fn void(
+    PayrollVoidPath { id }: PayrollVoidPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<VoidPayrollDto>,
+) -> Result<ApiResponse<Model>> {
+    claims.require_boss()?;
+    let payroll = service::void(&state.db, id, claims.sub, dto).await?;
+
+    state.notifier.send(
+        payroll.user_id,
+        Notification::PayrollVoided {
+            amount: payroll.amount.to_string(),
+            reason: payroll.void_reason.clone().unwrap_or_default(),
+        },
+    );
+
+    Ok(ApiResponse::ok(payroll))
+}
+
+/// 员工确认工资单
+async fn acknowledge(
+    PayrollAcknowledgePath { id }: PayrollAcknowledgePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<AcknowledgePayrollDto>,
+) -> Result<ApiResponse<Model>> {
+    let payroll = service::acknowledge(&state.db, id, claims.sub, dto).await?;
+
+    state.notifier.send(
+        payroll.boss_id,
+        Notification::PayrollAcknowledged {
+            user_name: staff_name(&state, payroll.user_id).await,
+            amount: payroll.amount.to_string(),
+        },
+    );
+
+    Ok(ApiResponse::ok(payroll))
+}
+
+/// 员工对工资单提出异议
+async fn dispute(
+    PayrollDisputePath { id }: PayrollDisputePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<DisputePayrollDto>,
+) -> Result<ApiResponse<Model>> {
+    let payroll = service::dispute(&state.db, id, claims.sub, dto).await?;
+
+    state.notifier.send(
+        payroll.boss_id,
+        Notification::PayrollDisputed {
+            user_name: staff_name(&state, payroll.user_id).await,
+            amount: payroll.amount.to_string(),
+            comment: payroll.ack_comment.clone().unwrap_or_default(),
+        },
+    );
+
+    Ok(ApiResponse::ok(payroll))
+}
+
+/// 老板处理工资单异议
+async fn resolve(
+    PayrollResolvePath { id }: PayrollResolvePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<ResolvePayrollDto>,
+) -> Result<ApiResponse<Model>> {
+    claims.require_boss()?;
+    let payroll = service::resolve(&state.db, id, claims.sub, dto).await?;
+
+    state.notifier.send(
+        payroll.user_id,
+        Notification::PayrollDisputeResolved {
+            amount: payroll.amount.to_string(),
+            resolution: payroll.resolution.clone().unwrap_or_default(),
+        },
+    );
+
+    Ok(ApiResponse::ok(payroll))
+}
+
+/// 通知中显示的员工名称
+async fn staff_name(state: &AppState, user_id: Uuid) -> String {
+    user::Entity::find_by_id(user_id)
+        .one(&state.db)
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.display_name.unwrap_or(u.username))
+        .unwrap_or_default()
+}
+
+/// 下载 PDF 工资条
+async fn payslip(
+    PayslipPath { id }: PayslipPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<Response> {
+    let (filename, bytes) =
+        payslip_pdf::render(&state.db, state.s3.as_ref(), id, &claims).await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// 导出批量转账 CSV
+async fn export(
+    _: PayrollExportPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<ExportPayrollsDto>,
+) -> Result<Response> {
+    claims.require_boss()?;
+    let (filename, bytes) = transfer_export::export_csv(&state.db, claims.sub, dto).await?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// 批量转账完成后标记工资单已转账
+async fn mark_transferred(
+    _: MarkTransferredPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    key: IdempotencyKey,
+    AppJson(dto): AppJson<MarkTransferredDto>,
+) -> Result<ApiResponse<u64>> {
+    claims.require_boss()?;
+    key.run(&state.db, claims.sub, "payroll.mark_transferred", &dto, || async {
+        let count =
+            transfer_export::mark_transferred(&state.db, claims.sub, dto.payroll_ids.clone())
+                .await?;
+        Ok(ApiResponse::ok(count))
+    })
+    .await
+}
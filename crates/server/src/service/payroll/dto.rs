@@ -5,27 +5,96 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as Json;
 use uuid::Uuid;
 
+use entity::attendance;
+use entity::payroll::PayrollAckStatus;
+use entity::payroll_item::{self, PayrollItemKind};
+
+/// 工资舍入单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum PayrollRounding {
+    /// 四舍五入到角
+    Jiao,
+    /// 四舍五入到元
+    Yuan,
+}
+
+/// 手填的工资明细
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayrollItemDto {
+    /// 明细类型：bonus/deduction/advanceRepayment
+    pub kind: PayrollItemKind,
+    /// 金额（元），填正数，扣款和借支还款自动记为负数
+    pub amount: Decimal,
+    /// 说明
+    pub note: Option<String>,
+}
+
 /// 创建工资单请求
-#[derive(Debug, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreatePayrollDto {
     /// 员工ID
     pub user_id: Uuid,
-    /// 发放金额
-    pub amount: Decimal,
+    /// 发放金额，不传则等于明细合计；与合计不一致时按工坊策略处理
+    pub amount: Option<Decimal>,
     /// 关联的计件记录ID列表
+    #[serde(default)]
     pub record_ids: Vec<Uuid>,
+    /// 关联的考勤记录ID列表，计入计时工资
+    #[serde(default)]
+    pub attendance_ids: Vec<Uuid>,
+    /// 奖金、扣款、借支还款等明细
+    pub items: Option<Vec<PayrollItemDto>>,
+    /// 按重新计价后的应发金额自动扣回借支，不超过未还借支；明细中已有借支还款时忽略
+    #[serde(default)]
+    pub repay_advance: bool,
+    /// 舍入单位，不传则不舍入
+    pub rounding: Option<PayrollRounding>,
     /// 付款凭证图片URL
     pub payment_image: Option<String>,
     /// 备注
     pub note: Option<String>,
 }
 
+/// 作废工资单请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VoidPayrollDto {
+    /// 作废原因
+    pub reason: String,
+}
+
+/// 员工确认工资单请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AcknowledgePayrollDto {
+    /// 说明
+    pub comment: Option<String>,
+}
+
+/// 员工对工资单提出异议请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DisputePayrollDto {
+    /// 异议说明，如少算的计件
+    pub comment: String,
+}
+
+/// 老板处理工资单异议请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvePayrollDto {
+    /// 处理说明，如已补发或已核实无误
+    pub resolution: String,
+}
+
 /// 更新工资单请求
 #[derive(Debug, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdatePayrollDto {
-    /// 发放金额
+    /// 发放金额，与明细合计不一致时按工坊策略处理
     pub amount: Option<Decimal>,
     /// 付款凭证图片URL
     pub payment_image: Option<String>,
@@ -41,8 +110,14 @@ pub struct PayrollRecordResponse {
     pub id: Uuid,
     /// 计件数量
     pub quantity: i32,
-    /// 计件金额（元）
+    /// 计件金额（元），基础金额 + 阶梯加价 + 加急加价
     pub amount: Decimal,
+    /// 基础金额（元），数量 × 计件单价
+    pub base_amount: Decimal,
+    /// 阶梯加价（元）
+    pub tier_bonus: Decimal,
+    /// 加急加价（元）
+    pub urgency_bonus: Decimal,
     /// 记录时间
     pub recorded_at: chrono::DateTime<chrono::Utc>,
     /// 工序名称
@@ -53,6 +128,8 @@ pub struct PayrollRecordResponse {
     pub order_images: Option<Json>,
     /// 计件单价
     pub piece_price: Option<Decimal>,
+    /// 结算后调整的原因，普通计件为空
+    pub adjust_reason: Option<String>,
 }
 
 /// 工资单详情响应
@@ -73,6 +150,167 @@ pub struct PayrollDetailResponse {
     pub note: Option<String>,
     /// 发放时间
     pub paid_at: DateTime<Utc>,
+    /// 转账完成时间
+    pub transferred_at: Option<DateTime<Utc>>,
+    /// 员工确认状态: pending/acknowledged/disputed/resolved
+    pub ack_status: PayrollAckStatus,
+    /// 员工确认或提出异议时的说明
+    pub ack_comment: Option<String>,
+    /// 员工确认或提出异议的时间
+    pub acked_at: Option<DateTime<Utc>>,
+    /// 老板对异议的处理说明
+    pub resolution: Option<String>,
+    /// 作废时间
+    pub voided_at: Option<DateTime<Utc>>,
+    /// 作废原因
+    pub void_reason: Option<String>,
     /// 关联的计件记录列表
     pub records: Vec<PayrollRecordResponse>,
+    /// 工资明细：计件工资、奖金、扣款、借支还款、舍入等
+    pub items: Vec<payroll_item::Model>,
+    /// 结算的考勤记录
+    pub attendances: Vec<attendance::Model>,
+}
+
+/// 待发工资预览查询参数
+#[derive(Debug, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayrollPreviewParams {
+    /// 开始日期，格式 YYYY-MM-DD
+    pub start_date: Option<String>,
+    /// 结束日期，格式 YYYY-MM-DD
+    pub end_date: Option<String>,
+    /// 员工ID，不传则查询全部员工
+    pub user_id: Option<Uuid>,
+    /// 员工姓名关键词
+    pub user_name: Option<String>,
+}
+
+/// 按订单和工序汇总的待发计件
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayrollPreviewGroup {
+    /// 订单ID
+    pub order_id: Option<Uuid>,
+    /// 订单/产品名称
+    pub order_name: Option<String>,
+    /// 工序ID
+    pub process_id: Uuid,
+    /// 工序名称
+    pub process_name: Option<String>,
+    /// 录入时采用的计件单价，早期记录为空
+    pub piece_price: Option<Decimal>,
+    /// 待发数量（件）
+    pub quantity: i64,
+    /// 待发金额（元）
+    pub amount: Decimal,
+    /// 计件记录ID列表
+    pub record_ids: Vec<Uuid>,
+}
+
+/// 员工待发工资预览
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayrollPreviewItem {
+    /// 员工ID
+    pub user_id: Uuid,
+    /// 员工姓名
+    pub user_name: String,
+    /// 待发数量（件）
+    pub total_quantity: i64,
+    /// 待发计件金额（元），按当前金额，发放时会重新计价
+    pub piece_amount: Decimal,
+    /// 按订单和工序汇总的计件明细
+    pub groups: Vec<PayrollPreviewGroup>,
+    /// 未结算的考勤记录ID列表
+    pub attendance_ids: Vec<Uuid>,
+    /// 考勤总工时（小时）
+    pub hours: Decimal,
+    /// 待发计时金额（元）
+    pub time_amount: Decimal,
+    /// 未还借支（元）
+    pub outstanding_advance: Decimal,
+    /// 建议扣回的借支（元）
+    pub suggested_repayment: Decimal,
+    /// 建议发放金额（元），计件加计时减借支还款
+    pub suggested_amount: Decimal,
+}
+
+/// 待发工资预览响应
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayrollPreviewResponse {
+    /// 各员工待发工资列表
+    pub list: Vec<PayrollPreviewItem>,
+    /// 建议发放金额合计（元）
+    pub total_amount: Decimal,
+}
+
+/// 转账导出列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportColumn {
+    /// 员工显示名称
+    StaffName,
+    /// 收款人姓名，未填写收款账户时为员工名称
+    PayeeName,
+    /// 手机号
+    Phone,
+    /// 收款方式
+    Method,
+    /// 收款账号
+    Account,
+    /// 开户行
+    BankName,
+    /// 金额
+    Amount,
+    /// 备注，默认为工资单备注或“工资+发放日期”
+    Memo,
+}
+
+/// 预设导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportLayout {
+    /// 通用格式，包含全部列
+    Generic,
+    /// 支付宝批量转账：账号、姓名、金额、备注
+    Alipay,
+    /// 银行代发：账号、户名、开户行、金额、用途
+    Bank,
+}
+
+impl ExportLayout {
+    pub fn columns(self) -> Vec<ExportColumn> {
+        use ExportColumn::*;
+        match self {
+            Self::Generic => vec![
+                StaffName, PayeeName, Phone, Method, Account, BankName, Amount, Memo,
+            ],
+            Self::Alipay => vec![Account, PayeeName, Amount, Memo],
+            Self::Bank => vec![Account, PayeeName, BankName, Amount, Memo],
+        }
+    }
+}
+
+/// 导出工资单转账文件请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPayrollsDto {
+    /// 工资单ID列表
+    pub payroll_ids: Vec<Uuid>,
+    /// 预设格式，不传则为通用格式
+    pub layout: Option<ExportLayout>,
+    /// 自定义列及顺序，传入时覆盖预设格式
+    pub columns: Option<Vec<ExportColumn>>,
+    /// 是否输出表头，默认输出
+    pub include_header: Option<bool>,
+}
+
+/// 批量标记已转账请求
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkTransferredDto {
+    /// 工资单ID列表
+    pub payroll_ids: Vec<Uuid>,
 }
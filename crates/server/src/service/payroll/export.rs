@@ -0,0 +1,190 @@
+//! 批量转账导出：按银行/支付宝批量转账的 CSV 格式导出工资单，转账完成后批量标记
+
+use chrono::{Local, Utc};
+use sea_orm::{ColumnTrait, DbConn, EntityTrait, QueryFilter};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use super::dto::{ExportColumn, ExportLayout, ExportPayrollsDto};
+use crate::error::{AppError, Result};
+use entity::payroll::{self, Column};
+use entity::user::{self, PayoutMethod};
+
+/// 单次最多导出的工资单数
+const MAX_EXPORT: usize = 1000;
+
+/// UTF-8 BOM，便于 Excel 正确识别中文
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+fn header(column: ExportColumn) -> &'static str {
+    match column {
+        ExportColumn::StaffName => "员工",
+        ExportColumn::PayeeName => "收款人姓名",
+        ExportColumn::Phone => "手机号",
+        ExportColumn::Method => "收款方式",
+        ExportColumn::Account => "收款账号",
+        ExportColumn::BankName => "开户行",
+        ExportColumn::Amount => "金额",
+        ExportColumn::Memo => "备注",
+    }
+}
+
+fn method_label(method: PayoutMethod) -> &'static str {
+    match method {
+        PayoutMethod::Bank => "银行卡",
+        PayoutMethod::Alipay => "支付宝",
+        PayoutMethod::Wechat => "微信",
+    }
+}
+
+/// 以公式字符开头的单元格前加单引号，避免被表格软件当作公式执行
+fn escape_cell(cell: String) -> String {
+    if cell.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", cell)
+    } else {
+        cell
+    }
+}
+
+/// 导出选中的工资单，返回文件名和 CSV 内容
+///
+/// 导出收款账号列时，员工未填写收款账户会返回错误并列出这些员工
+pub async fn export_csv(
+    db: &DbConn,
+    boss_id: Uuid,
+    dto: ExportPayrollsDto,
+) -> Result<(String, Vec<u8>)> {
+    let mut seen = HashSet::new();
+    let mut ids = dto.payroll_ids;
+    ids.retain(|id| seen.insert(*id));
+    if ids.is_empty() {
+        return Err(AppError::BadRequest("至少选择一张工资单".to_string()));
+    }
+    if ids.len() > MAX_EXPORT {
+        return Err(AppError::BadRequest(format!(
+            "单次最多导出{}张工资单",
+            MAX_EXPORT
+        )));
+    }
+    let columns = match dto.columns {
+        Some(columns) if !columns.is_empty() => columns,
+        _ => dto.layout.unwrap_or(ExportLayout::Generic).columns(),
+    };
+
+    let payrolls: HashMap<Uuid, payroll::Model> = payroll::Entity::find()
+        .filter(Column::Id.is_in(ids.clone()))
+        .filter(Column::BossId.eq(boss_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+    if payrolls.len() != ids.len() {
+        return Err(AppError::BadRequest("部分工资单不存在或无权限".to_string()));
+    }
+    if payrolls.values().any(|p| p.is_voided()) {
+        return Err(AppError::BadRequest("已作废的工资单不能导出".to_string()));
+    }
+
+    let users: HashMap<Uuid, user::Model> = user::Entity::find()
+        .filter(user::Column::Id.is_in(payrolls.values().map(|p| p.user_id)))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    if columns.contains(&ExportColumn::Account) {
+        let mut missing: Vec<String> = Vec::new();
+        for id in &ids {
+            let staff = users.get(&payrolls[id].user_id);
+            if staff.and_then(|u| u.payout()).is_none() {
+                let name = staff
+                    .map(|u| u.display_name.clone().unwrap_or(u.username.clone()))
+                    .unwrap_or_default();
+                if !missing.contains(&name) {
+                    missing.push(name);
+                }
+            }
+        }
+        if !missing.is_empty() {
+            return Err(AppError::BadRequest(format!(
+                "以下员工未填写收款账户：{}",
+                missing.join("、")
+            )));
+        }
+    }
+
+    let mut writer = csv::Writer::from_writer(BOM.to_vec());
+    let csv_err = |e: csv::Error| AppError::Internal(e.to_string());
+    if dto.include_header.unwrap_or(true) {
+        writer
+            .write_record(columns.iter().map(|c| header(*c)))
+            .map_err(csv_err)?;
+    }
+    // 按请求中的顺序输出
+    for id in &ids {
+        let p = &payrolls[id];
+        let staff = users.get(&p.user_id);
+        let staff_name = staff
+            .map(|u| u.display_name.clone().unwrap_or(u.username.clone()))
+            .unwrap_or_default();
+        let account = staff.and_then(|u| u.payout());
+        let row = columns.iter().map(|c| {
+            let cell = match c {
+                ExportColumn::StaffName => staff_name.clone(),
+                ExportColumn::PayeeName => account
+                    .as_ref()
+                    .map(|a| a.account_name.clone())
+                    .unwrap_or_else(|| staff_name.clone()),
+                ExportColumn::Phone => staff.map(|u| u.phone.clone()).unwrap_or_default(),
+                ExportColumn::Method => account
+                    .as_ref()
+                    .map(|a| method_label(a.method).to_string())
+                    .unwrap_or_default(),
+                ExportColumn::Account => account
+                    .as_ref()
+                    .map(|a| a.account_no.clone())
+                    .unwrap_or_default(),
+                ExportColumn::BankName => account
+                    .as_ref()
+                    .and_then(|a| a.bank_name.clone())
+                    .unwrap_or_default(),
+                ExportColumn::Amount => format!("{:.2}", p.amount),
+                ExportColumn::Memo => p.note.clone().unwrap_or_else(|| {
+                    format!("工资 {}", p.paid_at.with_timezone(&Local).format("%Y-%m-%d"))
+                }),
+            };
+            match c {
+                ExportColumn::Amount => cell,
+                _ => escape_cell(cell),
+            }
+        });
+        writer.write_record(row).map_err(csv_err)?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let filename = format!(
+        "payroll-transfer-{}.csv",
+        Utc::now().with_timezone(&Local).format("%Y%m%d%H%M")
+    );
+    Ok((filename, bytes))
+}
+
+/// 批量标记已转账，已作废和已标记的工资单会被跳过，返回标记数量
+pub async fn mark_transferred(db: &DbConn, boss_id: Uuid, ids: Vec<Uuid>) -> Result<u64> {
+    let result = payroll::Entity::update_many()
+        .col_expr(
+            Column::TransferredAt,
+            sea_orm::sea_query::Expr::value(Utc::now()),
+        )
+        .filter(Column::Id.is_in(ids))
+        .filter(Column::BossId.eq(boss_id))
+        .filter(Column::VoidedAt.is_null())
+        .filter(Column::TransferredAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected)
+}
@@ -1,5 +1,7 @@
 mod controller;
 pub mod dto;
+pub mod export;
+pub mod payslip;
 pub mod service;
 
 pub use controller::router;
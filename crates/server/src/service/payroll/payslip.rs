@@ -0,0 +1,348 @@
+//! PDF 工资条：工坊抬头、计件明细、工资明细合计和备注
+
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use chrono::{Local, Utc};
+use printpdf::{
+    image_crate, Image, ImageTransform, IndirectFontRef, Line, Mm, PdfDocument,
+    PdfDocumentReference, PdfLayerReference, Point,
+};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, DbConn, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use super::dto::PayrollRecordResponse;
+use super::service::{get_records_internal, list_items};
+use crate::error::{AppError, Result};
+use crate::s3::S3Client;
+use crate::service::auth::Claims;
+use crate::traits::OwnedByBoss;
+use entity::payroll_item::{self, PayrollItemKind};
+use entity::user::Role;
+use entity::{payroll, user, workshop};
+
+/// 中文字体路径的环境变量
+const FONT_ENV: &str = "PAYSLIP_FONT_PATH";
+
+/// 未配置字体时依次尝试的系统中文字体
+const FALLBACK_FONTS: &[&str] = &[
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+    "/usr/share/fonts/droid-sans-fallback/DroidSansFallbackFull.ttf",
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+    "C:\\Windows\\Fonts\\simhei.ttf",
+];
+
+/// 已加载的字体内容，进程内只读取一次
+static FONT: OnceLock<Option<Arc<Vec<u8>>>> = OnceLock::new();
+
+/// 工资条使用的中文字体：优先环境变量，否则取第一个存在的系统字体
+fn font_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(FONT_ENV)
+        && !path.trim().is_empty()
+    {
+        return Some(PathBuf::from(path.trim()));
+    }
+    FALLBACK_FONTS
+        .iter()
+        .map(PathBuf::from)
+        .find(|p| p.is_file())
+}
+
+/// 工资条字体内容，首次调用时读取，启动时调用一次即可预先加载
+pub fn font() -> Option<Arc<Vec<u8>>> {
+    FONT.get_or_init(|| {
+        let path = font_path()?;
+        match std::fs::read(&path) {
+            Ok(bytes) => Some(Arc::new(bytes)),
+            Err(e) => {
+                tracing::error!("Failed to read payslip font {}: {}", path.display(), e);
+                None
+            }
+        }
+    })
+    .clone()
+}
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 15.0;
+const ROW_HEIGHT: f32 = 7.0;
+/// 明细表各列的横坐标：订单、工序、数量、单价、金额
+const COLUMNS: [f32; 5] = [15.0, 75.0, 125.0, 145.0, 170.0];
+
+/// 生成工资条，老板和收款员工可下载，返回文件名和 PDF 内容
+pub async fn render(
+    db: &DbConn,
+    s3: Option<&S3Client>,
+    id: Uuid,
+    claims: &Claims,
+) -> Result<(String, Vec<u8>)> {
+    let payroll = payroll::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Payroll {} not found", id)))?;
+    match claims.role {
+        Role::Boss => payroll.verify_owner(claims.sub)?,
+        Role::Staff if payroll.user_id != claims.sub => return Err(AppError::Forbidden),
+        Role::Staff => {}
+    }
+
+    let ws = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(payroll.boss_id))
+        .one(db)
+        .await?;
+    let staff_name = user::Entity::find_by_id(payroll.user_id)
+        .one(db)
+        .await?
+        .map(|u| u.display_name.unwrap_or(u.username))
+        .unwrap_or_default();
+    let records = get_records_internal(db, id).await?;
+    let items = list_items(db, id).await?;
+
+    let font = font().ok_or_else(|| {
+        AppError::Internal(format!(
+            "服务器未配置工资条中文字体，请设置环境变量 {}",
+            FONT_ENV
+        ))
+    })?;
+    let logo = match (s3, ws.as_ref().and_then(|w| w.image.clone())) {
+        (Some(s3), Some(key)) => fetch_logo(s3, &key).await,
+        _ => None,
+    };
+
+    let payslip = Payslip {
+        workshop_name: ws.as_ref().map(|w| w.name.clone()).unwrap_or_default(),
+        workshop_address: ws.as_ref().and_then(|w| w.address.clone()),
+        staff_name,
+        payroll,
+        records,
+        items,
+    };
+    let filename = format!(
+        "payslip-{}-{}.pdf",
+        payslip.payroll.paid_at.with_timezone(&Local).format("%Y%m%d"),
+        &id.simple().to_string()[..8]
+    );
+    // 嵌入字体和排版较耗 CPU，放到阻塞线程池执行
+    let bytes = tokio::task::spawn_blocking(move || payslip.draw(&font, logo))
+        .await
+        .map_err(|e| AppError::Internal(format!("生成工资条失败: {}", e)))??;
+    Ok((filename, bytes))
+}
+
+/// 从对象存储读取工坊 logo，读取或解码失败时不显示
+async fn fetch_logo(s3: &S3Client, key: &str) -> Option<image_crate::DynamicImage> {
+    let object = s3
+        .client
+        .get_object()
+        .bucket(&s3.bucket)
+        .key(key)
+        .send()
+        .await
+        .ok()?;
+    let bytes = object.body.collect().await.ok()?.into_bytes();
+    image_crate::load_from_memory(&bytes).ok()
+}
+
+fn item_label(kind: PayrollItemKind) -> &'static str {
+    match kind {
+        PayrollItemKind::PieceWork => "计件工资",
+        PayrollItemKind::TimeWork => "计时工资",
+        PayrollItemKind::Bonus => "奖金",
+        PayrollItemKind::Deduction => "扣款",
+        PayrollItemKind::AdvanceRepayment => "借支还款",
+        PayrollItemKind::Rounding => "抹零",
+        PayrollItemKind::ManualAdjustment => "手动调整",
+    }
+}
+
+fn money(amount: Decimal) -> String {
+    format!("¥{:.2}", amount)
+}
+
+/// 截断过长的文字，避免挤占相邻列
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        text.to_string()
+    } else {
+        let mut s: String = text.chars().take(max_chars - 1).collect();
+        s.push('…');
+        s
+    }
+}
+
+struct Payslip {
+    workshop_name: String,
+    workshop_address: Option<String>,
+    staff_name: String,
+    payroll: payroll::Model,
+    records: Vec<PayrollRecordResponse>,
+    items: Vec<payroll_item::Model>,
+}
+
+/// 逐行向下书写，空间不足时自动换页
+struct Cursor<'a> {
+    doc: &'a PdfDocumentReference,
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    y: f32,
+}
+
+impl Cursor<'_> {
+    fn ensure(&mut self, height: f32) {
+        if self.y - height < MARGIN {
+            let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+            self.layer = self.doc.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+
+    fn text(&self, text: &str, size: f32, x: f32) {
+        self.layer
+            .use_text(text, size, Mm(x), Mm(self.y), &self.font);
+    }
+
+    fn rule(&self) {
+        let line = Line {
+            points: vec![
+                (Point::new(Mm(MARGIN), Mm(self.y)), false),
+                (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(self.y)), false),
+            ],
+            is_closed: false,
+        };
+        self.layer.add_line(line);
+    }
+}
+
+impl Payslip {
+    fn draw(&self, font: &[u8], logo: Option<image_crate::DynamicImage>) -> Result<Vec<u8>> {
+        let pdf_err = |e: printpdf::Error| AppError::Internal(format!("生成工资条失败: {}", e));
+
+        let (doc, page, layer) =
+            PdfDocument::new("工资条", Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let font = doc.add_external_font(font).map_err(pdf_err)?;
+        let layer = doc.get_page(page).get_layer(layer);
+        let mut c = Cursor {
+            doc: &doc,
+            layer,
+            font,
+            y: PAGE_HEIGHT - MARGIN - 8.0,
+        };
+
+        // 工坊抬头
+        let mut title_x = MARGIN;
+        if let Some(logo) = logo {
+            let width_px = logo.width().max(1) as f32;
+            let dpi = 300.0;
+            let scale = 18.0 / (width_px / dpi * 25.4);
+            Image::from_dynamic_image(&logo).add_to_layer(
+                c.layer.clone(),
+                ImageTransform {
+                    translate_x: Some(Mm(MARGIN)),
+                    translate_y: Some(Mm(c.y - 6.0)),
+                    scale_x: Some(scale),
+                    scale_y: Some(scale),
+                    dpi: Some(dpi),
+                    ..Default::default()
+                },
+            );
+            title_x = MARGIN + 22.0;
+        }
+        c.text(&self.workshop_name, 18.0, title_x);
+        if let Some(address) = &self.workshop_address {
+            c.y -= 7.0;
+            c.text(address, 10.0, title_x);
+        }
+
+        c.y -= 14.0;
+        c.text("工资条", 16.0, MARGIN);
+        c.y -= 8.0;
+        let paid_at = self.payroll.paid_at.with_timezone(&Local);
+        c.text(&format!("员工：{}", self.staff_name), 11.0, MARGIN);
+        c.text(
+            &format!("发放时间：{}", paid_at.format("%Y-%m-%d %H:%M")),
+            11.0,
+            95.0,
+        );
+        c.y -= 6.0;
+        c.text(
+            &format!("工资单号：{}", self.payroll.id.simple()),
+            9.0,
+            MARGIN,
+        );
+        if self.payroll.is_voided() {
+            c.y -= 6.0;
+            c.text(
+                &format!(
+                    "已作废：{}",
+                    self.payroll.void_reason.as_deref().unwrap_or_default()
+                ),
+                11.0,
+                MARGIN,
+            );
+        }
+
+        // 计件明细
+        c.y -= 10.0;
+        for (x, title) in COLUMNS.iter().zip(["订单", "工序", "数量", "单价", "金额"]) {
+            c.text(title, 10.0, *x);
+        }
+        c.y -= 2.5;
+        c.rule();
+        for r in &self.records {
+            c.ensure(ROW_HEIGHT);
+            c.y -= ROW_HEIGHT;
+            let process = match &r.adjust_reason {
+                Some(_) => format!("{}（调整）", r.process_name.as_deref().unwrap_or("-")),
+                None => r.process_name.clone().unwrap_or_else(|| "-".to_string()),
+            };
+            c.text(&truncate(r.order_name.as_deref().unwrap_or("-"), 16), 9.0, COLUMNS[0]);
+            c.text(&truncate(&process, 13), 9.0, COLUMNS[1]);
+            c.text(&r.quantity.to_string(), 9.0, COLUMNS[2]);
+            c.text(
+                &r.piece_price.map(money).unwrap_or_else(|| "-".to_string()),
+                9.0,
+                COLUMNS[3],
+            );
+            c.text(&money(r.amount), 9.0, COLUMNS[4]);
+        }
+        c.y -= 3.0;
+        c.rule();
+
+        // 工资明细与合计
+        for item in &self.items {
+            c.ensure(ROW_HEIGHT);
+            c.y -= ROW_HEIGHT;
+            let label = match &item.note {
+                Some(note) => format!("{}（{}）", item_label(item.kind), truncate(note, 20)),
+                None => item_label(item.kind).to_string(),
+            };
+            c.text(&label, 10.0, COLUMNS[1]);
+            c.text(&money(item.amount), 10.0, COLUMNS[4]);
+        }
+        c.ensure(12.0);
+        c.y -= 10.0;
+        c.text("实发金额", 13.0, COLUMNS[1]);
+        c.text(&money(self.payroll.amount), 13.0, COLUMNS[4]);
+
+        if let Some(note) = &self.payroll.note {
+            c.ensure(ROW_HEIGHT * 2.0);
+            c.y -= ROW_HEIGHT * 2.0;
+            c.text(&format!("备注：{}", truncate(note, 60)), 10.0, MARGIN);
+        }
+
+        c.ensure(ROW_HEIGHT * 2.0);
+        c.y -= ROW_HEIGHT * 2.0;
+        c.text(
+            &format!(
+                "生成时间：{}",
+                Utc::now().with_timezone(&Local).format("%Y-%m-%d %H:%M")
+            ),
+            8.0,
+            MARGIN,
+        );
+
+        doc.save_to_bytes().map_err(pdf_err)
+    }
+}
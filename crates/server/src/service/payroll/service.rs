@@ -1,18 +1,30 @@
 use chrono::NaiveDate;
+use rust_decimal::{Decimal, RoundingStrategy};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    Set, TransactionTrait,
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set, TransactionTrait,
 };
+use sea_orm::sea_query::Expr;
 use std::collections::HashMap;
 use uuid::Uuid;
 
 use super::dto::{
-    CreatePayrollDto, PayrollDetailResponse, PayrollRecordResponse, UpdatePayrollDto,
+    AcknowledgePayrollDto, CreatePayrollDto, DisputePayrollDto, PayrollDetailResponse,
+    PayrollItemDto, PayrollPreviewGroup, PayrollPreviewItem, PayrollPreviewParams,
+    PayrollPreviewResponse, PayrollRecordResponse, PayrollRounding, ResolvePayrollDto,
+    UpdatePayrollDto, VoidPayrollDto,
 };
-use crate::common::{ListData, QueryParams};
-use entity::payroll::{self, Column, Model};
-use entity::{order, payroll_record, piece_record, process};
+use crate::common::{apply_date_filter, ListData, QueryParams};
+use entity::payroll::{self, Column, Model, PayrollAckStatus};
+use entity::payroll_item::{self, PayrollItemKind};
+use entity::workshop::{self, PayrollDivergencePolicy};
+use entity::{attendance, order, payroll_record, piece_record, process, user};
 use crate::error::{AppError, Result};
+use crate::service::advance::service as advance_service;
+use crate::service::piece_record::pricing::{self, PricingInput};
+use crate::service::piece_record::service as piece_record_service;
+use crate::traits::OwnedByBoss;
+use entity::piece_record_log::PieceRecordAction;
 
 pub async fn list(
     db: &DbConn,
@@ -37,6 +49,13 @@ pub async fn list(
         && let Ok(date) = NaiveDate::parse_from_str(end, "%Y-%m-%d") {
             query = query.filter(Column::PaidAt.lte(date.and_hms_opt(23, 59, 59).unwrap()));
         }
+    if let Some(ref statuses) = params.status {
+        let status_enums: Vec<PayrollAckStatus> =
+            statuses.iter().filter_map(|s| s.parse().ok()).collect();
+        if !status_enums.is_empty() {
+            query = query.filter(Column::AckStatus.is_in(status_enums));
+        }
+    }
 
     let order_dir = if params.sort_order == "asc" {
         sea_orm::Order::Asc
@@ -55,16 +74,33 @@ pub async fn list(
     Ok(ListData { list, total })
 }
 
-pub async fn create(db: &DbConn, dto: CreatePayrollDto, boss_id: Uuid) -> Result<Model> {
+/// 创建工资单，金额由计件合计和手填明细汇总得出，返回与手填金额不一致时的提醒
+pub async fn create(
+    db: &DbConn,
+    dto: CreatePayrollDto,
+    boss_id: Uuid,
+) -> Result<(Model, Option<String>)> {
+    let txn = db.begin().await?;
+    let result = create_in(&txn, dto, boss_id).await?;
+    txn.commit().await?;
+    Ok(result)
+}
+
+/// 在调用方的事务内创建工资单
+pub(crate) async fn create_in<C: ConnectionTrait>(
+    conn: &C,
+    dto: CreatePayrollDto,
+    boss_id: Uuid,
+) -> Result<(Model, Option<String>)> {
     // 验证计件记录状态
-    if dto.record_ids.is_empty() {
-        return Err(AppError::BadRequest("至少选择一条计件记录".into()));
+    if dto.record_ids.is_empty() && dto.attendance_ids.is_empty() {
+        return Err(AppError::BadRequest("至少选择一条计件或考勤记录".into()));
     }
 
     let records = piece_record::Entity::find()
         .filter(piece_record::Column::Id.is_in(dto.record_ids.clone()))
         .filter(piece_record::Column::BossId.eq(boss_id))
-        .all(db)
+        .all(conn)
         .await?;
 
     if records.len() != dto.record_ids.len() {
@@ -72,6 +108,12 @@ pub async fn create(db: &DbConn, dto: CreatePayrollDto, boss_id: Uuid) -> Result
     }
 
     for rec in &records {
+        if rec.user_id != dto.user_id {
+            return Err(AppError::BadRequest(format!(
+                "计件记录 {} 不属于该员工",
+                rec.id
+            )));
+        }
         if rec.status != piece_record::PieceRecordStatus::Approved {
             return Err(AppError::BadRequest(format!(
                 "计件记录 {} 状态不是已批准",
@@ -80,8 +122,94 @@ pub async fn create(db: &DbConn, dto: CreatePayrollDto, boss_id: Uuid) -> Result
         }
     }
 
-    // 使用事务
-    let txn = db.begin().await?;
+    // 结算前按当前阶梯和加急规则重新计算金额
+    let process_ids: Vec<Uuid> = records.iter().map(|r| r.process_id).collect();
+    let processes: HashMap<Uuid, process::Model> = process::Entity::find()
+        .filter(process::Column::Id.is_in(process_ids))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+    let order_ids: Vec<Uuid> = processes.values().map(|p| p.order_id).collect();
+    let orders: HashMap<Uuid, order::Model> = order::Entity::find()
+        .filter(order::Column::Id.is_in(order_ids))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|o| (o.id, o))
+        .collect();
+
+    // 结算价，调整记录按差额结算不重新计价
+    let mut settled = Vec::with_capacity(records.len());
+    let mut piece_total = Decimal::ZERO;
+    for rec in &records {
+        let mut active: piece_record::ActiveModel = rec.clone().into();
+        let mut amount = rec.amount;
+        if !rec.is_adjustment()
+            && let Some(proc) = processes.get(&rec.process_id)
+            && let Some(ord) = orders.get(&proc.order_id)
+        {
+            let input = PricingInput {
+                user_id: rec.user_id,
+                quantity: rec.quantity,
+                piece_price: rec.piece_price.unwrap_or(proc.piece_price),
+                recorded_at: rec.recorded_at,
+                record_id: rec.id,
+            };
+            let breakdown = pricing::compute(conn, proc, ord, input).await?;
+            breakdown.apply(&mut active);
+            amount = breakdown.amount;
+        }
+        active.status = Set(piece_record::PieceRecordStatus::Settled);
+        piece_total += amount;
+        settled.push((rec.id, active));
+    }
+
+    let attendances =
+        unsettled_attendances(conn, &dto.attendance_ids, boss_id, dto.user_id).await?;
+    let time_total: Decimal = attendances.iter().map(|a| a.amount).sum();
+
+    let mut extras = dto.items.unwrap_or_default();
+    if dto.repay_advance
+        && !extras
+            .iter()
+            .any(|i| i.kind == PayrollItemKind::AdvanceRepayment)
+    {
+        let payable = piece_total + time_total + extras_total(&extras);
+        let outstanding = advance_service::outstanding(conn, boss_id, dto.user_id).await?;
+        extras.extend(suggest_repayment(outstanding, payable));
+    }
+    let mut items = build_items(
+        WorkTotals {
+            piece: piece_total,
+            records: records.len(),
+            time: time_total,
+            attendances: attendances.len(),
+        },
+        &extras,
+        dto.rounding,
+    )?;
+    let repayment: Decimal = -items
+        .iter()
+        .filter(|i| i.kind == PayrollItemKind::AdvanceRepayment)
+        .map(|i| i.amount)
+        .sum::<Decimal>();
+    if repayment > Decimal::ZERO {
+        let outstanding = advance_service::outstanding(conn, boss_id, dto.user_id).await?;
+        if repayment > outstanding {
+            return Err(AppError::BadRequest(format!(
+                "借支还款 {} 超过未还借支 {}",
+                repayment, outstanding
+            )));
+        }
+    }
+    let policy = divergence_policy(conn, boss_id).await?;
+    let warning = reconcile(&mut items, dto.amount, policy)?;
+    let amount = items_total(&items);
+    if amount < Decimal::ZERO {
+        return Err(AppError::BadRequest("实发金额不能为负数".into()));
+    }
 
     // 创建工资单
     let payroll_id = Uuid::new_v4();
@@ -89,31 +217,472 @@ pub async fn create(db: &DbConn, dto: CreatePayrollDto, boss_id: Uuid) -> Result
         id: Set(payroll_id),
         user_id: Set(dto.user_id),
         boss_id: Set(boss_id),
-        amount: Set(dto.amount),
+        amount: Set(amount),
         payment_image: Set(dto.payment_image),
         note: Set(dto.note),
         paid_at: Set(chrono::Utc::now()),
+        transferred_at: Set(None),
+        ack_status: Set(PayrollAckStatus::Pending),
+        ack_comment: Set(None),
+        acked_at: Set(None),
+        resolution: Set(None),
+        voided_at: Set(None),
+        void_reason: Set(None),
     };
-    let payroll = model.insert(&txn).await?;
+    let payroll = model.insert(conn).await?;
 
-    // 创建关联记录并更新计件状态
-    for rec in &records {
-        // 创建关联
+    // 只认领仍为已批准的计件，并发结算时后到的一方整体回滚
+    if !settled.is_empty() {
+        let result = piece_record::Entity::update_many()
+            .col_expr(
+                piece_record::Column::Status,
+                Expr::value(piece_record::PieceRecordStatus::Settled),
+            )
+            .filter(piece_record::Column::Id.is_in(settled.iter().map(|(id, _)| *id)))
+            .filter(piece_record::Column::Status.eq(piece_record::PieceRecordStatus::Approved))
+            .exec(conn)
+            .await?;
+        if result.rows_affected != settled.len() as u64 {
+            return Err(AppError::Conflict("部分计件已被其他工资单结算，请刷新后重试".into()));
+        }
+    }
+
+    // 创建关联记录并写入结算价
+    for (record_id, active) in settled {
         let pr = payroll_record::ActiveModel {
             id: Set(Uuid::new_v4()),
             payroll_id: Set(payroll_id),
-            piece_record_id: Set(rec.id),
+            piece_record_id: Set(record_id),
         };
-        pr.insert(&txn).await?;
+        pr.insert(conn).await?;
+        active.update(conn).await?;
+    }
+    if !attendances.is_empty() {
+        // 只认领仍未结算的考勤，并发结算时后到的一方整体回滚
+        let result = attendance::Entity::update_many()
+            .col_expr(attendance::Column::PayrollId, Expr::value(Some(payroll_id)))
+            .filter(attendance::Column::Id.is_in(attendances.iter().map(|a| a.id)))
+            .filter(attendance::Column::PayrollId.is_null())
+            .exec(conn)
+            .await?;
+        if result.rows_affected != attendances.len() as u64 {
+            return Err(AppError::Conflict("部分考勤已被其他工资单结算，请刷新后重试".into()));
+        }
+    }
+    insert_items(conn, payroll_id, 0, items).await?;
 
-        // 更新计件状态为已结算
-        let mut active: piece_record::ActiveModel = rec.clone().into();
-        active.status = Set(piece_record::PieceRecordStatus::Settled);
-        active.update(&txn).await?;
+    Ok((payroll, warning))
+}
+
+/// 校验并加载待结算的考勤：属于该员工、已下班且未结算
+async fn unsettled_attendances<C: ConnectionTrait>(
+    conn: &C,
+    ids: &[Uuid],
+    boss_id: Uuid,
+    user_id: Uuid,
+) -> Result<Vec<attendance::Model>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut ids = ids.to_vec();
+    ids.sort();
+    ids.dedup();
+    let attendances = attendance::Entity::find()
+        .filter(attendance::Column::Id.is_in(ids.clone()))
+        .filter(attendance::Column::BossId.eq(boss_id))
+        .all(conn)
+        .await?;
+    if attendances.len() != ids.len() {
+        return Err(AppError::BadRequest("部分考勤记录不存在或无权限".into()));
+    }
+    for att in &attendances {
+        if att.user_id != user_id {
+            return Err(AppError::BadRequest(format!(
+                "{} 的考勤不属于该员工",
+                att.work_date
+            )));
+        }
+        if att.is_settled() {
+            return Err(AppError::BadRequest(format!(
+                "{} 的考勤已结算",
+                att.work_date
+            )));
+        }
+        if att.is_open() {
+            return Err(AppError::BadRequest(format!(
+                "{} 的考勤尚未下班打卡",
+                att.work_date
+            )));
+        }
     }
+    Ok(attendances)
+}
 
-    txn.commit().await?;
-    Ok(payroll)
+/// 待写入的工资明细
+struct ItemDraft {
+    kind: PayrollItemKind,
+    amount: Decimal,
+    note: Option<String>,
+}
+
+fn items_total(items: &[ItemDraft]) -> Decimal {
+    items.iter().map(|i| i.amount).sum()
+}
+
+/// 计件和计时工资合计及记录条数
+struct WorkTotals {
+    piece: Decimal,
+    records: usize,
+    time: Decimal,
+    attendances: usize,
+}
+
+/// 按计件记录和考勤当前金额估算明细合计，同时校验手填明细；结算时会重新计价
+pub(crate) fn estimate_total(
+    piece_total: Decimal,
+    time_total: Decimal,
+    extras: &[PayrollItemDto],
+) -> Result<Decimal> {
+    let work = WorkTotals {
+        piece: piece_total,
+        records: 0,
+        time: time_total,
+        attendances: 0,
+    };
+    Ok(items_total(&build_items(work, extras, None)?))
+}
+
+/// 手填奖金和扣款的合计，扣款为负
+fn extras_total(extras: &[PayrollItemDto]) -> Decimal {
+    extras
+        .iter()
+        .map(|i| match i.kind {
+            PayrollItemKind::Bonus => i.amount,
+            PayrollItemKind::Deduction => -i.amount,
+            _ => Decimal::ZERO,
+        })
+        .sum()
+}
+
+/// 按应发金额建议扣回的借支，不超过未还借支，无需扣回时为空
+pub(crate) fn suggest_repayment(outstanding: Decimal, payable: Decimal) -> Option<PayrollItemDto> {
+    let amount = outstanding.min(payable).round_dp(2);
+    (amount > Decimal::ZERO).then(|| PayrollItemDto {
+        kind: PayrollItemKind::AdvanceRepayment,
+        amount,
+        note: Some("借支还款".to_string()),
+    })
+}
+
+/// 由计件、计时合计和手填明细生成工资明细，需要舍入时追加舍入差额
+fn build_items(
+    work: WorkTotals,
+    extras: &[PayrollItemDto],
+    rounding: Option<PayrollRounding>,
+) -> Result<Vec<ItemDraft>> {
+    let mut items = Vec::new();
+    if work.records > 0 || !work.piece.is_zero() {
+        items.push(ItemDraft {
+            kind: PayrollItemKind::PieceWork,
+            amount: work.piece,
+            note: Some(format!("{}条计件记录", work.records)),
+        });
+    }
+    if work.attendances > 0 || !work.time.is_zero() {
+        items.push(ItemDraft {
+            kind: PayrollItemKind::TimeWork,
+            amount: work.time,
+            note: Some(format!("{}条考勤记录", work.attendances)),
+        });
+    }
+
+    for extra in extras {
+        if extra.amount <= Decimal::ZERO {
+            return Err(AppError::BadRequest("明细金额必须大于0".into()));
+        }
+        let amount = match extra.kind {
+            PayrollItemKind::Bonus => extra.amount,
+            PayrollItemKind::Deduction | PayrollItemKind::AdvanceRepayment => -extra.amount,
+            _ => {
+                return Err(AppError::BadRequest(
+                    "明细只能填写奖金、扣款或借支还款".into(),
+                ));
+            }
+        };
+        items.push(ItemDraft {
+            kind: extra.kind,
+            amount: amount.round_dp(2),
+            note: extra
+                .note
+                .as_ref()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+        });
+    }
+
+    if let Some(rounding) = rounding {
+        let subtotal = items_total(&items);
+        let dp = match rounding {
+            PayrollRounding::Jiao => 1,
+            PayrollRounding::Yuan => 0,
+        };
+        let diff = subtotal.round_dp_with_strategy(dp, RoundingStrategy::MidpointAwayFromZero)
+            - subtotal;
+        if !diff.is_zero() {
+            items.push(ItemDraft {
+                kind: PayrollItemKind::Rounding,
+                amount: diff,
+                note: None,
+            });
+        }
+    }
+    Ok(items)
+}
+
+/// 老板所在工坊的金额不一致处理方式，未创建工坊时按提醒处理
+async fn divergence_policy<C: ConnectionTrait>(
+    conn: &C,
+    boss_id: Uuid,
+) -> Result<PayrollDivergencePolicy> {
+    Ok(workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .one(conn)
+        .await?
+        .map(|w| w.payroll_divergence_policy)
+        .unwrap_or(PayrollDivergencePolicy::Warn))
+}
+
+/// 手填发放金额与明细合计不一致时按工坊策略处理：禁止时报错，提醒时追加手动调整明细
+fn reconcile(
+    items: &mut Vec<ItemDraft>,
+    manual: Option<Decimal>,
+    policy: PayrollDivergencePolicy,
+) -> Result<Option<String>> {
+    let Some(manual) = manual else {
+        return Ok(None);
+    };
+    let total = items_total(items);
+    let diff = manual.round_dp(2) - total;
+    if diff.is_zero() {
+        return Ok(None);
+    }
+    match policy {
+        PayrollDivergencePolicy::Block => Err(AppError::BadRequest(format!(
+            "发放金额 {} 与明细合计 {} 不一致",
+            manual, total
+        ))),
+        PayrollDivergencePolicy::Warn => {
+            items.push(ItemDraft {
+                kind: PayrollItemKind::ManualAdjustment,
+                amount: diff,
+                note: None,
+            });
+            Ok(Some(format!(
+                "发放金额与明细合计 {} 相差 {}，已记为手动调整",
+                total, diff
+            )))
+        }
+    }
+}
+
+/// 写入明细，`start` 为第一条明细的显示顺序
+async fn insert_items<C: ConnectionTrait>(
+    conn: &C,
+    payroll_id: Uuid,
+    start: i32,
+    items: Vec<ItemDraft>,
+) -> Result<()> {
+    for (position, item) in (start..).zip(items) {
+        payroll_item::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            payroll_id: Set(payroll_id),
+            kind: Set(item.kind),
+            amount: Set(item.amount),
+            note: Set(item.note),
+            position: Set(position),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await?;
+    }
+    Ok(())
+}
+
+/// 工资单明细，按写入顺序排列
+pub(crate) async fn list_items<C: ConnectionTrait>(
+    conn: &C,
+    payroll_id: Uuid,
+) -> Result<Vec<payroll_item::Model>> {
+    Ok(payroll_item::Entity::find()
+        .filter(payroll_item::Column::PayrollId.eq(payroll_id))
+        .order_by_asc(payroll_item::Column::Position)
+        .order_by_asc(payroll_item::Column::Id)
+        .all(conn)
+        .await?)
+}
+
+/// 预览中单个员工的待发工资：计件按 (工序, 录入单价) 分组，以及未结算的考勤
+#[derive(Default)]
+struct PendingPay<'a> {
+    groups: HashMap<(Uuid, Option<Decimal>), PayrollPreviewGroup>,
+    attendances: Vec<&'a attendance::Model>,
+}
+
+/// 待发工资预览：按员工汇总已批准未结算的计件和已下班未结算的考勤，计件按订单和工序分组，
+/// 有未还借支时按应发金额建议扣回
+pub async fn preview(
+    db: &DbConn,
+    boss_id: Uuid,
+    params: PayrollPreviewParams,
+) -> Result<PayrollPreviewResponse> {
+    let mut record_query = piece_record::Entity::find()
+        .filter(piece_record::Column::BossId.eq(boss_id))
+        .filter(piece_record::Column::Status.eq(piece_record::PieceRecordStatus::Approved));
+    let mut attendance_query = attendance::Entity::find()
+        .filter(attendance::Column::BossId.eq(boss_id))
+        .filter(attendance::Column::PayrollId.is_null());
+    if let Some(uid) = params.user_id {
+        record_query = record_query.filter(piece_record::Column::UserId.eq(uid));
+        attendance_query = attendance_query.filter(attendance::Column::UserId.eq(uid));
+    }
+    record_query = apply_date_filter(
+        record_query,
+        piece_record::Column::RecordedAt,
+        params.start_date.as_deref(),
+        params.end_date.as_deref(),
+    );
+    attendance_query = apply_date_filter(
+        attendance_query,
+        attendance::Column::WorkDate,
+        params.start_date.as_deref(),
+        params.end_date.as_deref(),
+    );
+    let records = record_query
+        .order_by_asc(piece_record::Column::RecordedAt)
+        .all(db)
+        .await?;
+    let attendances: Vec<attendance::Model> = attendance_query
+        .order_by_asc(attendance::Column::WorkDate)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter(|a| !a.is_open())
+        .collect();
+
+    let process_ids: Vec<Uuid> = records.iter().map(|r| r.process_id).collect();
+    let processes: HashMap<Uuid, process::Model> = process::Entity::find()
+        .filter(process::Column::Id.is_in(process_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+    let order_ids: Vec<Uuid> = processes.values().map(|p| p.order_id).collect();
+    let orders: HashMap<Uuid, order::Model> = order::Entity::find()
+        .filter(order::Column::Id.is_in(order_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|o| (o.id, o))
+        .collect();
+
+    // 按员工分组，计件再按工序和录入时的单价分组（调价前后的记录分开列出）
+    let mut by_user: HashMap<Uuid, PendingPay> = HashMap::new();
+    for rec in &records {
+        let pending = by_user.entry(rec.user_id).or_default();
+        let key = (rec.process_id, rec.piece_price);
+        let group = pending.groups.entry(key).or_insert_with(|| {
+            let proc = processes.get(&rec.process_id);
+            let ord = proc.and_then(|p| orders.get(&p.order_id));
+            PayrollPreviewGroup {
+                order_id: proc.map(|p| p.order_id),
+                order_name: ord.map(|o| o.product_name.clone()),
+                process_id: rec.process_id,
+                process_name: proc.map(|p| p.name.clone()),
+                piece_price: rec.piece_price,
+                quantity: 0,
+                amount: Decimal::ZERO,
+                record_ids: Vec::new(),
+            }
+        });
+        group.quantity += rec.quantity as i64;
+        group.amount += rec.amount;
+        group.record_ids.push(rec.id);
+    }
+    for att in &attendances {
+        by_user.entry(att.user_id).or_default().attendances.push(att);
+    }
+
+    let user_ids: Vec<Uuid> = by_user.keys().copied().collect();
+    let users: HashMap<Uuid, user::Model> = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids.clone()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+    let advance_totals = advance_service::totals(db, Some(boss_id), &user_ids).await?;
+    let name_filter = params
+        .user_name
+        .map(|n| n.trim().to_lowercase())
+        .filter(|n| !n.is_empty());
+
+    let mut list = Vec::with_capacity(by_user.len());
+    for (user_id, PendingPay { groups, attendances: atts }) in by_user {
+        let Some(staff) = users.get(&user_id) else {
+            continue;
+        };
+        let user_name = staff
+            .display_name
+            .clone()
+            .unwrap_or_else(|| staff.username.clone());
+        if let Some(ref filter) = name_filter
+            && !user_name.to_lowercase().contains(filter)
+        {
+            continue;
+        }
+
+        let mut groups: Vec<PayrollPreviewGroup> = groups.into_values().collect();
+        groups.sort_by(|a, b| {
+            (&a.order_name, &a.process_name, a.piece_price).cmp(&(
+                &b.order_name,
+                &b.process_name,
+                b.piece_price,
+            ))
+        });
+        let total_quantity: i64 = groups.iter().map(|g| g.quantity).sum();
+        let piece_amount: Decimal = groups.iter().map(|g| g.amount).sum();
+        let hours: Decimal = atts.iter().map(|a| a.hours).sum();
+        let time_amount: Decimal = atts.iter().map(|a| a.amount).sum();
+
+        let outstanding_advance = advance_totals
+            .get(&user_id)
+            .map_or(Decimal::ZERO, |(advanced, repaid)| advanced - repaid);
+        let extras: Vec<PayrollItemDto> =
+            suggest_repayment(outstanding_advance, piece_amount + time_amount)
+                .into_iter()
+                .collect();
+        let suggested_repayment = extras.first().map_or(Decimal::ZERO, |i| i.amount);
+        let suggested_amount = estimate_total(piece_amount, time_amount, &extras)?;
+
+        list.push(PayrollPreviewItem {
+            user_id,
+            user_name,
+            total_quantity,
+            piece_amount,
+            groups,
+            attendance_ids: atts.iter().map(|a| a.id).collect(),
+            hours,
+            time_amount,
+            outstanding_advance,
+            suggested_repayment,
+            suggested_amount,
+        });
+    }
+    list.sort_by(|a, b| a.user_name.cmp(&b.user_name));
+    let total_amount: Decimal = list.iter().map(|i| i.suggested_amount).sum();
+
+    Ok(PayrollPreviewResponse { list, total_amount })
 }
 
 pub async fn get_one(
@@ -131,6 +700,12 @@ pub async fn get_one(
         .ok_or_else(|| AppError::NotFound(format!("Payroll {} not found", id)))?;
 
     let records = get_records_internal(db, id).await?;
+    let items = list_items(db, id).await?;
+    let attendances = attendance::Entity::find()
+        .filter(attendance::Column::PayrollId.eq(id))
+        .order_by_asc(attendance::Column::WorkDate)
+        .all(db)
+        .await?;
 
     Ok(PayrollDetailResponse {
         id: payroll.id,
@@ -140,19 +715,68 @@ pub async fn get_one(
         payment_image: payroll.payment_image,
         note: payroll.note,
         paid_at: payroll.paid_at,
+        transferred_at: payroll.transferred_at,
+        ack_status: payroll.ack_status,
+        ack_comment: payroll.ack_comment,
+        acked_at: payroll.acked_at,
+        resolution: payroll.resolution,
+        voided_at: payroll.voided_at,
+        void_reason: payroll.void_reason,
         records,
+        items,
+        attendances,
     })
 }
 
-pub async fn update(db: &DbConn, id: Uuid, dto: UpdatePayrollDto) -> Result<Model> {
-    let payroll = payroll::Entity::find_by_id(id)
-        .one(db)
-        .await?
-        .ok_or_else(|| AppError::NotFound(format!("Payroll {} not found", id)))?;
+/// 修改工资单，修改金额时按工坊策略重新核对明细，返回不一致提醒；已确认的金额变动后需员工重新确认
+pub async fn update(
+    db: &DbConn,
+    id: Uuid,
+    dto: UpdatePayrollDto,
+    boss_id: Uuid,
+) -> Result<(Model, Option<String>)> {
+    let payroll = find_owned(db, id, boss_id).await?;
+    if payroll.is_voided() {
+        return Err(AppError::BadRequest("工资单已作废，不能修改".into()));
+    }
 
+    let txn = db.begin().await?;
+    let mut warning = None;
+    let boss_id = payroll.boss_id;
+    let (old_amount, ack_status) = (payroll.amount, payroll.ack_status);
     let mut model: payroll::ActiveModel = payroll.into();
     if let Some(v) = dto.amount {
-        model.amount = Set(v);
+        // 先去掉原有的手动调整，再按新金额核对
+        payroll_item::Entity::delete_many()
+            .filter(payroll_item::Column::PayrollId.eq(id))
+            .filter(payroll_item::Column::Kind.eq(PayrollItemKind::ManualAdjustment))
+            .exec(&txn)
+            .await?;
+        let existing_items = list_items(&txn, id).await?;
+        let next_position = existing_items.last().map_or(0, |i| i.position + 1);
+        let mut items: Vec<ItemDraft> = existing_items
+            .into_iter()
+            .map(|i| ItemDraft {
+                kind: i.kind,
+                amount: i.amount,
+                note: i.note,
+            })
+            .collect();
+        let existing = items.len();
+        let policy = divergence_policy(&txn, boss_id).await?;
+        warning = reconcile(&mut items, Some(v), policy)?;
+        let amount = items_total(&items);
+        if amount < Decimal::ZERO {
+            return Err(AppError::BadRequest("实发金额不能为负数".into()));
+        }
+        insert_items(&txn, id, next_position, items.split_off(existing)).await?;
+        model.amount = Set(amount);
+        // 员工确认的是旧金额，改动后需重新确认；有异议的留待处理流程
+        if amount != old_amount && ack_status == PayrollAckStatus::Acknowledged {
+            model.ack_status = Set(PayrollAckStatus::Pending);
+            model.ack_comment = Set(None);
+            model.acked_at = Set(None);
+        }
     }
     if let Some(v) = dto.note {
         model.note = Set(Some(v));
@@ -160,18 +784,182 @@ pub async fn update(db: &DbConn, id: Uuid, dto: UpdatePayrollDto) -> Result<Mode
     if let Some(v) = dto.payment_image {
         model.payment_image = Set(Some(v));
     }
+    let payroll = model.update(&txn).await?;
+    txn.commit().await?;
+    Ok((payroll, warning))
+}
+
+/// 删除工资单，关联的计件记录退回已批准
+pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
+    let payroll = find_owned(db, id, boss_id).await?;
+
+    let txn = db.begin().await?;
+    if !payroll.is_voided() {
+        unsettle_records(&txn, id, boss_id, Some("工资单已删除".to_string())).await?;
+    }
+    payroll_record::Entity::delete_many()
+        .filter(payroll_record::Column::PayrollId.eq(id))
+        .exec(&txn)
+        .await?;
+    payroll::Entity::delete_by_id(id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+/// 作废工资单：保留工资单和明细，关联的计件记录退回已批准，可重新结算
+pub async fn void(db: &DbConn, id: Uuid, boss_id: Uuid, dto: VoidPayrollDto) -> Result<Model> {
+    let payroll = find_owned(db, id, boss_id).await?;
+    if payroll.is_voided() {
+        return Err(AppError::BadRequest("工资单已作废".into()));
+    }
+    let reason = dto.reason.trim().to_string();
+    if reason.is_empty() {
+        return Err(AppError::BadRequest("请填写作废原因".into()));
+    }
+
+    let txn = db.begin().await?;
+    unsettle_records(&txn, id, boss_id, Some(reason.clone())).await?;
+    let mut model: payroll::ActiveModel = payroll.into();
+    model.voided_at = Set(Some(chrono::Utc::now()));
+    model.void_reason = Set(Some(reason));
+    let payroll = model.update(&txn).await?;
+    txn.commit().await?;
+    Ok(payroll)
+}
+
+/// 员工确认工资单金额无误，待确认或异议已处理时可确认
+pub async fn acknowledge(
+    db: &DbConn,
+    id: Uuid,
+    user_id: Uuid,
+    dto: AcknowledgePayrollDto,
+) -> Result<Model> {
+    let payroll = find_payee(db, id, user_id).await?;
+    if !matches!(
+        payroll.ack_status,
+        PayrollAckStatus::Pending | PayrollAckStatus::Resolved
+    ) {
+        return Err(AppError::BadRequest("该工资单当前不能确认".into()));
+    }
+    let mut model: payroll::ActiveModel = payroll.into();
+    model.ack_status = Set(PayrollAckStatus::Acknowledged);
+    model.ack_comment = Set(dto.comment.filter(|s| !s.trim().is_empty()));
+    model.acked_at = Set(Some(chrono::Utc::now()));
+    Ok(model.update(db).await?)
+}
+
+/// 员工对工资单金额提出异议，需填写说明
+pub async fn dispute(
+    db: &DbConn,
+    id: Uuid,
+    user_id: Uuid,
+    dto: DisputePayrollDto,
+) -> Result<Model> {
+    let payroll = find_payee(db, id, user_id).await?;
+    if !matches!(
+        payroll.ack_status,
+        PayrollAckStatus::Pending | PayrollAckStatus::Resolved
+    ) {
+        return Err(AppError::BadRequest("该工资单当前不能提出异议".into()));
+    }
+    let comment = dto.comment.trim().to_string();
+    if comment.is_empty() {
+        return Err(AppError::BadRequest("请填写异议说明".into()));
+    }
+    let mut model: payroll::ActiveModel = payroll.into();
+    model.ack_status = Set(PayrollAckStatus::Disputed);
+    model.ack_comment = Set(Some(comment));
+    model.acked_at = Set(Some(chrono::Utc::now()));
+    Ok(model.update(db).await?)
+}
+
+/// 老板处理员工异议，处理后由员工再次确认
+pub async fn resolve(
+    db: &DbConn,
+    id: Uuid,
+    boss_id: Uuid,
+    dto: ResolvePayrollDto,
+) -> Result<Model> {
+    let payroll = find_owned(db, id, boss_id).await?;
+    if payroll.ack_status != PayrollAckStatus::Disputed {
+        return Err(AppError::BadRequest("该工资单没有待处理的异议".into()));
+    }
+    let resolution = dto.resolution.trim().to_string();
+    if resolution.is_empty() {
+        return Err(AppError::BadRequest("请填写处理说明".into()));
+    }
+    let mut model: payroll::ActiveModel = payroll.into();
+    model.ack_status = Set(PayrollAckStatus::Resolved);
+    model.resolution = Set(Some(resolution));
     Ok(model.update(db).await?)
 }
 
-pub async fn delete(db: &DbConn, id: Uuid) -> Result<()> {
-    let result = payroll::Entity::delete_by_id(id).exec(db).await?;
-    if result.rows_affected == 0 {
-        return Err(AppError::NotFound(format!("Payroll {} not found", id)));
+/// 查找员工本人收到的、未作废的工资单
+async fn find_payee(db: &DbConn, id: Uuid, user_id: Uuid) -> Result<Model> {
+    let payroll = payroll::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Payroll {} not found", id)))?;
+    if payroll.user_id != user_id {
+        return Err(AppError::Forbidden);
+    }
+    if payroll.is_voided() {
+        return Err(AppError::BadRequest("工资单已作废".into()));
+    }
+    Ok(payroll)
+}
+
+async fn find_owned(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<Model> {
+    let payroll = payroll::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Payroll {} not found", id)))?;
+    payroll.verify_owner(boss_id)?;
+    Ok(payroll)
+}
+
+/// 将工资单关联的已结算计件退回已批准并记录操作历史，关联的考勤恢复为未结算
+async fn unsettle_records<C: ConnectionTrait>(
+    conn: &C,
+    payroll_id: Uuid,
+    operator_id: Uuid,
+    reason: Option<String>,
+) -> Result<()> {
+    let piece_ids: Vec<Uuid> = payroll_record::Entity::find()
+        .filter(payroll_record::Column::PayrollId.eq(payroll_id))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|r| r.piece_record_id)
+        .collect();
+    let records = piece_record::Entity::find()
+        .filter(piece_record::Column::Id.is_in(piece_ids))
+        .filter(piece_record::Column::Status.eq(piece_record::PieceRecordStatus::Settled))
+        .all(conn)
+        .await?;
+    for rec in records {
+        piece_record_service::log_action(
+            &rec,
+            operator_id,
+            PieceRecordAction::Unsettled,
+            rec.quantity,
+            reason.clone(),
+        )
+        .insert(conn)
+        .await?;
+        let mut active: piece_record::ActiveModel = rec.into();
+        active.status = Set(piece_record::PieceRecordStatus::Approved);
+        active.update(conn).await?;
     }
+    attendance::Entity::update_many()
+        .col_expr(attendance::Column::PayrollId, Expr::value(None::<Uuid>))
+        .filter(attendance::Column::PayrollId.eq(payroll_id))
+        .exec(conn)
+        .await?;
     Ok(())
 }
 
-async fn get_records_internal(db: &DbConn, payroll_id: Uuid) -> Result<Vec<PayrollRecordResponse>> {
+pub(crate) async fn get_records_internal(db: &DbConn, payroll_id: Uuid) -> Result<Vec<PayrollRecordResponse>> {
     let pr_records = payroll_record::Entity::find()
         .filter(payroll_record::Column::PayrollId.eq(payroll_id))
         .all(db)
@@ -207,16 +995,23 @@ async fn get_records_internal(db: &DbConn, payroll_id: Uuid) -> Result<Vec<Payro
         .map(|r| {
             let proc = processes.get(&r.process_id);
             let ord = proc.and_then(|p| orders.get(&p.order_id));
+            let piece_price = r.piece_price.or(proc.map(|p| p.piece_price));
+            let tier_bonus = r.tier_bonus.unwrap_or_default();
+            let urgency_bonus = r.urgency_bonus.unwrap_or_default();
 
             PayrollRecordResponse {
                 id: r.id,
                 quantity: r.quantity,
                 amount: r.amount,
+                base_amount: r.amount - tier_bonus - urgency_bonus,
+                tier_bonus,
+                urgency_bonus,
                 recorded_at: r.recorded_at,
                 process_name: proc.map(|p| p.name.clone()),
                 order_name: ord.map(|o| o.product_name.clone()),
                 order_images: ord.and_then(|o| o.images.clone()),
-                piece_price: proc.map(|p| p.piece_price),
+                piece_price,
+                adjust_reason: r.adjust_reason.clone(),
             }
         })
         .collect())
@@ -2,35 +2,27 @@ use axum::extract::State;
 use axum_extra::extract::Query;
 use axum::{Extension, Router};
 use axum_extra::routing::{RouterExt, TypedPath};
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde::Deserialize;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use super::dto::{BatchApproveDto, CreatePieceRecordDto, PieceRecordResponse, UpdatePieceRecordDto};
+use super::dto::{
+    AdjustPieceRecordDto, BatchApproveDto, BatchCreatePieceRecordDto, BatchCreatePieceRecordResponse, BatchRejectDto,
+    CreatePieceRecordDto, PieceRecordResponse,
+    RejectPieceRecordDto, ResubmitPieceRecordDto, UpdatePieceRecordDto, REJECT_REASON_PRESETS,
+};
 use crate::common::{ApiResponse, ListData, QueryParams};
-use entity::piece_record::Model;
-use entity::user::Role;
-use entity::workshop;
+use entity::piece_record::{Model, PieceRecordStatus};
+use entity::piece_record_log;
 use crate::error::{AppJson, Result};
+use crate::idempotency::IdempotencyKey;
 use crate::service::auth::Claims;
 use crate::service::notification::Notification;
 use crate::AppState;
 
 use super::service;
-use sea_orm::DbConn;
-
-/// 获取工坊的计件单位
-async fn get_piece_unit(db: &DbConn, boss_id: Uuid) -> String {
-    workshop::Entity::find()
-        .filter(workshop::Column::OwnerId.eq(boss_id))
-        .one(db)
-        .await
-        .ok()
-        .flatten()
-        .map(|w| w.piece_unit)
-        .unwrap_or_else(|| "件".to_string())
-}
 
 #[derive(TypedPath)]
 #[typed_path("/piece-records")]
@@ -54,6 +46,32 @@ pub struct PieceRecordRejectPath {
     id: Uuid,
 }
 
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/piece-records/{id}/resubmit")]
+pub struct PieceRecordResubmitPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/piece-records/{id}/adjust")]
+pub struct PieceRecordAdjustPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/piece-records/{id}/history")]
+pub struct PieceRecordHistoryPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath)]
+#[typed_path("/piece-records/reject-reasons")]
+pub struct RejectReasonsPath;
+
+#[derive(TypedPath)]
+#[typed_path("/piece-records/batch")]
+pub struct BatchCreatePath;
+
 #[derive(TypedPath)]
 #[typed_path("/piece-records/batch-approve")]
 pub struct BatchApprovePath;
@@ -73,6 +91,11 @@ pub fn router() -> Router<Arc<AppState>> {
         .typed_post(reject)
         .typed_post(batch_approve)
         .typed_post(batch_reject)
+        .typed_post(batch_create)
+        .typed_post(resubmit)
+        .typed_post(adjust)
+        .typed_get(history)
+        .typed_get(reject_reasons)
 }
 
 async fn list(
@@ -90,26 +113,75 @@ async fn create(
     _: PieceRecordsPath,
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<Claims>,
+    key: IdempotencyKey,
     AppJson(dto): AppJson<CreatePieceRecordDto>,
 ) -> Result<ApiResponse<Model>> {
-    let record = service::create(&state.db, dto.clone(), &claims).await?;
+    key.run(&state.db, claims.sub, "piece_record.create", &dto, || async {
+        let (record, warning) = service::create(&state.db, dto.clone(), &claims).await?;
+
+        // 员工提交计件时通知老板，自动通过的计件汇总在每日日报中
+        if record.status == PieceRecordStatus::Pending {
+            let response = service::get_one(&state.db, record.id, &claims).await?;
+            let unit = service::get_piece_unit(&state.db, record.boss_id).await;
+            state.notifier.send(
+                record.boss_id,
+                Notification::RecordSubmitted {
+                    user_name: response.user_name.unwrap_or_default(),
+                    process_name: response.process_name.unwrap_or_default(),
+                    quantity: record.quantity,
+                    unit,
+                },
+            );
+        }
+
+        Ok(ApiResponse::ok_with_warning(record, warning))
+    })
+    .await
+}
 
-    // 员工提交计件时通知老板
-    if claims.role == Role::Staff {
-        let response = service::get_one(&state.db, record.id, &claims).await?;
-        let unit = get_piece_unit(&state.db, record.boss_id).await;
-        state.notifier.send(
-            record.boss_id,
-            Notification::RecordSubmitted {
-                user_name: response.user_name.unwrap_or_default(),
-                process_name: response.process_name.unwrap_or_default(),
-                quantity: record.quantity,
-                unit,
+async fn batch_create(
+    _: BatchCreatePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    key: IdempotencyKey,
+    AppJson(dto): AppJson<BatchCreatePieceRecordDto>,
+) -> Result<ApiResponse<BatchCreatePieceRecordResponse>> {
+    claims.require_boss()?;
+    key.run(&state.db, claims.sub, "piece_record.batch_create", &dto, || async {
+        let (records, warning) =
+            service::batch_create(&state.db, claims.sub, dto.clone()).await?;
+
+        // 每个员工发送一条汇总通知
+        let mut per_user: HashMap<Uuid, (usize, i64, Decimal)> = HashMap::new();
+        for r in &records {
+            let entry = per_user.entry(r.user_id).or_default();
+            entry.0 += 1;
+            entry.1 += r.quantity as i64;
+            entry.2 += r.amount;
+        }
+        let unit = service::get_piece_unit(&state.db, claims.sub).await;
+        for (user_id, (count, quantity, amount)) in per_user {
+            state.notifier.send(
+                user_id,
+                Notification::RecordsBatchCreated {
+                    count,
+                    quantity,
+                    unit: unit.clone(),
+                    amount: amount.to_string(),
+                },
+            );
+        }
+
+        Ok(ApiResponse::ok_with_warning(
+            BatchCreatePieceRecordResponse {
+                count: records.len(),
+                total_quantity: records.iter().map(|r| r.quantity as i64).sum(),
+                total_amount: records.iter().map(|r| r.amount).sum(),
             },
-        );
-    }
-
-    Ok(ApiResponse::ok(record))
+            warning,
+        ))
+    })
+    .await
 }
 
 async fn get_one(
@@ -128,10 +200,8 @@ async fn update(
     Extension(claims): Extension<Claims>,
     AppJson(dto): AppJson<UpdatePieceRecordDto>,
 ) -> Result<ApiResponse<Model>> {
-    claims.require_boss()?;
-    Ok(ApiResponse::ok(
-        service::update(&state.db, id, dto, claims.sub).await?,
-    ))
+    let (record, warning) = service::update(&state.db, id, dto, &claims).await?;
+    Ok(ApiResponse::ok_with_warning(record, warning))
 }
 
 async fn delete(
@@ -139,8 +209,7 @@ async fn delete(
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<Claims>,
 ) -> Result<ApiResponse<()>> {
-    claims.require_boss()?;
-    service::delete(&state.db, id, claims.sub).await?;
+    service::delete(&state.db, id, &claims).await?;
     Ok(ApiResponse::ok(()))
 }
 
@@ -153,10 +222,10 @@ async fn approve(
 
     // 先获取记录详情用于通知
     let response = service::get_one(&state.db, id, &claims).await?;
-    let record = service::approve(&state.db, id, claims.sub).await?;
+    let (record, warning) = service::approve(&state.db, id, claims.sub).await?;
 
     // 通知员工审批通过
-    let unit = get_piece_unit(&state.db, record.boss_id).await;
+    let unit = service::get_piece_unit(&state.db, record.boss_id).await;
     state.notifier.send(
         record.user_id,
         Notification::RecordApproved {
@@ -167,28 +236,32 @@ async fn approve(
         },
     );
 
-    Ok(ApiResponse::ok(record))
+    Ok(ApiResponse::ok_with_warning(record, warning))
 }
 
 async fn reject(
     PieceRecordRejectPath { id }: PieceRecordRejectPath,
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<Claims>,
+    dto: Option<AppJson<RejectPieceRecordDto>>,
 ) -> Result<ApiResponse<Model>> {
     claims.require_boss()?;
+    let dto = dto.map(|AppJson(dto)| dto).unwrap_or_default();
+    let reason = service::build_reject_reason(dto.preset, dto.reason)?;
 
     // 先获取记录详情用于通知
     let response = service::get_one(&state.db, id, &claims).await?;
-    let record = service::reject(&state.db, id, claims.sub).await?;
+    let record = service::reject(&state.db, id, claims.sub, reason).await?;
 
     // 通知员工审批拒绝
-    let unit = get_piece_unit(&state.db, record.boss_id).await;
+    let unit = service::get_piece_unit(&state.db, record.boss_id).await;
     state.notifier.send(
         record.user_id,
         Notification::RecordRejected {
             process_name: response.process_name.unwrap_or_default(),
             quantity: record.quantity,
             unit,
+            reason: record.reject_reason.clone(),
         },
     );
 
@@ -199,47 +272,53 @@ async fn batch_approve(
     _: BatchApprovePath,
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<Claims>,
+    key: IdempotencyKey,
     AppJson(dto): AppJson<BatchApproveDto>,
 ) -> Result<ApiResponse<u64>> {
     claims.require_boss()?;
-
-    // 先获取待处理记录用于通知
-    let pending_records = service::get_pending_records(&state.db, &dto.ids, claims.sub).await?;
-
-    let count = service::batch_approve(&state.db, dto.ids, claims.sub).await?;
-
-    // 发送通知给每个员工
-    let unit = get_piece_unit(&state.db, claims.sub).await;
-    for record in pending_records {
-        state.notifier.send(
-            record.user_id,
-            Notification::RecordApproved {
-                process_name: record.process_name.unwrap_or_default(),
-                quantity: record.quantity,
-                unit: unit.clone(),
-                amount: record.amount.to_string(),
-            },
-        );
-    }
-
-    Ok(ApiResponse::ok(count))
+    key.run(&state.db, claims.sub, "piece_record.batch_approve", &dto, || async {
+        // 先获取待处理记录用于通知
+        let pending_records =
+            service::get_pending_records(&state.db, &dto.ids, claims.sub).await?;
+
+        let (count, warning) =
+            service::batch_approve(&state.db, dto.ids.clone(), claims.sub).await?;
+
+        // 发送通知给每个员工
+        let unit = service::get_piece_unit(&state.db, claims.sub).await;
+        for record in pending_records {
+            state.notifier.send(
+                record.user_id,
+                Notification::RecordApproved {
+                    process_name: record.process_name.unwrap_or_default(),
+                    quantity: record.quantity,
+                    unit: unit.clone(),
+                    amount: record.amount.to_string(),
+                },
+            );
+        }
+
+        Ok(ApiResponse::ok_with_warning(count, warning))
+    })
+    .await
 }
 
 async fn batch_reject(
     _: BatchRejectPath,
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<Claims>,
-    AppJson(dto): AppJson<BatchApproveDto>,
+    AppJson(dto): AppJson<BatchRejectDto>,
 ) -> Result<ApiResponse<u64>> {
     claims.require_boss()?;
+    let reason = service::build_reject_reason(dto.preset, dto.reason)?;
 
     // 先获取待处理记录用于通知
     let pending_records = service::get_pending_records(&state.db, &dto.ids, claims.sub).await?;
 
-    let count = service::batch_reject(&state.db, dto.ids, claims.sub).await?;
+    let count = service::batch_reject(&state.db, dto.ids, claims.sub, reason.clone()).await?;
 
     // 发送通知给每个员工
-    let unit = get_piece_unit(&state.db, claims.sub).await;
+    let unit = service::get_piece_unit(&state.db, claims.sub).await;
     for record in pending_records {
         state.notifier.send(
             record.user_id,
@@ -247,9 +326,76 @@ async fn batch_reject(
                 process_name: record.process_name.unwrap_or_default(),
                 quantity: record.quantity,
                 unit: unit.clone(),
+                reason: reason.clone(),
             },
         );
     }
 
     Ok(ApiResponse::ok(count))
 }
+
+async fn resubmit(
+    PieceRecordResubmitPath { id }: PieceRecordResubmitPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<ResubmitPieceRecordDto>,
+) -> Result<ApiResponse<Model>> {
+    let (record, warning) = service::resubmit(&state.db, id, claims.sub, dto).await?;
+
+    // 通知老板重新审核
+    let response = service::get_one(&state.db, record.id, &claims).await?;
+    let unit = service::get_piece_unit(&state.db, record.boss_id).await;
+    state.notifier.send(
+        record.boss_id,
+        Notification::RecordResubmitted {
+            user_name: response.user_name.unwrap_or_default(),
+            process_name: response.process_name.unwrap_or_default(),
+            quantity: record.quantity,
+            unit,
+        },
+    );
+
+    Ok(ApiResponse::ok_with_warning(record, warning))
+}
+
+async fn adjust(
+    PieceRecordAdjustPath { id }: PieceRecordAdjustPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(dto): AppJson<AdjustPieceRecordDto>,
+) -> Result<ApiResponse<Model>> {
+    claims.require_boss()?;
+    let record = service::adjust(&state.db, id, claims.sub, dto).await?;
+
+    // 通知员工调整将计入下次工资
+    let response = service::get_one(&state.db, record.id, &claims).await?;
+    let unit = service::get_piece_unit(&state.db, record.boss_id).await;
+    state.notifier.send(
+        record.user_id,
+        Notification::RecordAdjusted {
+            process_name: response.process_name.unwrap_or_default(),
+            quantity: record.quantity,
+            unit,
+            amount: record.amount.to_string(),
+            reason: record.adjust_reason.clone().unwrap_or_default(),
+        },
+    );
+
+    Ok(ApiResponse::ok(record))
+}
+
+async fn history(
+    PieceRecordHistoryPath { id }: PieceRecordHistoryPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Vec<piece_record_log::Model>>> {
+    Ok(ApiResponse::ok(
+        service::history(&state.db, id, &claims).await?,
+    ))
+}
+
+async fn reject_reasons(_: RejectReasonsPath) -> Result<ApiResponse<Vec<String>>> {
+    Ok(ApiResponse::ok(
+        REJECT_REASON_PRESETS.iter().map(|s| s.to_string()).collect(),
+    ))
+}
@@ -0,0 +1,129 @@
+//! 自动审核日报：每天固定时间向老板汇总自上次日报以来按规则自动通过的计件
+//!
+//! 工坊记录最近发送日期，发送前原子占用，避免多实例重复发送；服务停机错过时启动后补发。
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Timelike, Utc};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ColumnTrait, Condition, DbConn, EntityTrait, QueryFilter, QuerySelect};
+use uuid::Uuid;
+
+use super::service::get_piece_unit;
+use crate::error::Result;
+use crate::service::notification::{Notification, Notifier};
+use crate::AppState;
+use entity::piece_record::{self, Column, PieceRecordStatus};
+use entity::workshop;
+
+/// 日报发送时间（本地时间，时）
+const DIGEST_HOUR: u32 = 20;
+
+/// 启动日报定时任务，启动时先补发最近一次应发而未发的日报
+pub fn spawn(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        run(&state, last_due_date(Local::now())).await;
+        loop {
+            tokio::time::sleep(until_next_run()).await;
+            run(&state, Local::now().date_naive()).await;
+        }
+    });
+}
+
+/// 发送 `date` 的日报，统计窗口为前一天发送时间到当天发送时间，前后两次日报首尾相接
+async fn run(state: &AppState, date: NaiveDate) {
+    let from = local_time(date - Duration::days(1), DIGEST_HOUR);
+    let to = local_time(date, DIGEST_HOUR);
+    if let Err(e) = send_digests(&state.db, &state.notifier, date, from, to).await {
+        tracing::error!("Failed to send auto-approve digest: {}", e);
+    }
+}
+
+/// 距下一次发送的时长
+fn until_next_run() -> std::time::Duration {
+    let now = Local::now().naive_local();
+    let today = now.date().and_hms_opt(DIGEST_HOUR, 0, 0).unwrap();
+    let next = if now < today {
+        today
+    } else {
+        today + Duration::days(1)
+    };
+    (next - now).to_std().unwrap_or_default()
+}
+
+/// 最近一次应发日报的日期：当天已过发送时间为当天，否则为前一天
+fn last_due_date(now: DateTime<Local>) -> NaiveDate {
+    let today = now.date_naive();
+    if now.hour() >= DIGEST_HOUR {
+        today
+    } else {
+        today - Duration::days(1)
+    }
+}
+
+/// 本地某天某时转为 UTC
+fn local_time(date: NaiveDate, hour: u32) -> DateTime<Utc> {
+    let naive = date.and_hms_opt(hour, 0, 0).unwrap();
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&naive))
+}
+
+/// 占用工坊当天的日报发送，多实例或重复补发时只有一次成功
+async fn claim(db: &DbConn, boss_id: Uuid, date: NaiveDate) -> Result<bool> {
+    let res = workshop::Entity::update_many()
+        .col_expr(workshop::Column::DigestSentOn, Expr::value(date))
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .filter(
+            Condition::any()
+                .add(workshop::Column::DigestSentOn.is_null())
+                .add(workshop::Column::DigestSentOn.lt(date)),
+        )
+        .exec(db)
+        .await?;
+    Ok(res.rows_affected > 0)
+}
+
+/// 按老板汇总 [from, to) 内自动通过的计件，占用 `date` 的发送后通知
+pub async fn send_digests(
+    db: &DbConn,
+    notifier: &Notifier,
+    date: NaiveDate,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<()> {
+    let rows: Vec<(Uuid, i64, Option<i64>, Option<Decimal>)> = piece_record::Entity::find()
+        .select_only()
+        .column(Column::BossId)
+        .column_as(Column::Id.count(), "count")
+        .column_as(Column::Quantity.sum(), "quantity")
+        .column_as(Column::Amount.sum(), "amount")
+        .filter(Column::AutoApproveRule.is_not_null())
+        .filter(Column::Status.ne(PieceRecordStatus::Rejected))
+        .filter(Column::RecordedAt.gte(from))
+        .filter(Column::RecordedAt.lt(to))
+        .group_by(Column::BossId)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    for (boss_id, count, quantity, amount) in rows {
+        if !claim(db, boss_id, date).await? {
+            continue;
+        }
+        let unit = get_piece_unit(db, boss_id).await;
+        notifier.send(
+            boss_id,
+            Notification::AutoApprovedDigest {
+                count,
+                quantity: quantity.unwrap_or(0),
+                unit,
+                amount: amount.unwrap_or(Decimal::ZERO).to_string(),
+            },
+        );
+    }
+    Ok(())
+}
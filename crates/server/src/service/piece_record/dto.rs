@@ -6,7 +6,7 @@ use uuid::Uuid;
 use entity::piece_record::{PieceRecordStatus, RecordedBy};
 
 /// 创建计件记录请求
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreatePieceRecordDto {
     /// 工序ID
@@ -15,6 +15,8 @@ pub struct CreatePieceRecordDto {
     pub user_id: Uuid,
     /// 计件数量
     pub quantity: i32,
+    /// 计件凭证照片的文件 key 列表（通过 /upload/presign 上传）
+    pub images: Option<Vec<String>>,
 }
 
 /// 计件记录详情响应
@@ -51,6 +53,20 @@ pub struct PieceRecordResponse {
     pub order_image: Option<String>,
     /// 计件单价（关联字段）
     pub piece_price: Option<Decimal>,
+    /// 最近一次驳回原因
+    pub reject_reason: Option<String>,
+    /// 自动通过时命中的规则名称
+    pub auto_approve_rule: Option<String>,
+    /// 计件凭证照片的文件 key 列表
+    pub images: Vec<String>,
+    /// 调整记录对应的原计件ID，普通计件为空
+    pub adjusts_record_id: Option<Uuid>,
+    /// 调整原因
+    pub adjust_reason: Option<String>,
+    /// 扫码录入时对应的扎ID
+    pub bundle_id: Option<Uuid>,
+    /// 修改前的原始提交数量，未修改过时为空
+    pub original_quantity: Option<i32>,
 }
 
 /// 更新计件记录请求
@@ -59,12 +75,101 @@ pub struct PieceRecordResponse {
 pub struct UpdatePieceRecordDto {
     /// 计件数量
     pub quantity: Option<i32>,
+    /// 计件凭证照片，传入时整体替换
+    pub images: Option<Vec<String>>,
 }
 
 /// 批量审批请求
-#[derive(Debug, Deserialize, JsonSchema)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BatchApproveDto {
     /// 要批量审批的计件记录ID列表
     pub ids: Vec<Uuid>,
 }
+
+/// 常用驳回原因
+pub const REJECT_REASON_PRESETS: &[&str] = &[
+    "数量有误",
+    "工序选错",
+    "质量不合格",
+    "重复提交",
+    "未按要求完成",
+];
+
+/// 驳回计件请求
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RejectPieceRecordDto {
+    /// 常用驳回原因，需为预设值之一
+    pub preset: Option<String>,
+    /// 补充说明
+    pub reason: Option<String>,
+}
+
+/// 批量驳回请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRejectDto {
+    /// 要批量驳回的计件记录ID列表
+    pub ids: Vec<Uuid>,
+    /// 常用驳回原因，需为预设值之一
+    pub preset: Option<String>,
+    /// 补充说明
+    pub reason: Option<String>,
+}
+
+/// 重新提交被驳回的计件请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResubmitPieceRecordDto {
+    /// 修改后的计件数量，不传则沿用原数量
+    pub quantity: Option<i32>,
+    /// 计件凭证照片，传入时整体替换
+    pub images: Option<Vec<String>>,
+}
+
+/// 已结算计件的调整请求，数量和金额均为差额
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdjustPieceRecordDto {
+    /// 数量差额，可为负数
+    pub quantity: Option<i32>,
+    /// 金额差额（元），不传则按原记录单价 × 数量差额计算
+    pub amount: Option<Decimal>,
+    /// 调整原因
+    pub reason: String,
+}
+
+/// 批量录入的一行
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchPieceRecordItem {
+    /// 员工ID
+    pub user_id: Uuid,
+    /// 工序ID
+    pub process_id: Uuid,
+    /// 计件数量
+    pub quantity: i32,
+    /// 完成时间，不传则为当前时间
+    pub recorded_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// 批量录入计件请求（老板代录）
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchCreatePieceRecordDto {
+    /// 计件明细
+    pub items: Vec<BatchPieceRecordItem>,
+}
+
+/// 批量录入结果
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchCreatePieceRecordResponse {
+    /// 录入条数
+    pub count: usize,
+    /// 录入总数量
+    pub total_quantity: i64,
+    /// 录入总金额（元）
+    pub total_amount: Decimal,
+}
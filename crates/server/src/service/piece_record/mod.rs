@@ -1,5 +1,7 @@
 mod controller;
+pub mod digest;
 pub mod dto;
+pub mod pricing;
 pub mod service;
 
 pub use controller::router;
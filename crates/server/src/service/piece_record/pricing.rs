@@ -0,0 +1,149 @@
+//! 计件金额计算：基础单价 + 阶梯加价 + 订单加急加价
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, TimeZone, Utc};
+use rust_decimal::Decimal;
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, EntityTrait, ExprTrait, QueryFilter, QuerySelect, Set,
+};
+use uuid::Uuid;
+
+use crate::error::Result;
+use entity::piece_record::{self, PieceRecordStatus};
+use entity::process::{RateTier, TierPeriod};
+use entity::{order, process};
+
+/// 计件金额明细
+#[derive(Debug, Clone, Copy)]
+pub struct AmountBreakdown {
+    /// 采用的基础单价
+    pub piece_price: Decimal,
+    /// 基础金额，数量 × 基础单价
+    pub base_amount: Decimal,
+    /// 阶梯加价
+    pub tier_bonus: Decimal,
+    /// 加急加价
+    pub urgency_bonus: Decimal,
+    /// 合计金额
+    pub amount: Decimal,
+}
+
+impl AmountBreakdown {
+    /// 写入计件记录
+    pub fn apply(&self, model: &mut piece_record::ActiveModel) {
+        model.piece_price = Set(Some(self.piece_price));
+        model.tier_bonus = Set(Some(self.tier_bonus));
+        model.urgency_bonus = Set(Some(self.urgency_bonus));
+        model.amount = Set(self.amount);
+    }
+}
+
+/// 待计价的计件
+pub struct PricingInput {
+    pub user_id: Uuid,
+    pub quantity: i32,
+    pub piece_price: Decimal,
+    pub recorded_at: DateTime<Utc>,
+    /// 记录ID，同一时间录入的按ID先后累计
+    pub record_id: Uuid,
+}
+
+/// 计算计件金额
+pub async fn compute<C: ConnectionTrait>(
+    conn: &C,
+    proc: &process::Model,
+    ord: &order::Model,
+    input: PricingInput,
+) -> Result<AmountBreakdown> {
+    let quantity = Decimal::from(input.quantity);
+    let base_amount = input.piece_price * quantity;
+
+    let tiers = proc.tiers();
+    let tiered_amount = if tiers.is_empty() {
+        base_amount
+    } else {
+        let prior = prior_quantity(conn, proc, &input).await?;
+        tiered_amount(&tiers, input.piece_price, prior, input.quantity as i64)
+    };
+    let tier_bonus = tiered_amount - base_amount;
+
+    let urgency_bonus = match ord.urgency_multiplier {
+        Some(m) if m > Decimal::ONE => (tiered_amount * (m - Decimal::ONE)).round_dp(2),
+        _ => Decimal::ZERO,
+    };
+
+    Ok(AmountBreakdown {
+        piece_price: input.piece_price,
+        base_amount,
+        tier_bonus,
+        urgency_bonus,
+        amount: tiered_amount + urgency_bonus,
+    })
+}
+
+/// 周期内该员工在本工序、按 (录入时间, ID) 排在本条记录之前的累计件数（不含已驳回）
+async fn prior_quantity<C: ConnectionTrait>(
+    conn: &C,
+    proc: &process::Model,
+    input: &PricingInput,
+) -> Result<i64> {
+    let sum: Option<Option<i64>> = piece_record::Entity::find()
+        .select_only()
+        .column_as(piece_record::Column::Quantity.sum(), "sum")
+        .filter(piece_record::Column::ProcessId.eq(proc.id))
+        .filter(piece_record::Column::UserId.eq(input.user_id))
+        .filter(piece_record::Column::Status.is_in([
+            PieceRecordStatus::Pending,
+            PieceRecordStatus::Approved,
+            PieceRecordStatus::Settled,
+        ]))
+        .filter(
+            piece_record::Column::RecordedAt.gte(period_start(proc.tier_period, input.recorded_at)),
+        )
+        .filter(
+            Condition::any()
+                .add(piece_record::Column::RecordedAt.lt(input.recorded_at))
+                .add(
+                    piece_record::Column::RecordedAt
+                        .eq(input.recorded_at)
+                        .and(piece_record::Column::Id.lt(input.record_id)),
+                ),
+        )
+        .into_tuple()
+        .one(conn)
+        .await?;
+    Ok(sum.flatten().unwrap_or(0))
+}
+
+/// 累计周期的起始时间（按本地时间划分天和月）
+fn period_start(period: TierPeriod, at: DateTime<Utc>) -> DateTime<Utc> {
+    let local = at.with_timezone(&Local).date_naive();
+    let date = match period {
+        TierPeriod::Daily => local,
+        TierPeriod::Monthly => NaiveDate::from_ymd_opt(local.year(), local.month(), 1).unwrap(),
+    };
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap())
+        .earliest()
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or(at)
+}
+
+/// 按阶梯计算第 prior+1 到 prior+quantity 件的金额
+fn tiered_amount(tiers: &[RateTier], base_price: Decimal, prior: i64, quantity: i64) -> Decimal {
+    let start = prior;
+    let end = prior + quantity;
+    // 各价格区间的下界（不含），按升序排列
+    let mut bounds: Vec<(i64, Decimal)> = vec![(0, base_price)];
+    bounds.extend(tiers.iter().map(|t| (t.threshold as i64, t.piece_price)));
+
+    let mut amount = Decimal::ZERO;
+    for (i, (lower, price)) in bounds.iter().enumerate() {
+        let upper = bounds.get(i + 1).map(|(l, _)| *l).unwrap_or(i64::MAX);
+        let from = std::cmp::max(start, *lower);
+        let to = std::cmp::min(end, upper);
+        if to > from {
+            amount += *price * Decimal::from(to - from);
+        }
+    }
+    amount
+}
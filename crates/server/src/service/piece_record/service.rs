@@ -1,17 +1,24 @@
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbConn, EntityLoaderTrait, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, DbConn, DbErr, EntityLoaderTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, SqlErr, TransactionTrait,
 };
 use uuid::Uuid;
 
-use super::dto::{CreatePieceRecordDto, PieceRecordResponse, UpdatePieceRecordDto};
+use super::dto::{
+    AdjustPieceRecordDto, BatchCreatePieceRecordDto, BatchPieceRecordItem, CreatePieceRecordDto,
+    PieceRecordResponse, ResubmitPieceRecordDto, UpdatePieceRecordDto, REJECT_REASON_PRESETS,
+};
+use super::pricing::{self, PricingInput};
 use crate::common::{ListData, QueryParams};
 use crate::error::{AppError, Result};
 use crate::service::auth::Claims;
+use crate::service::process::service as process_service;
 use entity::order::OrderStatus;
+use entity::piece_record_log::{self, PieceRecordAction};
 use entity::user::{self, Role};
+use entity::workshop::{self, OverproductionPolicy};
 use entity::{
     order,
     piece_record::{self, Column, Model, PieceRecordStatus, RecordedBy},
@@ -19,6 +26,18 @@ use entity::{
 };
 use std::collections::HashMap;
 
+/// 获取工坊的计件单位
+pub(crate) async fn get_piece_unit(db: &DbConn, boss_id: Uuid) -> String {
+    workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .one(db)
+        .await
+        .ok()
+        .flatten()
+        .map(|w| w.piece_unit)
+        .unwrap_or_else(|| "件".to_string())
+}
+
 pub async fn list(
     db: &DbConn,
     params: QueryParams,
@@ -105,6 +124,8 @@ pub async fn list(
         .map(|u| (u.id, u))
         .collect();
 
+    let originals = original_quantities(db, records.iter().map(|r| r.id).collect()).await?;
+
     // 组装响应
     let list = records
         .into_iter()
@@ -138,7 +159,14 @@ pub async fn list(
                 order_id: proc.map(|p| p.order_id),
                 order_name: ord.map(|o| o.product_name.clone()),
                 order_image,
-                piece_price: proc.map(|p| p.piece_price),
+                piece_price: r.piece_price.or(proc.map(|p| p.piece_price)),
+                reject_reason: r.reject_reason.clone(),
+                auto_approve_rule: r.auto_approve_rule.clone(),
+                images: r.image_keys(),
+                adjusts_record_id: r.adjusts_record_id,
+                adjust_reason: r.adjust_reason.clone(),
+                bundle_id: r.bundle_id,
+                original_quantity: originals.get(&r.id).copied(),
             }
         })
         .collect();
@@ -146,7 +174,73 @@ pub async fn list(
     Ok(ListData { list, total })
 }
 
-pub async fn create(db: &DbConn, dto: CreatePieceRecordDto, claims: &Claims) -> Result<Model> {
+/// 单条计件最多附带的照片数
+const MAX_IMAGES: usize = 9;
+
+/// 整理凭证照片 key 列表，空列表存为 None
+fn normalize_images(images: Vec<String>) -> Result<Option<serde_json::Value>> {
+    let keys: Vec<String> = images
+        .into_iter()
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty())
+        .collect();
+    if keys.len() > MAX_IMAGES {
+        return Err(AppError::BadRequest(format!("最多上传 {} 张照片", MAX_IMAGES)));
+    }
+    if keys.iter().any(|k| !k.starts_with("uploads/")) {
+        return Err(AppError::BadRequest("照片需先通过上传接口上传".to_string()));
+    }
+    Ok(if keys.is_empty() {
+        None
+    } else {
+        Some(serde_json::json!(keys))
+    })
+}
+
+/// 校验工序的拍照要求，未附照片且需要拍照时报错
+fn check_photo(
+    proc: &process::Model,
+    quantity: i32,
+    images: Option<&serde_json::Value>,
+) -> Result<()> {
+    if images.is_some() || !proc.requires_photo(quantity) {
+        return Ok(());
+    }
+    let message = match proc.photo_threshold {
+        Some(t) if !proc.photo_required => format!("计件数量达到 {} 时需上传照片", t),
+        _ => "该工序需上传计件照片".to_string(),
+    };
+    Err(AppError::BadRequest(message))
+}
+
+/// 创建计件记录，返回记录和超产提醒（如有）
+pub async fn create(
+    db: &DbConn,
+    dto: CreatePieceRecordDto,
+    claims: &Claims,
+) -> Result<(Model, Option<String>)> {
+    create_record(db, dto, claims, None).await
+}
+
+/// 校验工序分配给了该员工
+async fn ensure_assigned(db: &DbConn, proc: &process::Model, user_id: Uuid) -> Result<()> {
+    let staff = user::Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .ok_or(AppError::Forbidden)?;
+    if !proc.is_assigned_to(&staff) {
+        return Err(AppError::BadRequest("该工序未分配给您".to_string()));
+    }
+    Ok(())
+}
+
+/// 创建计件记录，扫码录入时关联对应的扎
+pub(crate) async fn create_record(
+    db: &DbConn,
+    dto: CreatePieceRecordDto,
+    claims: &Claims,
+    bundle_id: Option<Uuid>,
+) -> Result<(Model, Option<String>)> {
     let proc = process::Entity::find_by_id(dto.process_id)
         .one(db)
         .await?
@@ -155,7 +249,8 @@ pub async fn create(db: &DbConn, dto: CreatePieceRecordDto, claims: &Claims) ->
     // 确定实际的 user_id，防止越权
     let actual_user_id = match claims.role {
         Role::Staff => {
-            // 员工只能为自己创建记录
+            // 员工只能为自己创建记录，且工序需分配给本人
+            ensure_assigned(db, &proc, claims.sub).await?;
             claims.sub
         }
         Role::Boss => {
@@ -182,6 +277,13 @@ pub async fn create(db: &DbConn, dto: CreatePieceRecordDto, claims: &Claims) ->
         }
     };
 
+    let images = normalize_images(dto.images.clone().unwrap_or_default())?;
+    if claims.role == Role::Staff {
+        check_photo(&proc, dto.quantity, images.as_ref())?;
+    }
+
+    let warning = check_quantity_limits(db, &proc, dto.quantity as i64, None).await?;
+
     // 自动更新订单状态: pending → processing
     let ord = order::Entity::find_by_id(proc.order_id)
         .one(db)
@@ -196,27 +298,251 @@ pub async fn create(db: &DbConn, dto: CreatePieceRecordDto, claims: &Claims) ->
         order_model.update(db).await?;
     }
 
-    let amount = proc.piece_price * Decimal::from(dto.quantity);
+    // 按录入时间取生效的单价版本，再叠加阶梯和加急加价
+    let id = Uuid::new_v4();
+    let recorded_at = chrono::Utc::now();
+    let piece_price = process_service::price_at(db, &proc, recorded_at).await?;
+    let breakdown = pricing::compute(
+        db,
+        &proc,
+        &ord,
+        PricingInput {
+            user_id: actual_user_id,
+            quantity: dto.quantity,
+            piece_price,
+            recorded_at,
+            record_id: id,
+        },
+    )
+    .await?;
+
+    // 员工提交命中工坊自动审核规则时直接通过
+    let auto_rule = match claims.role {
+        Role::Staff => match_approve_rule(db, &proc, &ord, actual_user_id, dto.quantity).await?,
+        Role::Boss => None,
+    };
 
     // 根据角色设置 status 和 recorded_by
     let (status, recorded_by) = match claims.role {
         Role::Boss => (PieceRecordStatus::Approved, RecordedBy::ByBoss),
+        Role::Staff if auto_rule.is_some() => (PieceRecordStatus::Approved, RecordedBy::BySelf),
         Role::Staff => (PieceRecordStatus::Pending, RecordedBy::BySelf),
     };
 
-    let model = piece_record::ActiveModel {
-        id: Set(Uuid::new_v4()),
+    let mut model = piece_record::ActiveModel {
+        id: Set(id),
         process_id: Set(dto.process_id),
         user_id: Set(actual_user_id),
         boss_id: Set(proc.boss_id),
         quantity: Set(dto.quantity),
-        amount: Set(amount),
         status: Set(status),
         recorded_by: Set(recorded_by),
-        recorded_at: Set(chrono::Utc::now()),
+        recorded_at: Set(recorded_at),
+        images: Set(images),
+        bundle_id: Set(bundle_id),
+        auto_approve_rule: Set(auto_rule),
         ..Default::default()
     };
-    Ok(model.insert(db).await?)
+    breakdown.apply(&mut model);
+    let record = model.insert(db).await.map_err(bundle_conflict)?;
+    Ok((record, warning))
+}
+
+/// 同一扎同一工序的唯一索引冲突时提示已扫码计件
+fn bundle_conflict(e: DbErr) -> AppError {
+    match e.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            AppError::Conflict("该扎的此工序已扫码计件".to_string())
+        }
+        _ => e.into(),
+    }
+}
+
+/// 按顺序匹配工坊的自动审核规则，返回首个命中规则的名称
+async fn match_approve_rule(
+    db: &DbConn,
+    proc: &process::Model,
+    ord: &order::Model,
+    user_id: Uuid,
+    quantity: i32,
+) -> Result<Option<String>> {
+    let rules = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(proc.boss_id))
+        .one(db)
+        .await?
+        .map(|w| w.approve_rules())
+        .unwrap_or_default();
+    if rules.is_empty() {
+        return Ok(None);
+    }
+
+    // 工序已录入数量（含待审核），仅在有规则要求不超订单数量时查询
+    let recorded = if rules.iter().any(|r| r.within_remaining) {
+        sum_quantity_by_process(
+            db,
+            vec![proc.id],
+            &[
+                PieceRecordStatus::Pending,
+                PieceRecordStatus::Approved,
+                PieceRecordStatus::Settled,
+            ],
+            None,
+        )
+        .await?
+        .get(&proc.id)
+        .copied()
+        .unwrap_or(0)
+    } else {
+        0
+    };
+    let within_remaining = recorded + quantity as i64 <= ord.quantity as i64;
+
+    Ok(rules
+        .into_iter()
+        .find(|rule| {
+            (rule.user_ids.is_empty() || rule.user_ids.contains(&user_id))
+                && (rule.process_ids.is_empty() || rule.process_ids.contains(&proc.id))
+                && rule.max_quantity.is_none_or(|max| quantity <= max)
+                && (!rule.within_remaining || within_remaining)
+        })
+        .map(|rule| rule.name))
+}
+
+/// 老板批量代录计件：先校验全部行，再在一个事务内写入，返回新记录和超产提醒（如有）
+pub async fn batch_create(
+    db: &DbConn,
+    boss_id: Uuid,
+    dto: BatchCreatePieceRecordDto,
+) -> Result<(Vec<Model>, Option<String>)> {
+    if dto.items.is_empty() {
+        return Err(AppError::BadRequest("至少录入一条计件".to_string()));
+    }
+
+    let ws = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("工坊不存在".to_string()))?;
+
+    let process_ids: Vec<Uuid> = dto.items.iter().map(|i| i.process_id).collect();
+    let processes: HashMap<Uuid, process::Model> = process::Entity::find()
+        .filter(process::Column::Id.is_in(process_ids))
+        .filter(process::Column::BossId.eq(boss_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+
+    let user_ids: Vec<Uuid> = dto.items.iter().map(|i| i.user_id).collect();
+    let staff: HashMap<Uuid, user::Model> = user::Entity::find()
+        .filter(user::Column::Id.is_in(user_ids))
+        .filter(user::Column::WorkshopId.eq(ws.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u))
+        .collect();
+
+    let order_ids: Vec<Uuid> = processes.values().map(|p| p.order_id).collect();
+    let orders: HashMap<Uuid, order::Model> = order::Entity::find()
+        .filter(order::Column::Id.is_in(order_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|o| (o.id, o))
+        .collect();
+
+    // 逐行校验，汇总所有错误一次返回
+    let now = chrono::Utc::now();
+    let mut errors = Vec::new();
+    let mut per_process: HashMap<Uuid, i64> = HashMap::new();
+    for (idx, item) in dto.items.iter().enumerate() {
+        let row = idx + 1;
+        if !processes.contains_key(&item.process_id) {
+            errors.push(format!("第 {} 行：工序不存在", row));
+        }
+        if !staff.contains_key(&item.user_id) {
+            errors.push(format!("第 {} 行：该用户不属于您的工坊", row));
+        }
+        if item.quantity <= 0 {
+            errors.push(format!("第 {} 行：计件数量需大于 0", row));
+        }
+        if item.recorded_at.is_some_and(|t| t > now) {
+            errors.push(format!("第 {} 行：完成时间不能晚于当前时间", row));
+        }
+        *per_process.entry(item.process_id).or_default() += item.quantity as i64;
+    }
+    if !errors.is_empty() {
+        return Err(AppError::BadRequest(errors.join("；")));
+    }
+
+    // 按工序合计校验上游和超产限制
+    let mut warnings = Vec::new();
+    for (process_id, quantity) in &per_process {
+        if let Some(w) = check_quantity_limits(db, &processes[process_id], *quantity, None).await? {
+            warnings.push(w);
+        }
+    }
+
+    // 按 (完成时间, ID) 顺序写入，阶梯单价按先后累计
+    let mut items: Vec<(Uuid, BatchPieceRecordItem)> =
+        dto.items.into_iter().map(|i| (Uuid::new_v4(), i)).collect();
+    items.sort_by_key(|(id, i)| (i.recorded_at.unwrap_or(now), *id));
+
+    let txn = db.begin().await?;
+    let mut created = Vec::with_capacity(items.len());
+    for (id, item) in items {
+        let proc = &processes[&item.process_id];
+        let ord = orders
+            .get(&proc.order_id)
+            .ok_or_else(|| AppError::NotFound("订单不存在".to_string()))?;
+        let recorded_at = item.recorded_at.unwrap_or(now);
+        let piece_price = process_service::price_at(&txn, proc, recorded_at).await?;
+        let input = PricingInput {
+            user_id: item.user_id,
+            quantity: item.quantity,
+            piece_price,
+            recorded_at,
+            record_id: id,
+        };
+        let breakdown = pricing::compute(&txn, proc, ord, input).await?;
+
+        let mut model = piece_record::ActiveModel {
+            id: Set(id),
+            process_id: Set(item.process_id),
+            user_id: Set(item.user_id),
+            boss_id: Set(boss_id),
+            quantity: Set(item.quantity),
+            status: Set(PieceRecordStatus::Approved),
+            recorded_by: Set(RecordedBy::ByBoss),
+            recorded_at: Set(recorded_at),
+            ..Default::default()
+        };
+        breakdown.apply(&mut model);
+        created.push(model.insert(&txn).await?);
+    }
+
+    // 自动更新订单状态: pending → processing
+    let pending_orders: Vec<Uuid> = orders
+        .values()
+        .filter(|o| o.status == OrderStatus::Pending)
+        .map(|o| o.id)
+        .collect();
+    if !pending_orders.is_empty() {
+        order::Entity::update_many()
+            .col_expr(
+                order::Column::Status,
+                sea_orm::sea_query::Expr::value(OrderStatus::Processing),
+            )
+            .filter(order::Column::Id.is_in(pending_orders))
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+
+    let warning = (!warnings.is_empty()).then(|| warnings.join("；"));
+    Ok((created, warning))
 }
 
 pub async fn get_one(db: &DbConn, id: Uuid, claims: &Claims) -> Result<PieceRecordResponse> {
@@ -270,22 +596,73 @@ pub async fn get_one(db: &DbConn, id: Uuid, claims: &Claims) -> Result<PieceReco
         order_id: proc.map(|p| p.order_id),
         order_name: ord.map(|o| o.product_name.clone()),
         order_image,
-        piece_price: proc.map(|p| p.piece_price),
+        piece_price: record.piece_price.or(proc.map(|p| p.piece_price)),
+        reject_reason: record.reject_reason.clone(),
+        auto_approve_rule: record.auto_approve_rule.clone(),
+        images: record.image_keys(),
+        adjusts_record_id: record.adjusts_record_id,
+        adjust_reason: record.adjust_reason.clone(),
+        bundle_id: record.bundle_id,
+        original_quantity: original_quantities(db, vec![record.id])
+            .await?
+            .get(&record.id)
+            .copied(),
     })
 }
 
+/// 校验修改/撤回权限：老板可操作本人未结算的记录，员工只能操作自己待审核的记录
+fn check_editable(record: &Model, claims: &Claims) -> Result<()> {
+    match claims.role {
+        Role::Boss => {
+            if record.boss_id != claims.sub {
+                return Err(AppError::Forbidden);
+            }
+            if record.status == PieceRecordStatus::Settled {
+                return Err(AppError::BadRequest(
+                    "已结算的记录不能修改或删除，请通过调整更正".to_string(),
+                ));
+            }
+        }
+        Role::Staff => {
+            if record.user_id != claims.sub {
+                return Err(AppError::Forbidden);
+            }
+            if record.status != PieceRecordStatus::Pending {
+                return Err(AppError::BadRequest(
+                    "只能修改或撤回待审核的记录".to_string(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 修改计件数量，返回记录和超产提醒（如有）；数量变化记入修改历史
 pub async fn update(
     db: &DbConn,
     id: Uuid,
     dto: UpdatePieceRecordDto,
-    boss_id: Uuid,
-) -> Result<Model> {
+    claims: &Claims,
+) -> Result<(Model, Option<String>)> {
     let record = piece_record::Entity::find_by_id(id)
         .one(db)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("PieceRecord {} not found", id)))?;
-    if record.boss_id != boss_id {
-        return Err(AppError::Forbidden);
+    check_editable(&record, claims)?;
+    if record.is_adjustment() {
+        return Err(AppError::BadRequest(
+            "调整记录不能修改，可删除后重新调整".to_string(),
+        ));
+    }
+    if let Some(qty) = dto.quantity
+        && qty <= 0
+    {
+        return Err(AppError::BadRequest("计件数量需大于 0".to_string()));
+    }
+    if record.bundle_id.is_some() && dto.quantity.is_some_and(|q| q != record.quantity) {
+        return Err(AppError::BadRequest(
+            "扫码计件的数量以扎为准，不能修改".to_string(),
+        ));
     }
 
     let proc = process::Entity::find_by_id(record.process_id)
@@ -293,15 +670,78 @@ pub async fn update(
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Process {} not found", record.process_id)))?;
 
+    let images = dto.images.map(normalize_images).transpose()?;
+    if claims.role == Role::Staff {
+        let current = images.as_ref().unwrap_or(&record.images);
+        check_photo(&proc, dto.quantity.unwrap_or(record.quantity), current.as_ref())?;
+    }
+
+    let warning = match dto.quantity {
+        Some(qty) => check_quantity_limits(db, &proc, qty as i64, Some(record.id)).await?,
+        None => None,
+    };
+
+    // 沿用记录采用的单价，调价后需通过重新计价更新
+    let breakdown = match dto.quantity {
+        Some(qty) => {
+            let piece_price = match record.piece_price {
+                Some(p) => p,
+                None => process_service::price_at(db, &proc, record.recorded_at).await?,
+            };
+            let ord = order::Entity::find_by_id(proc.order_id)
+                .one(db)
+                .await?
+                .ok_or_else(|| AppError::NotFound("订单不存在".to_string()))?;
+            let input = PricingInput {
+                user_id: record.user_id,
+                quantity: qty,
+                piece_price,
+                recorded_at: record.recorded_at,
+                record_id: record.id,
+            };
+            Some(pricing::compute(db, &proc, &ord, input).await?)
+        }
+        None => None,
+    };
+    let txn = db.begin().await?;
+    if let Some(qty) = dto.quantity
+        && qty != record.quantity
+    {
+        log_action(&record, claims.sub, PieceRecordAction::Edited, qty, None)
+            .insert(&txn)
+            .await?;
+    }
     let mut model: piece_record::ActiveModel = record.into();
-    if let Some(qty) = dto.quantity {
+    if let (Some(qty), Some(b)) = (dto.quantity, breakdown) {
         model.quantity = Set(qty);
-        model.amount = Set(proc.piece_price * Decimal::from(qty));
+        b.apply(&mut model);
+    }
+    if let Some(images) = images {
+        model.images = Set(images);
     }
-    Ok(model.update(db).await?)
+    let record = model.update(&txn).await?;
+    txn.commit().await?;
+    Ok((record, warning))
+}
+
+/// 删除计件记录；员工删除自己待审核的记录即为撤回
+pub async fn delete(db: &DbConn, id: Uuid, claims: &Claims) -> Result<()> {
+    let record = piece_record::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("PieceRecord {} not found", id)))?;
+    check_editable(&record, claims)?;
+    piece_record::Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
 }
 
-pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
+/// 调整已结算的计件：生成一条已通过的差额记录计入下次工资，并记入原记录的历史
+pub async fn adjust(
+    db: &DbConn,
+    id: Uuid,
+    boss_id: Uuid,
+    dto: AdjustPieceRecordDto,
+) -> Result<Model> {
     let record = piece_record::Entity::find_by_id(id)
         .one(db)
         .await?
@@ -309,11 +749,110 @@ pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
     if record.boss_id != boss_id {
         return Err(AppError::Forbidden);
     }
-    piece_record::Entity::delete_by_id(id).exec(db).await?;
-    Ok(())
+    if record.status != PieceRecordStatus::Settled || record.is_adjustment() {
+        return Err(AppError::BadRequest(
+            "只能调整已结算的计件，未结算的记录可直接修改".to_string(),
+        ));
+    }
+    let reason = dto.reason.trim().to_string();
+    if reason.is_empty() {
+        return Err(AppError::BadRequest("请填写调整原因".to_string()));
+    }
+
+    let proc = process::Entity::find_by_id(record.process_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Process {} not found", record.process_id)))?;
+    let piece_price = record.piece_price.unwrap_or(proc.piece_price);
+    let quantity = dto.quantity.unwrap_or(0);
+    let amount = dto
+        .amount
+        .unwrap_or(piece_price * Decimal::from(quantity))
+        .round_dp(2);
+    if quantity == 0 && amount.is_zero() {
+        return Err(AppError::BadRequest("调整数量和金额不能同时为 0".to_string()));
+    }
+    if quantity != 0 && !amount.is_zero() && (quantity > 0) != amount.is_sign_positive() {
+        return Err(AppError::BadRequest("调整数量和金额的增减方向不一致".to_string()));
+    }
+
+    // 锁定原记录，避免并发调整同时通过下方的数量校验；作废工资单可能已把记录退回，锁定后重新确认状态
+    let txn = db.begin().await?;
+    let record = piece_record::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("PieceRecord {} not found", id)))?;
+    if record.status != PieceRecordStatus::Settled {
+        return Err(AppError::Conflict(
+            "记录已不是已结算状态，请刷新后重试".to_string(),
+        ));
+    }
+
+    // 原记录加上已有调整后的有效数量，调整后不能为负
+    let adjusted: Option<Option<i64>> = piece_record::Entity::find()
+        .select_only()
+        .column_as(Column::Quantity.sum(), "sum")
+        .filter(Column::AdjustsRecordId.eq(id))
+        .into_tuple()
+        .one(&txn)
+        .await?;
+    let current = record.quantity as i64 + adjusted.flatten().unwrap_or(0);
+    if current + (quantity as i64) < 0 {
+        return Err(AppError::BadRequest(format!(
+            "调整后数量不能小于 0，当前有效数量 {}",
+            current
+        )));
+    }
+
+    let mut log = log_action(
+        &record,
+        boss_id,
+        PieceRecordAction::Adjusted,
+        (current + quantity as i64) as i32,
+        Some(reason.clone()),
+    );
+    log.previous_quantity = Set(current as i32);
+    log.insert(&txn).await?;
+
+    let adjustment = piece_record::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        process_id: Set(record.process_id),
+        user_id: Set(record.user_id),
+        boss_id: Set(boss_id),
+        quantity: Set(quantity),
+        amount: Set(amount),
+        piece_price: Set(Some(piece_price)),
+        status: Set(PieceRecordStatus::Approved),
+        recorded_by: Set(RecordedBy::ByBoss),
+        recorded_at: Set(chrono::Utc::now()),
+        adjusts_record_id: Set(Some(record.id)),
+        adjust_reason: Set(Some(reason)),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+    Ok(adjustment)
+}
+
+/// 员工修改过的记录的原始提交数量
+async fn original_quantities(db: &DbConn, ids: Vec<Uuid>) -> Result<HashMap<Uuid, i32>> {
+    let logs = piece_record_log::Entity::find()
+        .filter(piece_record_log::Column::PieceRecordId.is_in(ids))
+        .filter(piece_record_log::Column::Action.eq(PieceRecordAction::Edited))
+        .order_by_asc(piece_record_log::Column::CreatedAt)
+        .all(db)
+        .await?;
+    let mut map = HashMap::new();
+    for log in logs {
+        map.entry(log.piece_record_id)
+            .or_insert(log.previous_quantity);
+    }
+    Ok(map)
 }
 
-pub async fn approve(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<Model> {
+pub async fn approve(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<(Model, Option<String>)> {
     let record = piece_record::Entity::find_by_id(id)
         .one(db)
         .await?
@@ -327,12 +866,57 @@ pub async fn approve(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<Model> {
         ));
     }
 
+    let proc = process::Entity::find_by_id(record.process_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Process {} not found", record.process_id)))?;
+    let warning = check_quantity_limits(db, &proc, record.quantity as i64, Some(record.id)).await?;
+
     let mut model: piece_record::ActiveModel = record.into();
     model.status = Set(PieceRecordStatus::Approved);
-    Ok(model.update(db).await?)
+    Ok((model.update(db).await?, warning))
 }
 
-pub async fn reject(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<Model> {
+/// 组合驳回原因：预设原因 + 补充说明
+pub fn build_reject_reason(preset: Option<String>, reason: Option<String>) -> Result<Option<String>> {
+    let preset = preset.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+    if let Some(p) = &preset
+        && !REJECT_REASON_PRESETS.contains(&p.as_str())
+    {
+        return Err(AppError::BadRequest(format!("未知的驳回原因: {}", p)));
+    }
+    let reason = reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+    Ok(match (preset, reason) {
+        (Some(p), Some(r)) => Some(format!("{}：{}", p, r)),
+        (p, r) => p.or(r),
+    })
+}
+
+pub(crate) fn log_action(
+    record: &Model,
+    operator_id: Uuid,
+    action: PieceRecordAction,
+    quantity: i32,
+    reason: Option<String>,
+) -> piece_record_log::ActiveModel {
+    piece_record_log::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        piece_record_id: Set(record.id),
+        operator_id: Set(operator_id),
+        action: Set(action),
+        previous_quantity: Set(record.quantity),
+        quantity: Set(quantity),
+        reason: Set(reason),
+        created_at: Set(chrono::Utc::now()),
+    }
+}
+
+pub async fn reject(
+    db: &DbConn,
+    id: Uuid,
+    boss_id: Uuid,
+    reason: Option<String>,
+) -> Result<Model> {
     let record = piece_record::Entity::find_by_id(id)
         .one(db)
         .await?
@@ -346,9 +930,119 @@ pub async fn reject(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<Model> {
         ));
     }
 
+    let txn = db.begin().await?;
+    log_action(
+        &record,
+        boss_id,
+        PieceRecordAction::Rejected,
+        record.quantity,
+        reason.clone(),
+    )
+    .insert(&txn)
+    .await?;
     let mut model: piece_record::ActiveModel = record.into();
     model.status = Set(PieceRecordStatus::Rejected);
-    Ok(model.update(db).await?)
+    model.reject_reason = Set(reason);
+    let record = model.update(&txn).await?;
+    txn.commit().await?;
+    Ok(record)
+}
+
+/// 员工修改被驳回的记录并重新提交，返回记录和超产提醒（如有）
+pub async fn resubmit(
+    db: &DbConn,
+    id: Uuid,
+    user_id: Uuid,
+    dto: ResubmitPieceRecordDto,
+) -> Result<(Model, Option<String>)> {
+    let record = piece_record::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("PieceRecord {} not found", id)))?;
+    if record.user_id != user_id {
+        return Err(AppError::Forbidden);
+    }
+    if record.status != PieceRecordStatus::Rejected {
+        return Err(AppError::BadRequest("只有被驳回的记录可以重新提交".to_string()));
+    }
+    let quantity = dto.quantity.unwrap_or(record.quantity);
+    if quantity <= 0 {
+        return Err(AppError::BadRequest("计件数量需大于 0".to_string()));
+    }
+    if record.bundle_id.is_some() && quantity != record.quantity {
+        return Err(AppError::BadRequest(
+            "扫码计件的数量以扎为准，不能修改".to_string(),
+        ));
+    }
+
+    let proc = process::Entity::find_by_id(record.process_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Process {} not found", record.process_id)))?;
+    // 驳回后可能已被取消分配
+    ensure_assigned(db, &proc, user_id).await?;
+    let ord = order::Entity::find_by_id(proc.order_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("订单不存在".to_string()))?;
+    let images = match dto.images {
+        Some(images) => normalize_images(images)?,
+        None => record.images.clone(),
+    };
+    check_photo(&proc, quantity, images.as_ref())?;
+    let warning = check_quantity_limits(db, &proc, quantity as i64, Some(record.id)).await?;
+
+    let piece_price = match record.piece_price {
+        Some(p) => p,
+        None => process_service::price_at(db, &proc, record.recorded_at).await?,
+    };
+    let input = PricingInput {
+        user_id: record.user_id,
+        quantity,
+        piece_price,
+        recorded_at: record.recorded_at,
+        record_id: record.id,
+    };
+    let breakdown = pricing::compute(db, &proc, &ord, input).await?;
+
+    let txn = db.begin().await?;
+    log_action(&record, user_id, PieceRecordAction::Resubmitted, quantity, None)
+        .insert(&txn)
+        .await?;
+    let mut model: piece_record::ActiveModel = record.into();
+    model.quantity = Set(quantity);
+    model.status = Set(PieceRecordStatus::Pending);
+    model.reject_reason = Set(None);
+    model.images = Set(images);
+    breakdown.apply(&mut model);
+    let record = model.update(&txn).await.map_err(bundle_conflict)?;
+    txn.commit().await?;
+    Ok((record, warning))
+}
+
+/// 计件记录的操作历史（驳回、修改、重新提交），按时间升序
+pub async fn history(
+    db: &DbConn,
+    id: Uuid,
+    claims: &Claims,
+) -> Result<Vec<piece_record_log::Model>> {
+    let record = piece_record::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("PieceRecord {} not found", id)))?;
+    let allowed = match claims.role {
+        Role::Boss => record.boss_id == claims.sub,
+        Role::Staff => record.user_id == claims.sub,
+    };
+    if !allowed {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(piece_record_log::Entity::find()
+        .filter(piece_record_log::Column::PieceRecordId.eq(id))
+        .order_by_asc(piece_record_log::Column::CreatedAt)
+        .all(db)
+        .await?)
 }
 
 /// 查询待处理的记录（用于批量操作前获取通知所需信息）
@@ -382,12 +1076,48 @@ pub async fn get_pending_records(
             order_id: r.process.as_ref().map(|p| p.order_id),
             order_name: None,
             order_image: None,
-            piece_price: r.process.as_ref().map(|p| p.piece_price),
+            piece_price: r
+                .piece_price
+                .or(r.process.as_ref().map(|p| p.piece_price)),
+            reject_reason: r.reject_reason.clone(),
+            auto_approve_rule: r.auto_approve_rule.clone(),
+            images: r.image_keys(),
+            adjusts_record_id: r.adjusts_record_id,
+            adjust_reason: r.adjust_reason.clone(),
+            bundle_id: r.bundle_id,
+            original_quantity: None,
         })
         .collect())
 }
 
-pub async fn batch_approve(db: &DbConn, ids: Vec<Uuid>, boss_id: Uuid) -> Result<u64> {
+/// 批量审批，返回审批条数和超产提醒（如有）
+pub async fn batch_approve(
+    db: &DbConn,
+    ids: Vec<Uuid>,
+    boss_id: Uuid,
+) -> Result<(u64, Option<String>)> {
+    // 待审核数量已计入工序总量，这里只需确认各工序整体仍未超限
+    let process_ids: Vec<Uuid> = piece_record::Entity::find()
+        .select_only()
+        .column(Column::ProcessId)
+        .distinct()
+        .filter(Column::Id.is_in(ids.clone()))
+        .filter(Column::BossId.eq(boss_id))
+        .filter(Column::Status.eq(PieceRecordStatus::Pending))
+        .into_tuple()
+        .all(db)
+        .await?;
+    let processes = process::Entity::find()
+        .filter(process::Column::Id.is_in(process_ids))
+        .all(db)
+        .await?;
+    let mut warnings = Vec::new();
+    for proc in &processes {
+        if let Some(w) = check_quantity_limits(db, proc, 0, None).await? {
+            warnings.push(w);
+        }
+    }
+
     let result = piece_record::Entity::update_many()
         .col_expr(
             piece_record::Column::Status,
@@ -398,19 +1128,186 @@ pub async fn batch_approve(db: &DbConn, ids: Vec<Uuid>, boss_id: Uuid) -> Result
         .filter(piece_record::Column::Status.eq(PieceRecordStatus::Pending))
         .exec(db)
         .await?;
-    Ok(result.rows_affected)
+    let warning = (!warnings.is_empty()).then(|| warnings.join("；"));
+    Ok((result.rows_affected, warning))
 }
 
-pub async fn batch_reject(db: &DbConn, ids: Vec<Uuid>, boss_id: Uuid) -> Result<u64> {
+pub async fn batch_reject(
+    db: &DbConn,
+    ids: Vec<Uuid>,
+    boss_id: Uuid,
+    reason: Option<String>,
+) -> Result<u64> {
+    let txn = db.begin().await?;
+    let records = piece_record::Entity::find()
+        .filter(piece_record::Column::Id.is_in(ids))
+        .filter(piece_record::Column::BossId.eq(boss_id))
+        .filter(piece_record::Column::Status.eq(PieceRecordStatus::Pending))
+        .all(&txn)
+        .await?;
+    if records.is_empty() {
+        return Ok(0);
+    }
+
+    let logs = records.iter().map(|r| {
+        log_action(
+            r,
+            boss_id,
+            PieceRecordAction::Rejected,
+            r.quantity,
+            reason.clone(),
+        )
+    });
+    piece_record_log::Entity::insert_many(logs).exec(&txn).await?;
+
     let result = piece_record::Entity::update_many()
         .col_expr(
             piece_record::Column::Status,
             sea_orm::sea_query::Expr::value(PieceRecordStatus::Rejected),
         )
-        .filter(piece_record::Column::Id.is_in(ids))
-        .filter(piece_record::Column::BossId.eq(boss_id))
-        .filter(piece_record::Column::Status.eq(PieceRecordStatus::Pending))
-        .exec(db)
+        .col_expr(
+            piece_record::Column::RejectReason,
+            sea_orm::sea_query::Expr::value(reason),
+        )
+        .filter(piece_record::Column::Id.is_in(records.iter().map(|r| r.id)))
+        .exec(&txn)
         .await?;
+    txn.commit().await?;
     Ok(result.rows_affected)
 }
+
+/// 按工序汇总计件数量，不含结算后调整
+async fn sum_quantity_by_process(
+    db: &DbConn,
+    process_ids: Vec<Uuid>,
+    statuses: &[PieceRecordStatus],
+    exclude_record: Option<Uuid>,
+) -> Result<HashMap<Uuid, i64>> {
+    let mut query = piece_record::Entity::find()
+        .select_only()
+        .column(Column::ProcessId)
+        .column_as(Column::Quantity.sum(), "sum")
+        .filter(Column::ProcessId.is_in(process_ids))
+        .filter(Column::Status.is_in(statuses.to_vec()))
+        .filter(Column::AdjustsRecordId.is_null())
+        .group_by(Column::ProcessId);
+    if let Some(id) = exclude_record {
+        query = query.filter(Column::Id.ne(id));
+    }
+    let sums: Vec<(Uuid, Option<i64>)> = query.into_tuple().all(db).await?;
+    Ok(sums
+        .into_iter()
+        .map(|(id, sum)| (id, sum.unwrap_or(0)))
+        .collect())
+}
+
+/// 校验计件数量限制：上游工序完成量与订单超产策略
+///
+/// 本工序按待审核 + 已审核 + 已结算计数，`quantity` 为本次新增或修改后的数量，
+/// `exclude_record` 为正在修改/审核的记录（避免重复计数）。超产策略为提醒时返回提醒文案。
+async fn check_quantity_limits(
+    db: &DbConn,
+    proc: &process::Model,
+    quantity: i64,
+    exclude_record: Option<Uuid>,
+) -> Result<Option<String>> {
+    let recorded = sum_quantity_by_process(
+        db,
+        vec![proc.id],
+        &[
+            PieceRecordStatus::Pending,
+            PieceRecordStatus::Approved,
+            PieceRecordStatus::Settled,
+        ],
+        exclude_record,
+    )
+    .await?
+    .get(&proc.id)
+    .copied()
+    .unwrap_or(0);
+
+    check_upstream_limit(db, proc, recorded, quantity).await?;
+    check_overproduction(db, proc, recorded, quantity).await
+}
+
+/// 校验工序数量不超过上游工序已完成（已审核 + 已结算）数量
+async fn check_upstream_limit(
+    db: &DbConn,
+    proc: &process::Model,
+    recorded: i64,
+    quantity: i64,
+) -> Result<()> {
+    let upstream_ids = proc.upstream_ids();
+    if upstream_ids.is_empty() {
+        return Ok(());
+    }
+
+    let upstreams = process::Entity::find()
+        .filter(process::Column::Id.is_in(upstream_ids.clone()))
+        .all(db)
+        .await?;
+    let upstream_done = sum_quantity_by_process(
+        db,
+        upstream_ids,
+        &[PieceRecordStatus::Approved, PieceRecordStatus::Settled],
+        None,
+    )
+    .await?;
+
+    for up in upstreams {
+        let limit = upstream_done.get(&up.id).copied().unwrap_or(0);
+        if recorded + quantity > limit {
+            return Err(AppError::BadRequest(format!(
+                "「{}」数量超出上游工序「{}」已完成数量 {}，当前最多还可录入 {}",
+                proc.name,
+                up.name,
+                limit,
+                (limit - recorded).max(0)
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 按工坊超产策略校验工序计件总数不超过订单数量（含容差）
+async fn check_overproduction(
+    db: &DbConn,
+    proc: &process::Model,
+    recorded: i64,
+    quantity: i64,
+) -> Result<Option<String>> {
+    let (policy, tolerance) = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(proc.boss_id))
+        .one(db)
+        .await?
+        .map(|w| (w.overproduction_policy, w.overproduction_tolerance))
+        .unwrap_or((OverproductionPolicy::Warn, 0));
+    if policy == OverproductionPolicy::Allow {
+        return Ok(None);
+    }
+
+    let ord = order::Entity::find_by_id(proc.order_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("订单不存在".to_string()))?;
+
+    let cap = ord.quantity as i64 * (100 + tolerance as i64) / 100;
+    let total = recorded + quantity;
+    if total <= cap {
+        return Ok(None);
+    }
+
+    match policy {
+        OverproductionPolicy::Block => Err(AppError::BadRequest(format!(
+            "超出订单数量：「{}」订单数量 {}，已录入 {}（含待审核），最多还可录入 {}",
+            proc.name,
+            ord.quantity,
+            recorded,
+            (cap - recorded).max(0)
+        ))),
+        _ => Ok(Some(format!(
+            "注意：「{}」累计 {} 已超出订单数量 {}",
+            proc.name, total, ord.quantity
+        ))),
+    }
+}
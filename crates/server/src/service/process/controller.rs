@@ -5,9 +5,10 @@ use serde::Deserialize;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use super::dto::{CreateProcessDto, ProcessQueryParams, UpdateProcessDto};
+use super::dto::{CreateProcessDto, ProcessQueryParams, RepriceResponse, UpdateProcessDto};
 use crate::common::{ApiResponse, ListData, QueryParams};
 use entity::process::Model;
+use entity::process_price;
 use crate::error::{AppJson, Result};
 use crate::service::auth::Claims;
 use crate::AppState;
@@ -24,6 +25,18 @@ pub struct ProcessPath {
     id: Uuid,
 }
 
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/processes/{id}/prices")]
+pub struct ProcessPricesPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/processes/{id}/reprice")]
+pub struct ProcessRepricePath {
+    id: Uuid,
+}
+
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .typed_get(list)
@@ -31,6 +44,8 @@ pub fn router() -> Router<Arc<AppState>> {
         .typed_get(get_one)
         .typed_put(update)
         .typed_delete(delete)
+        .typed_get(list_prices)
+        .typed_post(reprice)
 }
 
 async fn list(
@@ -52,7 +67,9 @@ async fn create(
     AppJson(dto): AppJson<CreateProcessDto>,
 ) -> Result<ApiResponse<Model>> {
     claims.require_boss()?;
-    Ok(ApiResponse::ok(service::create(&state.db, dto).await?))
+    Ok(ApiResponse::ok(
+        service::create(&state.db, &state.notifier, dto).await?,
+    ))
 }
 
 async fn get_one(
@@ -73,7 +90,7 @@ async fn update(
 ) -> Result<ApiResponse<Model>> {
     claims.require_boss()?;
     Ok(ApiResponse::ok(
-        service::update(&state.db, id, dto, claims.sub).await?,
+        service::update(&state.db, &state.notifier, id, dto, claims.sub).await?,
     ))
 }
 
@@ -86,3 +103,24 @@ async fn delete(
     service::delete(&state.db, id, claims.sub).await?;
     Ok(ApiResponse::ok(()))
 }
+
+async fn list_prices(
+    ProcessPricesPath { id }: ProcessPricesPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Vec<process_price::Model>>> {
+    Ok(ApiResponse::ok(
+        service::list_prices(&state.db, id, &claims).await?,
+    ))
+}
+
+async fn reprice(
+    ProcessRepricePath { id }: ProcessRepricePath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<RepriceResponse>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::reprice(&state.db, id, claims.sub).await?,
+    ))
+}
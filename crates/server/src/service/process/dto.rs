@@ -1,8 +1,11 @@
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use entity::process::{RateTier, TierPeriod};
+
 /// 创建工序请求
 #[derive(Debug, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -15,6 +18,22 @@ pub struct CreateProcessDto {
     pub description: Option<String>,
     /// 计件单价
     pub piece_price: Decimal,
+    /// 工序顺序，不传则排在订单最后
+    pub sequence: Option<i32>,
+    /// 上游工序ID列表（同一订单内）
+    pub depends_on: Option<Vec<Uuid>>,
+    /// 指定员工ID列表，与技能分组均为空时所有员工可做
+    pub assigned_user_ids: Option<Vec<Uuid>>,
+    /// 指定技能分组
+    pub assigned_skills: Option<Vec<String>>,
+    /// 阶梯单价
+    pub rate_tiers: Option<Vec<RateTier>>,
+    /// 阶梯单价累计周期，默认按天
+    pub tier_period: Option<TierPeriod>,
+    /// 员工计件是否必须上传照片
+    pub photo_required: Option<bool>,
+    /// 计件数量达到该值时必须上传照片
+    pub photo_threshold: Option<i32>,
 }
 
 /// 工序查询参数
@@ -33,6 +52,38 @@ pub struct UpdateProcessDto {
     pub name: Option<String>,
     /// 工序描述
     pub description: Option<String>,
-    /// 计件单价
+    /// 计件单价，变更时生成新的单价版本
     pub piece_price: Option<Decimal>,
+    /// 新单价的生效时间，不传则立即生效
+    pub price_effective_from: Option<DateTime<Utc>>,
+    /// 调价说明
+    pub price_note: Option<String>,
+    /// 工序顺序
+    pub sequence: Option<i32>,
+    /// 上游工序ID列表（同一订单内），传空数组表示清除依赖
+    pub depends_on: Option<Vec<Uuid>>,
+    /// 指定员工ID列表，传空数组表示不限员工
+    pub assigned_user_ids: Option<Vec<Uuid>>,
+    /// 指定技能分组，传空数组表示不限技能
+    pub assigned_skills: Option<Vec<String>>,
+    /// 阶梯单价，传空数组表示固定单价；需重新计价后才影响已有记录
+    pub rate_tiers: Option<Vec<RateTier>>,
+    /// 阶梯单价累计周期
+    pub tier_period: Option<TierPeriod>,
+    /// 员工计件是否必须上传照片
+    pub photo_required: Option<bool>,
+    /// 计件数量达到该值时必须上传照片，传 0 表示取消
+    pub photo_threshold: Option<i32>,
+}
+
+/// 重新计价结果
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RepriceResponse {
+    /// 参与重新计价的记录数（待审核 + 已通过）
+    pub total: u64,
+    /// 单价或金额发生变化的记录数
+    pub updated: u64,
+    /// 金额变化合计
+    pub amount_delta: Decimal,
 }
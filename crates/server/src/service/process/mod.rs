@@ -1,5 +1,6 @@
 mod controller;
 pub mod dto;
+pub mod price_sync;
 pub mod service;
 
 pub use controller::router;
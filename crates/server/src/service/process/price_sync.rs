@@ -0,0 +1,34 @@
+//! 单价版本到期同步：定期把已到生效时间的调价写入工序当前单价
+//!
+//! 计件计价按录入时间取版本，不依赖此任务；工序列表、员工任务和分享页展示的是工序当前单价。
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::service::apply_due_prices;
+use crate::AppState;
+
+/// 检查间隔
+const SYNC_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 启动同步任务，启动时先全量检查一次
+pub fn spawn(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut since: Option<DateTime<Utc>> = None;
+        loop {
+            let now = Utc::now();
+            match apply_due_prices(&state.db, since, now).await {
+                Ok(updated) => {
+                    if updated > 0 {
+                        tracing::info!("Applied due piece prices for {} processes", updated);
+                    }
+                    since = Some(now);
+                }
+                Err(e) => tracing::error!("Failed to apply due piece prices: {}", e),
+            }
+            tokio::time::sleep(SYNC_INTERVAL).await;
+        }
+    });
+}
@@ -1,16 +1,25 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sea_orm::sea_query::extension::postgres::PgBinOper;
+use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
-    Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbConn, EntityTrait, ExprTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
 };
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
-use super::dto::{CreateProcessDto, ProcessQueryParams, UpdateProcessDto};
+use super::dto::{CreateProcessDto, ProcessQueryParams, RepriceResponse, UpdateProcessDto};
 use crate::common::{ListData, QueryParams};
-use entity::process::{self, Column, Model};
+use entity::process::{self, Column, Model, RateTier, TierPeriod};
 use entity::user::Role;
-use entity::{order, user, workshop};
+use entity::piece_record::{self, PieceRecordStatus};
+use entity::{order, process_price, user, workshop};
 use crate::error::{AppError, Result};
 use crate::service::auth::Claims;
+use crate::service::notification::{Notification, Notifier};
+use crate::service::piece_record::pricing::{self, PricingInput};
 
 pub async fn list(
     db: &DbConn,
@@ -36,7 +45,9 @@ pub async fn list(
                 .one(db)
                 .await?
                 .ok_or(AppError::Forbidden)?;
-            query = query.filter(Column::BossId.eq(ws.owner_id));
+            query = query
+                .filter(Column::BossId.eq(ws.owner_id))
+                .filter(assigned_condition(&staff));
         }
     }
 
@@ -57,7 +68,10 @@ pub async fn list(
     query = match params.sort_by.as_deref() {
         Some("name") => query.order_by(Column::Name, order_dir),
         Some("piece_price") => query.order_by(Column::PiecePrice, order_dir),
-        _ => query.order_by(Column::Name, order_dir),
+        Some("sequence") => query.order_by(Column::Sequence, order_dir),
+        _ => query
+            .order_by_asc(Column::Sequence)
+            .order_by(Column::Name, order_dir),
     };
 
     let paginator = query.paginate(db, params.page_size);
@@ -67,23 +81,366 @@ pub async fn list(
     Ok(ListData { list, total })
 }
 
-pub async fn create(db: &DbConn, dto: CreateProcessDto) -> Result<Model> {
+/// 员工可做工序的过滤条件：未限定员工、指定了该员工或匹配其技能分组
+pub(crate) fn assigned_condition(staff: &user::Model) -> Condition {
+    let contains = |col: Column, value: serde_json::Value| {
+        Expr::col((process::Entity, col)).binary(PgBinOper::Contains, Expr::val(value))
+    };
+    let mut cond = Condition::any()
+        .add(
+            Column::AssignedUserIds
+                .is_null()
+                .and(Column::AssignedSkills.is_null()),
+        )
+        .add(contains(
+            Column::AssignedUserIds,
+            serde_json::json!([staff.id]),
+        ));
+    for skill in staff.skill_tags() {
+        cond = cond.add(contains(Column::AssignedSkills, serde_json::json!([skill])));
+    }
+    cond
+}
+
+/// 工坊内的全部员工
+async fn workshop_staff(db: &DbConn, boss_id: Uuid) -> Result<Vec<user::Model>> {
+    let Some(ws) = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .one(db)
+        .await?
+    else {
+        return Ok(vec![]);
+    };
+    Ok(user::Entity::find()
+        .filter(user::Column::WorkshopId.eq(ws.id))
+        .filter(user::Column::Role.eq(Role::Staff))
+        .all(db)
+        .await?)
+}
+
+/// 校验指定员工都属于本工坊，并去除空白和重复的技能分组
+fn normalize_assignment(
+    staff: &[user::Model],
+    user_ids: &[Uuid],
+    skills: &[String],
+) -> Result<(Vec<Uuid>, Vec<String>)> {
+    let mut ids = Vec::new();
+    for id in user_ids {
+        if !staff.iter().any(|s| s.id == *id) {
+            return Err(AppError::BadRequest(format!("员工 {} 不属于您的工坊", id)));
+        }
+        if !ids.contains(id) {
+            ids.push(*id);
+        }
+    }
+    let mut tags: Vec<String> = Vec::new();
+    for s in skills {
+        let s = s.trim().to_string();
+        if !s.is_empty() && !tags.contains(&s) {
+            tags.push(s);
+        }
+    }
+    Ok((ids, tags))
+}
+
+/// 通知分配发生变化的员工
+fn notify_assignment_changes(
+    notifier: &Notifier,
+    staff: &[user::Model],
+    before: Option<&Model>,
+    after: &Model,
+    order_name: &str,
+) {
+    // 新建的不限员工工序不逐个通知
+    if before.is_none() && after.is_open() {
+        return;
+    }
+    for s in staff {
+        let was = before.is_some_and(|p| p.is_assigned_to(s));
+        let now = after.is_assigned_to(s);
+        let notification = match (was, now) {
+            (false, true) => Notification::ProcessAssigned {
+                process_name: after.name.clone(),
+                order_name: order_name.to_string(),
+            },
+            (true, false) => Notification::ProcessUnassigned {
+                process_name: after.name.clone(),
+                order_name: order_name.to_string(),
+            },
+            _ => continue,
+        };
+        notifier.send(s.id, notification);
+    }
+}
+
+pub async fn create(db: &DbConn, notifier: &Notifier, dto: CreateProcessDto) -> Result<Model> {
     // 获取 order 的 boss_id
     let ord = order::Entity::find_by_id(dto.order_id)
         .one(db)
         .await?
         .ok_or_else(|| AppError::NotFound(format!("Order {} not found", dto.order_id)))?;
 
+    let siblings = process::Entity::find()
+        .filter(Column::OrderId.eq(dto.order_id))
+        .all(db)
+        .await?;
+
+    let id = Uuid::new_v4();
+    let depends_on = dto.depends_on.unwrap_or_default();
+    validate_depends_on(&siblings, id, &depends_on)?;
+
+    // 默认排在订单最后
+    let sequence = dto.sequence.unwrap_or_else(|| {
+        siblings
+            .iter()
+            .map(|p| p.sequence + 1)
+            .max()
+            .unwrap_or(0)
+    });
+
+    let rate_tiers = dto.rate_tiers.unwrap_or_default();
+    validate_tiers(&rate_tiers)?;
+    let photo_threshold = normalize_photo_threshold(dto.photo_threshold)?;
+
+    let staff = workshop_staff(db, ord.boss_id).await?;
+    let (assigned_user_ids, assigned_skills) = normalize_assignment(
+        &staff,
+        &dto.assigned_user_ids.unwrap_or_default(),
+        &dto.assigned_skills.unwrap_or_default(),
+    )?;
+
+    let txn = db.begin().await?;
     let model = process::ActiveModel {
-        id: Set(Uuid::new_v4()),
+        id: Set(id),
         order_id: Set(dto.order_id),
         boss_id: Set(ord.boss_id),
         name: Set(dto.name),
         description: Set(dto.description),
         piece_price: Set(dto.piece_price),
+        sequence: Set(sequence),
+        depends_on: Set(to_json(&depends_on)),
+        assigned_user_ids: Set(to_json(&assigned_user_ids)),
+        assigned_skills: Set(to_json(&assigned_skills)),
+        rate_tiers: Set(to_json(&rate_tiers)),
+        tier_period: Set(dto.tier_period.unwrap_or(TierPeriod::Daily)),
+        photo_required: Set(dto.photo_required.unwrap_or(false)),
+        photo_threshold: Set(photo_threshold),
         ..Default::default()
-    };
-    Ok(model.insert(db).await?)
+    }
+    .insert(&txn)
+    .await?;
+    // 初始单价从订单接单时间起生效
+    add_price_version(&txn, &model, dto.piece_price, ord.received_at, None).await?;
+    txn.commit().await?;
+
+    notify_assignment_changes(notifier, &staff, None, &model, &ord.product_name);
+    Ok(model)
+}
+
+/// 新增一个单价版本
+pub(crate) async fn add_price_version<C: ConnectionTrait>(
+    conn: &C,
+    process: &Model,
+    piece_price: Decimal,
+    effective_from: DateTime<Utc>,
+    note: Option<String>,
+) -> Result<process_price::Model> {
+    Ok(process_price::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        process_id: Set(process.id),
+        boss_id: Set(process.boss_id),
+        piece_price: Set(piece_price),
+        effective_from: Set(effective_from),
+        note: Set(note),
+        created_at: Set(Utc::now()),
+    }
+    .insert(conn)
+    .await?)
+}
+
+/// 没有单价版本的旧工序补一个初始版本：沿用工序原单价，从订单接单时间起生效
+pub(crate) async fn ensure_initial_version<C: ConnectionTrait>(
+    conn: &C,
+    process: &Model,
+) -> Result<Option<process_price::Model>> {
+    let exists = process_price::Entity::find()
+        .filter(process_price::Column::ProcessId.eq(process.id))
+        .count(conn)
+        .await?
+        > 0;
+    if exists {
+        return Ok(None);
+    }
+    let effective_from = order::Entity::find_by_id(process.order_id)
+        .one(conn)
+        .await?
+        .map(|o| o.received_at)
+        .unwrap_or(DateTime::UNIX_EPOCH);
+    let version =
+        add_price_version(conn, process, process.piece_price, effective_from, None).await?;
+    Ok(Some(version))
+}
+
+/// 工序的全部单价版本，按生效时间升序；旧工序的初始版本由启动时补齐
+async fn price_versions<C: ConnectionTrait>(
+    conn: &C,
+    process: &Model,
+) -> Result<Vec<process_price::Model>> {
+    Ok(process_price::Entity::find()
+        .filter(process_price::Column::ProcessId.eq(process.id))
+        .order_by_asc(process_price::Column::EffectiveFrom)
+        .order_by_asc(process_price::Column::CreatedAt)
+        .all(conn)
+        .await?)
+}
+
+/// 在给定版本中取 `at` 时刻生效的单价，即生效时间不晚于 `at` 的最后一个版本；
+/// 早于全部版本时使用工序单价
+fn resolve_price(versions: &[process_price::Model], fallback: Decimal, at: DateTime<Utc>) -> Decimal {
+    versions
+        .iter()
+        .rev()
+        .find(|v| v.effective_from <= at)
+        .map(|v| v.piece_price)
+        .unwrap_or(fallback)
+}
+
+/// 查询 `at` 时刻生效的计件单价
+pub async fn price_at<C: ConnectionTrait>(
+    conn: &C,
+    process: &Model,
+    at: DateTime<Utc>,
+) -> Result<Decimal> {
+    let versions = price_versions(conn, process).await?;
+    Ok(resolve_price(&versions, process.piece_price, at))
+}
+
+/// 把到期的单价版本同步为工序当前单价，`since` 为空时检查全部版本，否则只看 (since, now] 内生效的
+pub(crate) async fn apply_due_prices(
+    db: &DbConn,
+    since: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Result<u64> {
+    let mut query =
+        process_price::Entity::find().filter(process_price::Column::EffectiveFrom.lte(now));
+    if let Some(since) = since {
+        query = query.filter(process_price::Column::EffectiveFrom.gt(since));
+    }
+    let process_ids: HashSet<Uuid> = query
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|v| v.process_id)
+        .collect();
+    if process_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let mut versions: HashMap<Uuid, Vec<process_price::Model>> = HashMap::new();
+    for v in process_price::Entity::find()
+        .filter(process_price::Column::ProcessId.is_in(process_ids.iter().copied()))
+        .order_by_asc(process_price::Column::EffectiveFrom)
+        .order_by_asc(process_price::Column::CreatedAt)
+        .all(db)
+        .await?
+    {
+        versions.entry(v.process_id).or_default().push(v);
+    }
+    let processes = process::Entity::find()
+        .filter(Column::Id.is_in(process_ids))
+        .all(db)
+        .await?;
+
+    let mut updated = 0;
+    for proc in processes {
+        let current = versions.get(&proc.id).map_or(&[][..], Vec::as_slice);
+        let price = resolve_price(current, proc.piece_price, now);
+        if price != proc.piece_price {
+            let mut model: process::ActiveModel = proc.into();
+            model.piece_price = Set(price);
+            model.update(db).await?;
+            updated += 1;
+        }
+    }
+    Ok(updated)
+}
+
+fn to_json<T: Serialize>(items: &[T]) -> Option<serde_json::Value> {
+    if items.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_value(items).unwrap())
+    }
+}
+
+/// 校验阶梯单价：起算件数为正且不重复，单价不为负
+fn validate_tiers(tiers: &[RateTier]) -> Result<()> {
+    let mut thresholds = HashSet::new();
+    for t in tiers {
+        if t.threshold <= 0 {
+            return Err(AppError::BadRequest("阶梯起算件数需大于 0".to_string()));
+        }
+        if t.piece_price < Decimal::ZERO {
+            return Err(AppError::BadRequest("阶梯单价不能为负数".to_string()));
+        }
+        if !thresholds.insert(t.threshold) {
+            return Err(AppError::BadRequest(format!(
+                "阶梯起算件数重复: {}",
+                t.threshold
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 拍照数量门槛，0 表示不按数量要求
+fn normalize_photo_threshold(threshold: Option<i32>) -> Result<Option<i32>> {
+    match threshold {
+        Some(t) if t < 0 => Err(AppError::BadRequest("拍照数量门槛不能为负数".to_string())),
+        Some(0) | None => Ok(None),
+        Some(t) => Ok(Some(t)),
+    }
+}
+
+/// 校验上游工序：必须属于同一订单、不能依赖自身、不能形成环
+fn validate_depends_on(
+    siblings: &[process::Model],
+    self_id: Uuid,
+    depends_on: &[Uuid],
+) -> Result<()> {
+    let graph: HashMap<Uuid, Vec<Uuid>> = siblings
+        .iter()
+        .filter(|p| p.id != self_id)
+        .map(|p| (p.id, p.upstream_ids()))
+        .collect();
+
+    for up in depends_on {
+        if *up == self_id {
+            return Err(AppError::BadRequest("工序不能依赖自身".to_string()));
+        }
+        if !graph.contains_key(up) {
+            return Err(AppError::BadRequest(format!(
+                "上游工序 {} 不属于该订单",
+                up
+            )));
+        }
+    }
+
+    // 从新的上游出发沿依赖向上遍历，若回到自身则成环
+    let mut stack: Vec<Uuid> = depends_on.to_vec();
+    let mut visited = HashSet::new();
+    while let Some(id) = stack.pop() {
+        if id == self_id {
+            return Err(AppError::BadRequest("工序依赖不能形成循环".to_string()));
+        }
+        if !visited.insert(id) {
+            continue;
+        }
+        if let Some(ups) = graph.get(&id) {
+            stack.extend(ups.iter().copied());
+        }
+    }
+    Ok(())
 }
 
 pub async fn get_one(db: &DbConn, id: Uuid, claims: &Claims) -> Result<Model> {
@@ -109,7 +466,7 @@ pub async fn get_one(db: &DbConn, id: Uuid, claims: &Claims) -> Result<Model> {
                 .one(db)
                 .await?
                 .ok_or(AppError::Forbidden)?;
-            if process.boss_id != ws.owner_id {
+            if process.boss_id != ws.owner_id || !process.is_assigned_to(&staff) {
                 return Err(AppError::Forbidden);
             }
         }
@@ -117,7 +474,13 @@ pub async fn get_one(db: &DbConn, id: Uuid, claims: &Claims) -> Result<Model> {
     Ok(process)
 }
 
-pub async fn update(db: &DbConn, id: Uuid, dto: UpdateProcessDto, boss_id: Uuid) -> Result<Model> {
+pub async fn update(
+    db: &DbConn,
+    notifier: &Notifier,
+    id: Uuid,
+    dto: UpdateProcessDto,
+    boss_id: Uuid,
+) -> Result<Model> {
     let process = process::Entity::find_by_id(id)
         .one(db)
         .await?
@@ -126,6 +489,48 @@ pub async fn update(db: &DbConn, id: Uuid, dto: UpdateProcessDto, boss_id: Uuid)
         return Err(AppError::Forbidden);
     }
 
+    let order_id = process.order_id;
+    let old_price = process.piece_price;
+    let before = process.clone();
+
+    let assignment_changed = dto.assigned_user_ids.is_some() || dto.assigned_skills.is_some();
+    let staff = if assignment_changed {
+        workshop_staff(db, boss_id).await?
+    } else {
+        vec![]
+    };
+    let assignment = if assignment_changed {
+        Some(normalize_assignment(
+            &staff,
+            &dto
+                .assigned_user_ids
+                .clone()
+                .unwrap_or_else(|| before.assigned_users()),
+            &dto
+                .assigned_skills
+                .clone()
+                .unwrap_or_else(|| before.assigned_skill_tags()),
+        )?)
+    } else {
+        None
+    };
+
+    let txn = db.begin().await?;
+    // 工序单价只跟随已生效的版本，未来生效的调价由定时任务到期后同步
+    let mut current_price = None;
+    if let Some(v) = dto.piece_price
+        && (v != old_price || dto.price_effective_from.is_some())
+    {
+        ensure_initial_version(&txn, &process).await?;
+        let now = Utc::now();
+        let effective_from = dto.price_effective_from.unwrap_or(now);
+        add_price_version(&txn, &process, v, effective_from, dto.price_note.clone()).await?;
+        if effective_from <= now {
+            let versions = price_versions(&txn, &process).await?;
+            current_price = Some(resolve_price(&versions, old_price, now));
+        }
+    }
+
     let mut model: process::ActiveModel = process.into();
     if let Some(v) = dto.name {
         model.name = Set(v);
@@ -133,10 +538,122 @@ pub async fn update(db: &DbConn, id: Uuid, dto: UpdateProcessDto, boss_id: Uuid)
     if let Some(v) = dto.description {
         model.description = Set(Some(v));
     }
-    if let Some(v) = dto.piece_price {
+    if let Some(v) = current_price {
         model.piece_price = Set(v);
     }
-    Ok(model.update(db).await?)
+    if let Some(v) = dto.sequence {
+        model.sequence = Set(v);
+    }
+    if let Some(v) = dto.depends_on {
+        let siblings = process::Entity::find()
+            .filter(Column::OrderId.eq(order_id))
+            .all(db)
+            .await?;
+        validate_depends_on(&siblings, id, &v)?;
+        model.depends_on = Set(to_json(&v));
+    }
+    if let Some(v) = dto.rate_tiers {
+        validate_tiers(&v)?;
+        model.rate_tiers = Set(to_json(&v));
+    }
+    if let Some(v) = dto.tier_period {
+        model.tier_period = Set(v);
+    }
+    if let Some(v) = dto.photo_required {
+        model.photo_required = Set(v);
+    }
+    if let Some(v) = dto.photo_threshold {
+        model.photo_threshold = Set(normalize_photo_threshold(Some(v))?);
+    }
+    if let Some((user_ids, skills)) = &assignment {
+        model.assigned_user_ids = Set(to_json(user_ids));
+        model.assigned_skills = Set(to_json(skills));
+    }
+    let model = model.update(&txn).await?;
+    txn.commit().await?;
+
+    if assignment.is_some() {
+        let order_name = order::Entity::find_by_id(order_id)
+            .one(db)
+            .await?
+            .map(|o| o.product_name)
+            .unwrap_or_default();
+        notify_assignment_changes(notifier, &staff, Some(&before), &model, &order_name);
+    }
+    Ok(model)
+}
+
+/// 单价版本列表
+pub async fn list_prices(
+    db: &DbConn,
+    id: Uuid,
+    claims: &Claims,
+) -> Result<Vec<process_price::Model>> {
+    let process = get_one(db, id, claims).await?;
+    price_versions(db, &process).await
+}
+
+/// 按录入时间重新计算待审核、已通过计件的单价、阶梯和加急加价，已结算的记录不变
+pub async fn reprice(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<RepriceResponse> {
+    let process = process::Entity::find_by_id(id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Process {} not found", id)))?;
+    if process.boss_id != boss_id {
+        return Err(AppError::Forbidden);
+    }
+
+    let ord = order::Entity::find_by_id(process.order_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Order {} not found", process.order_id)))?;
+
+    let txn = db.begin().await?;
+    let versions = price_versions(&txn, &process).await?;
+    let records = piece_record::Entity::find()
+        .filter(piece_record::Column::ProcessId.eq(id))
+        .filter(
+            piece_record::Column::Status
+                .is_in([PieceRecordStatus::Pending, PieceRecordStatus::Approved]),
+        )
+        .filter(piece_record::Column::AdjustsRecordId.is_null())
+        .order_by_asc(piece_record::Column::RecordedAt)
+        .order_by_asc(piece_record::Column::Id)
+        .all(&txn)
+        .await?;
+
+    let total = records.len() as u64;
+    let mut updated = 0;
+    let mut amount_delta = Decimal::ZERO;
+    for r in records {
+        let input = PricingInput {
+            user_id: r.user_id,
+            quantity: r.quantity,
+            piece_price: resolve_price(&versions, process.piece_price, r.recorded_at),
+            recorded_at: r.recorded_at,
+            record_id: r.id,
+        };
+        let breakdown = pricing::compute(&txn, &process, &ord, input).await?;
+        if r.piece_price == Some(breakdown.piece_price)
+            && r.tier_bonus == Some(breakdown.tier_bonus)
+            && r.urgency_bonus == Some(breakdown.urgency_bonus)
+            && r.amount == breakdown.amount
+        {
+            continue;
+        }
+        amount_delta += breakdown.amount - r.amount;
+        let mut model: piece_record::ActiveModel = r.into();
+        breakdown.apply(&mut model);
+        model.update(&txn).await?;
+        updated += 1;
+    }
+    txn.commit().await?;
+
+    Ok(RepriceResponse {
+        total,
+        updated,
+        amount_delta,
+    })
 }
 
 pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
@@ -147,6 +664,27 @@ pub async fn delete(db: &DbConn, id: Uuid, boss_id: Uuid) -> Result<()> {
     if process.boss_id != boss_id {
         return Err(AppError::Forbidden);
     }
-    process::Entity::delete_by_id(id).exec(db).await?;
+
+    let txn = db.begin().await?;
+    // 从下游工序的依赖中移除该工序
+    let dependents = process::Entity::find()
+        .filter(Column::OrderId.eq(process.order_id))
+        .filter(Column::Id.ne(id))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .filter(|p| p.upstream_ids().contains(&id));
+    for dep in dependents {
+        let remaining: Vec<Uuid> = dep
+            .upstream_ids()
+            .into_iter()
+            .filter(|u| *u != id)
+            .collect();
+        let mut model: process::ActiveModel = dep.into();
+        model.depends_on = Set(to_json(&remaining));
+        model.update(&txn).await?;
+    }
+    process::Entity::delete_by_id(id).exec(&txn).await?;
+    txn.commit().await?;
     Ok(())
 }
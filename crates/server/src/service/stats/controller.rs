@@ -7,8 +7,9 @@ use uuid::Uuid;
 
 use super::dto::{
     CustomerContributionList, CustomerSummaryList, DailyOrderStatsList, DailyStatsList,
-    GroupStatsList, MonthlyOrderStatsList, OrderOverview, OrderProgressList, OrderStats,
-    OrderStatsParams, WorkerProductionList, WorkerStatsParams,
+    GroupStatsList, MonthlyOrderStatsList, OrderOverview, OrderProfit, OrderProgressList,
+    OrderStats, OrderStatsParams, ProfitParams, ProfitSummary, WorkerProductionList,
+    WorkerStatsParams,
 };
 use crate::common::ApiResponse;
 use entity::user::Role;
@@ -64,6 +65,16 @@ pub struct OrderProgressPath;
 #[typed_path("/stats/orders/daily")]
 pub struct DailyOrderStatsPath;
 
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/orders/{id}/profit")]
+pub struct OrderProfitPath {
+    id: Uuid,
+}
+
+#[derive(TypedPath)]
+#[typed_path("/stats/profit")]
+pub struct ProfitSummaryPath;
+
 pub fn router() -> Router<Arc<AppState>> {
     Router::new()
         .typed_get(order_stats)
@@ -77,6 +88,8 @@ pub fn router() -> Router<Arc<AppState>> {
         .typed_get(customer_contribution)
         .typed_get(order_progress)
         .typed_get(daily_order_stats)
+        .typed_get(order_profit)
+        .typed_get(profit_summary)
 }
 
 async fn order_stats(
@@ -223,3 +236,29 @@ async fn daily_order_stats(
         service::daily_order_stats(&state.db, claims.sub, params).await?,
     ))
 }
+
+// ============ Profitability ============
+
+async fn order_profit(
+    OrderProfitPath { id }: OrderProfitPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<ProfitParams>,
+) -> Result<ApiResponse<OrderProfit>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::order_profit(&state.db, id, claims.sub, params.include_pending).await?,
+    ))
+}
+
+async fn profit_summary(
+    _: ProfitSummaryPath,
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Query(params): Query<ProfitParams>,
+) -> Result<ApiResponse<ProfitSummary>> {
+    claims.require_boss()?;
+    Ok(ApiResponse::ok(
+        service::profit_summary(&state.db, claims.sub, params).await?,
+    ))
+}
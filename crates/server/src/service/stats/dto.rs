@@ -13,6 +13,16 @@ pub struct ProcessProgress {
     pub name: String,
     /// 已完成数量
     pub completed_quantity: i64,
+    /// 待审核数量
+    pub pending_quantity: i64,
+    /// 工序顺序
+    pub sequence: i32,
+    /// 上游工序ID列表
+    pub depends_on: Vec<Uuid>,
+    /// 上游已完成数量（多个上游取最小值），无上游时为空
+    pub upstream_quantity: Option<i64>,
+    /// 在制品数量：上游已完成但本工序尚未完成的数量，无上游时为空
+    pub wip_quantity: Option<i64>,
 }
 
 /// 订单统计详情
@@ -67,8 +77,12 @@ pub struct WorkerProduction {
     pub user_name: String,
     /// 总数量（件）
     pub total_quantity: i64,
-    /// 总金额（元）
+    /// 总金额（元），含计件和计时工资
     pub total_amount: Decimal,
+    /// 考勤总工时（小时）
+    pub total_hours: Decimal,
+    /// 计时工资（元）
+    pub time_amount: Decimal,
 }
 
 /// 员工产量统计列表
@@ -260,3 +274,88 @@ pub struct DailyOrderStatsList {
     /// 每日订单统计列表
     pub list: Vec<DailyOrderStat>,
 }
+
+// ============ Profitability ============
+
+/// 利润统计查询参数
+#[derive(Debug, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfitParams {
+    /// 开始日期（按接单时间），格式 YYYY-MM-DD
+    pub start_date: Option<String>,
+    /// 结束日期（按接单时间），格式 YYYY-MM-DD
+    pub end_date: Option<String>,
+    /// 是否把待审核计件计入人工成本，默认 false
+    #[serde(default)]
+    pub include_pending: bool,
+}
+
+/// 单个订单利润
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderProfit {
+    /// 订单ID
+    pub order_id: Uuid,
+    /// 产品名称
+    pub product_name: String,
+    /// 客户ID
+    pub customer_id: Uuid,
+    /// 客户名称
+    pub customer_name: String,
+    /// 订单状态
+    pub status: String,
+    /// 订单数量
+    pub quantity: i32,
+    /// 单价
+    pub unit_price: Decimal,
+    /// 收入（单价 × 数量）
+    pub revenue: Decimal,
+    /// 已审核（含已结算）计件金额
+    pub approved_cost: Decimal,
+    /// 待审核计件金额
+    pub pending_cost: Decimal,
+    /// 人工成本（已审核，按参数决定是否包含待审核）
+    pub labour_cost: Decimal,
+    /// 毛利（收入 - 人工成本）
+    pub profit: Decimal,
+    /// 毛利率（%），收入为 0 时为空
+    pub margin: Option<Decimal>,
+}
+
+/// 客户利润汇总
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomerProfit {
+    /// 客户ID
+    pub customer_id: Uuid,
+    /// 客户名称
+    pub customer_name: String,
+    /// 订单数量
+    pub order_count: i64,
+    /// 收入
+    pub revenue: Decimal,
+    /// 人工成本
+    pub labour_cost: Decimal,
+    /// 毛利
+    pub profit: Decimal,
+    /// 毛利率（%），收入为 0 时为空
+    pub margin: Option<Decimal>,
+}
+
+/// 利润汇总（按日期范围）
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfitSummary {
+    /// 总收入
+    pub total_revenue: Decimal,
+    /// 总人工成本
+    pub total_labour_cost: Decimal,
+    /// 总毛利
+    pub total_profit: Decimal,
+    /// 总毛利率（%），收入为 0 时为空
+    pub margin: Option<Decimal>,
+    /// 各订单利润，按毛利升序（亏损订单在前）
+    pub orders: Vec<OrderProfit>,
+    /// 各客户利润，按毛利降序
+    pub customers: Vec<CustomerProfit>,
+}
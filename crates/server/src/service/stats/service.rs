@@ -1,14 +1,16 @@
 use rust_decimal::Decimal;
 use sea_orm::{
-    ColumnTrait, DbConn, EntityTrait, LoaderTrait, ModelTrait, QueryFilter, QueryOrder, QuerySelect,
+    ColumnTrait, DbConn, EntityTrait, ExprTrait, LoaderTrait, ModelTrait, QueryFilter, QueryOrder,
+    QuerySelect,
 };
 use uuid::Uuid;
 
 use super::dto::{
-    CustomerContribution, CustomerContributionList, CustomerSummary, CustomerSummaryList,
-    DailyOrderStat, DailyOrderStatsList, DailyStat, DailyStatsList, GroupStat, GroupStatsList,
-    MonthlyOrderStat, MonthlyOrderStatsList, OrderOverview, OrderProgressItem, OrderProgressList,
-    OrderStats, OrderStatsParams, ProcessProgress, WorkerProduction, WorkerProductionList,
+    CustomerContribution, CustomerContributionList, CustomerProfit, CustomerSummary,
+    CustomerSummaryList, DailyOrderStat, DailyOrderStatsList, DailyStat, DailyStatsList,
+    GroupStat, GroupStatsList, MonthlyOrderStat, MonthlyOrderStatsList, OrderOverview,
+    OrderProfit, OrderProgressItem, OrderProgressList, OrderStats, OrderStatsParams,
+    ProcessProgress, ProfitParams, ProfitSummary, WorkerProduction, WorkerProductionList,
     WorkerStatsParams,
 };
 use crate::common::apply_date_filter;
@@ -16,7 +18,7 @@ use crate::error::{AppError, Result};
 use crate::traits::OwnedByBoss;
 use entity::order::OrderStatus;
 use entity::piece_record::PieceRecordStatus;
-use entity::{customer, order, piece_record, process, user};
+use entity::{attendance, customer, order, piece_record, process, user};
 
 pub async fn order_stats(db: &DbConn, order_id: Uuid, boss_id: Uuid) -> Result<OrderStats> {
     let ord = order::Entity::find_by_id(order_id)
@@ -25,28 +27,43 @@ pub async fn order_stats(db: &DbConn, order_id: Uuid, boss_id: Uuid) -> Result<O
         .ok_or_else(|| AppError::NotFound(format!("Order {} not found", order_id)))?;
     ord.verify_owner(boss_id)?;
 
-    let processes = ord.find_related(process::Entity).all(db).await?;
+    let processes = ord
+        .find_related(process::Entity)
+        .order_by_asc(process::Column::Sequence)
+        .order_by_asc(process::Column::Name)
+        .all(db)
+        .await?;
 
-    // Batch query: get completed quantities for all processes at once (avoid N+1)
+    // Batch query: get completed and pending quantities for all processes at once (avoid N+1)
     use std::collections::HashMap;
     let process_ids: Vec<Uuid> = processes.iter().map(|p| p.id).collect();
-    let completed_sums: Vec<(Uuid, Option<i64>)> = piece_record::Entity::find()
+    let sums: Vec<(Uuid, PieceRecordStatus, Option<i64>)> = piece_record::Entity::find()
         .select_only()
         .column(piece_record::Column::ProcessId)
+        .column(piece_record::Column::Status)
         .column_as(piece_record::Column::Quantity.sum(), "sum")
         .filter(piece_record::Column::ProcessId.is_in(process_ids))
-        .filter(
-            piece_record::Column::Status
-                .is_in([PieceRecordStatus::Approved, PieceRecordStatus::Settled]),
-        )
+        .filter(piece_record::Column::Status.is_in([
+            PieceRecordStatus::Pending,
+            PieceRecordStatus::Approved,
+            PieceRecordStatus::Settled,
+        ]))
+        .filter(piece_record::Column::AdjustsRecordId.is_null())
         .group_by(piece_record::Column::ProcessId)
+        .group_by(piece_record::Column::Status)
         .into_tuple()
         .all(db)
         .await?;
-    let completed_by_process: HashMap<Uuid, i64> = completed_sums
-        .into_iter()
-        .map(|(id, sum)| (id, sum.unwrap_or(0)))
-        .collect();
+    let mut completed_by_process: HashMap<Uuid, i64> = HashMap::new();
+    let mut pending_by_process: HashMap<Uuid, i64> = HashMap::new();
+    for (id, status, sum) in sums {
+        let target = if status == PieceRecordStatus::Pending {
+            &mut pending_by_process
+        } else {
+            &mut completed_by_process
+        };
+        *target.entry(id).or_insert(0) += sum.unwrap_or(0);
+    }
 
     let mut process_stats = Vec::new();
     let mut total_completed: i64 = 0;
@@ -54,10 +71,24 @@ pub async fn order_stats(db: &DbConn, order_id: Uuid, boss_id: Uuid) -> Result<O
     for proc in processes {
         let completed = completed_by_process.get(&proc.id).copied().unwrap_or(0);
         total_completed += completed;
+
+        // 上游完成量取最小值，差额即为两道工序之间的在制品
+        let depends_on = proc.upstream_ids();
+        let upstream_quantity = depends_on
+            .iter()
+            .map(|id| completed_by_process.get(id).copied().unwrap_or(0))
+            .min();
+        let wip_quantity = upstream_quantity.map(|up| std::cmp::max(up - completed, 0));
+
         process_stats.push(ProcessProgress {
             process_id: proc.id,
             name: proc.name,
             completed_quantity: completed,
+            pending_quantity: pending_by_process.get(&proc.id).copied().unwrap_or(0),
+            sequence: proc.sequence,
+            depends_on,
+            upstream_quantity,
+            wip_quantity,
         });
     }
 
@@ -87,7 +118,7 @@ pub async fn customer_summary(db: &DbConn, boss_id: Uuid) -> Result<CustomerSumm
 
     let list = customers
         .into_iter()
-        .zip(customer_orders.into_iter())
+        .zip(customer_orders)
         .map(|(cust, orders)| {
             let total = orders.len() as i64;
             let pending = orders
@@ -140,13 +171,41 @@ pub async fn worker_production(
 
     let records = query.all(db).await?;
 
+    // 计时工资按出勤日期统计，不含未下班的考勤
+    let att_query = attendance::Entity::find()
+        .filter(attendance::Column::BossId.eq(boss_id))
+        .filter(
+            attendance::Column::ClockIn
+                .is_null()
+                .or(attendance::Column::ClockOut.is_not_null()),
+        );
+    let att_query = apply_date_filter(
+        att_query,
+        attendance::Column::WorkDate,
+        params.start_date.as_deref(),
+        params.end_date.as_deref(),
+    );
+    let attendances = att_query.all(db).await?;
+
     // Group by user_id
     use std::collections::HashMap;
-    let mut user_stats: HashMap<Uuid, (i64, Decimal)> = HashMap::new();
+    #[derive(Default)]
+    struct Totals {
+        quantity: i64,
+        piece_amount: Decimal,
+        hours: Decimal,
+        time_amount: Decimal,
+    }
+    let mut user_stats: HashMap<Uuid, Totals> = HashMap::new();
     for rec in &records {
-        let entry = user_stats.entry(rec.user_id).or_insert((0, Decimal::ZERO));
-        entry.0 += rec.quantity as i64;
-        entry.1 += rec.amount;
+        let entry = user_stats.entry(rec.user_id).or_default();
+        entry.quantity += rec.produced_quantity();
+        entry.piece_amount += rec.amount;
+    }
+    for att in &attendances {
+        let entry = user_stats.entry(att.user_id).or_default();
+        entry.hours += att.hours;
+        entry.time_amount += att.amount;
     }
 
     // Batch load all users at once (avoid N+1 query)
@@ -160,7 +219,7 @@ pub async fn worker_production(
         .collect();
 
     let mut list = Vec::new();
-    for (user_id, (qty, amt)) in user_stats {
+    for (user_id, t) in user_stats {
         let name = users
             .get(&user_id)
             .map(|u| u.display_name.clone().unwrap_or_else(|| u.username.clone()))
@@ -168,8 +227,10 @@ pub async fn worker_production(
         list.push(WorkerProduction {
             user_id,
             user_name: name,
-            total_quantity: qty,
-            total_amount: amt,
+            total_quantity: t.quantity,
+            total_amount: t.piece_amount + t.time_amount,
+            total_hours: t.hours,
+            time_amount: t.time_amount,
         });
     }
 
@@ -214,7 +275,7 @@ pub async fn daily_stats(
     for rec in &records {
         let date_str = rec.recorded_at.format("%Y-%m-%d").to_string();
         let entry = daily_map.entry(date_str).or_insert((0, Decimal::ZERO));
-        entry.0 += rec.quantity as i64;
+        entry.0 += rec.produced_quantity();
         entry.1 += rec.amount;
     }
 
@@ -275,7 +336,7 @@ pub async fn stats_by_order(
     for rec in &records {
         if let Some(proc) = processes.get(&rec.process_id) {
             let entry = order_map.entry(proc.order_id).or_insert((0, Decimal::ZERO));
-            entry.0 += rec.quantity as i64;
+            entry.0 += rec.produced_quantity();
             entry.1 += rec.amount;
         }
     }
@@ -305,7 +366,7 @@ pub async fn stats_by_order(
     }
 
     // Sort by quantity descending
-    list.sort_by(|a, b| b.total_quantity.cmp(&a.total_quantity));
+    list.sort_by_key(|s| std::cmp::Reverse(s.total_quantity));
 
     Ok(GroupStatsList { list })
 }
@@ -346,7 +407,7 @@ pub async fn stats_by_process(
         let entry = process_map
             .entry(rec.process_id)
             .or_insert((0, Decimal::ZERO));
-        entry.0 += rec.quantity as i64;
+        entry.0 += rec.produced_quantity();
         entry.1 += rec.amount;
     }
 
@@ -375,7 +436,7 @@ pub async fn stats_by_process(
     }
 
     // Sort by quantity descending
-    list.sort_by(|a, b| b.total_quantity.cmp(&a.total_quantity));
+    list.sort_by_key(|s| std::cmp::Reverse(s.total_quantity));
 
     Ok(GroupStatsList { list })
 }
@@ -525,7 +586,7 @@ pub async fn customer_contribution(
     }
 
     // Sort by total_amount descending
-    list.sort_by(|a, b| b.total_amount.cmp(&a.total_amount));
+    list.sort_by_key(|s| std::cmp::Reverse(s.total_amount));
 
     Ok(CustomerContributionList { list })
 }
@@ -567,6 +628,7 @@ pub async fn order_progress(
             piece_record::Column::Status
                 .is_in([PieceRecordStatus::Approved, PieceRecordStatus::Settled]),
         )
+        .filter(piece_record::Column::AdjustsRecordId.is_null())
         .group_by(piece_record::Column::ProcessId)
         .into_tuple()
         .all(db)
@@ -579,8 +641,8 @@ pub async fn order_progress(
     let mut list = Vec::new();
     for ((ord, cust), procs) in orders
         .into_iter()
-        .zip(customers.into_iter())
-        .zip(order_processes.into_iter())
+        .zip(customers)
+        .zip(order_processes)
     {
         let customer_name = cust.map(|c| c.name).unwrap_or_default();
 
@@ -650,3 +712,179 @@ pub async fn daily_order_stats(
 
     Ok(DailyOrderStatsList { list })
 }
+
+// ============ Profitability ============
+
+/// 毛利率（%），收入为 0 时返回 None
+fn margin_of(profit: Decimal, revenue: Decimal) -> Option<Decimal> {
+    if revenue.is_zero() {
+        None
+    } else {
+        Some((profit / revenue * Decimal::ONE_HUNDRED).round_dp(2))
+    }
+}
+
+/// 计算一批订单的收入与人工成本
+async fn compute_order_profits(
+    db: &DbConn,
+    orders: Vec<order::Model>,
+    include_pending: bool,
+) -> Result<Vec<OrderProfit>> {
+    use std::collections::HashMap;
+
+    let customers: Vec<Option<customer::Model>> = orders.load_one(customer::Entity, db).await?;
+    let order_processes: Vec<Vec<process::Model>> = orders.load_many(process::Entity, db).await?;
+
+    // 按工序、状态聚合计件金额
+    let all_process_ids: Vec<Uuid> = order_processes.iter().flatten().map(|p| p.id).collect();
+    let sums: Vec<(Uuid, PieceRecordStatus, Option<Decimal>)> = piece_record::Entity::find()
+        .select_only()
+        .column(piece_record::Column::ProcessId)
+        .column(piece_record::Column::Status)
+        .column_as(piece_record::Column::Amount.sum(), "sum")
+        .filter(piece_record::Column::ProcessId.is_in(all_process_ids))
+        .filter(piece_record::Column::Status.is_in([
+            PieceRecordStatus::Pending,
+            PieceRecordStatus::Approved,
+            PieceRecordStatus::Settled,
+        ]))
+        .group_by(piece_record::Column::ProcessId)
+        .group_by(piece_record::Column::Status)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    // process_id -> (approved + settled, pending)
+    let mut cost_by_process: HashMap<Uuid, (Decimal, Decimal)> = HashMap::new();
+    for (process_id, status, sum) in sums {
+        let entry = cost_by_process
+            .entry(process_id)
+            .or_insert((Decimal::ZERO, Decimal::ZERO));
+        let sum = sum.unwrap_or_default();
+        if status == PieceRecordStatus::Pending {
+            entry.1 += sum;
+        } else {
+            entry.0 += sum;
+        }
+    }
+
+    Ok(orders
+        .into_iter()
+        .zip(customers)
+        .zip(order_processes)
+        .map(|((ord, cust), procs)| {
+            let (approved_cost, pending_cost) = procs
+                .iter()
+                .filter_map(|p| cost_by_process.get(&p.id))
+                .fold((Decimal::ZERO, Decimal::ZERO), |acc, c| {
+                    (acc.0 + c.0, acc.1 + c.1)
+                });
+            let labour_cost = if include_pending {
+                approved_cost + pending_cost
+            } else {
+                approved_cost
+            };
+            let revenue = Decimal::from(ord.quantity) * ord.unit_price;
+            let profit = revenue - labour_cost;
+
+            OrderProfit {
+                order_id: ord.id,
+                product_name: ord.product_name,
+                customer_id: ord.customer_id,
+                customer_name: cust.map(|c| c.name).unwrap_or_default(),
+                status: ord.status.to_string(),
+                quantity: ord.quantity,
+                unit_price: ord.unit_price,
+                revenue,
+                approved_cost,
+                pending_cost,
+                labour_cost,
+                profit,
+                margin: margin_of(profit, revenue),
+            }
+        })
+        .collect())
+}
+
+/// 单个订单利润
+pub async fn order_profit(
+    db: &DbConn,
+    order_id: Uuid,
+    boss_id: Uuid,
+    include_pending: bool,
+) -> Result<OrderProfit> {
+    let ord = order::Entity::find_by_id(order_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Order {} not found", order_id)))?;
+    ord.verify_owner(boss_id)?;
+
+    compute_order_profits(db, vec![ord], include_pending)
+        .await?
+        .pop()
+        .ok_or_else(|| AppError::Internal("Failed to compute order profit".to_string()))
+}
+
+/// 利润汇总：按订单、按客户（日期范围按接单时间，不含已取消订单）
+pub async fn profit_summary(
+    db: &DbConn,
+    boss_id: Uuid,
+    params: ProfitParams,
+) -> Result<ProfitSummary> {
+    use std::collections::HashMap;
+
+    let query = order::Entity::find()
+        .filter(order::Column::BossId.eq(boss_id))
+        .filter(order::Column::Status.ne(OrderStatus::Cancelled));
+    let query = apply_date_filter(
+        query,
+        order::Column::ReceivedAt,
+        params.start_date.as_deref(),
+        params.end_date.as_deref(),
+    );
+    let orders = query.all(db).await?;
+
+    let mut orders = compute_order_profits(db, orders, params.include_pending).await?;
+
+    let mut customer_map: HashMap<Uuid, CustomerProfit> = HashMap::new();
+    for o in &orders {
+        let entry = customer_map
+            .entry(o.customer_id)
+            .or_insert_with(|| CustomerProfit {
+                customer_id: o.customer_id,
+                customer_name: o.customer_name.clone(),
+                order_count: 0,
+                revenue: Decimal::ZERO,
+                labour_cost: Decimal::ZERO,
+                profit: Decimal::ZERO,
+                margin: None,
+            });
+        entry.order_count += 1;
+        entry.revenue += o.revenue;
+        entry.labour_cost += o.labour_cost;
+        entry.profit += o.profit;
+    }
+    let mut customers: Vec<CustomerProfit> = customer_map
+        .into_values()
+        .map(|mut c| {
+            c.margin = margin_of(c.profit, c.revenue);
+            c
+        })
+        .collect();
+    customers.sort_by_key(|c| std::cmp::Reverse(c.profit));
+
+    orders.sort_by_key(|o| o.profit);
+
+    let total_revenue: Decimal = orders.iter().map(|o| o.revenue).sum();
+    let total_labour_cost: Decimal = orders.iter().map(|o| o.labour_cost).sum();
+    let total_profit = total_revenue - total_labour_cost;
+
+    Ok(ProfitSummary {
+        total_revenue,
+        total_labour_cost,
+        total_profit,
+        margin: margin_of(total_profit, total_revenue),
+        orders,
+        customers,
+    })
+}
@@ -1,6 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Extension, Router,
 };
 use std::sync::Arc;
@@ -12,8 +12,10 @@ use crate::AppState;
 
 use super::dto::{
     BindWorkshopRequest, CreateWorkshopRequest, InviteCodeResponse, StaffResponse,
+    UpdateAutoApproveRulesRequest, UpdateStaffSkillsRequest, UpdateStaffWageRatesRequest,
     UpdateWorkshopRequest, WorkshopResponse,
 };
+use entity::workshop::AutoApproveRule;
 use super::service;
 
 pub fn router() -> Router<Arc<AppState>> {
@@ -22,8 +24,14 @@ pub fn router() -> Router<Arc<AppState>> {
             "/workshop",
             get(get_workshop).post(create_workshop).put(update_workshop),
         )
+        .route(
+            "/workshop/auto-approve-rules",
+            get(get_auto_approve_rules).put(update_auto_approve_rules),
+        )
         .route("/staff", get(get_staff_list))
         .route("/staff/{id}", delete(remove_staff))
+        .route("/staff/{id}/skills", put(update_staff_skills))
+        .route("/staff/{id}/wage-rates", put(update_staff_wage_rates))
         .route("/invite-code", post(generate_invite_code))
         .route("/bind-workshop", post(bind_workshop))
 }
@@ -57,6 +65,25 @@ async fn update_workshop(
     Ok(ApiResponse::ok(ws))
 }
 
+async fn get_auto_approve_rules(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+) -> Result<ApiResponse<Vec<AutoApproveRule>>> {
+    claims.require_boss()?;
+    let rules = service::get_auto_approve_rules(&state.db, claims.sub).await?;
+    Ok(ApiResponse::ok(rules))
+}
+
+async fn update_auto_approve_rules(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    AppJson(req): AppJson<UpdateAutoApproveRulesRequest>,
+) -> Result<ApiResponse<Vec<AutoApproveRule>>> {
+    claims.require_boss()?;
+    let rules = service::update_auto_approve_rules(&state.db, claims.sub, req).await?;
+    Ok(ApiResponse::ok(rules))
+}
+
 async fn generate_invite_code(
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<Claims>,
@@ -94,3 +121,25 @@ async fn remove_staff(
     service::remove_staff(&state.db, claims.sub, staff_id).await?;
     Ok(ApiResponse::ok(()))
 }
+
+async fn update_staff_skills(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(staff_id): Path<uuid::Uuid>,
+    AppJson(req): AppJson<UpdateStaffSkillsRequest>,
+) -> Result<ApiResponse<StaffResponse>> {
+    claims.require_boss()?;
+    let staff = service::update_staff_skills(&state.db, &state.notifier, claims.sub, staff_id, req).await?;
+    Ok(ApiResponse::ok(staff))
+}
+
+async fn update_staff_wage_rates(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<Claims>,
+    Path(staff_id): Path<uuid::Uuid>,
+    AppJson(req): AppJson<UpdateStaffWageRatesRequest>,
+) -> Result<ApiResponse<StaffResponse>> {
+    claims.require_boss()?;
+    let staff = service::update_staff_wage_rates(&state.db, claims.sub, staff_id, req).await?;
+    Ok(ApiResponse::ok(staff))
+}
@@ -1,3 +1,6 @@
+use entity::user::PayoutAccount;
+use rust_decimal::Decimal;
+use entity::workshop::{AutoApproveRule, OverproductionPolicy, PayrollDivergencePolicy};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -20,6 +23,12 @@ pub struct WorkshopResponse {
     pub piece_unit: String,
     /// 业务标签
     pub business_label: String,
+    /// 超产策略: block/warn/allow
+    pub overproduction_policy: OverproductionPolicy,
+    /// 超产容差（%）
+    pub overproduction_tolerance: i32,
+    /// 工资单金额与明细不一致时的处理: block/warn
+    pub payroll_divergence_policy: PayrollDivergencePolicy,
 }
 
 /// 创建工坊请求
@@ -38,6 +47,12 @@ pub struct CreateWorkshopRequest {
     pub piece_unit: Option<String>,
     /// 业务标签
     pub business_label: Option<String>,
+    /// 超产策略: block/warn/allow
+    pub overproduction_policy: Option<OverproductionPolicy>,
+    /// 超产容差（%），0-100
+    pub overproduction_tolerance: Option<i32>,
+    /// 工资单金额与明细不一致时的处理: block/warn
+    pub payroll_divergence_policy: Option<PayrollDivergencePolicy>,
 }
 
 /// 更新工坊请求
@@ -56,6 +71,12 @@ pub struct UpdateWorkshopRequest {
     pub piece_unit: Option<String>,
     /// 业务标签
     pub business_label: Option<String>,
+    /// 超产策略: block/warn/allow
+    pub overproduction_policy: Option<OverproductionPolicy>,
+    /// 超产容差（%），0-100
+    pub overproduction_tolerance: Option<i32>,
+    /// 工资单金额与明细不一致时的处理: block/warn
+    pub payroll_divergence_policy: Option<PayrollDivergencePolicy>,
 }
 
 /// 邀请码响应
@@ -90,4 +111,38 @@ pub struct StaffResponse {
     pub phone: Option<String>,
     /// 头像URL
     pub avatar: Option<String>,
+    /// 技能分组
+    pub skills: Vec<String>,
+    /// 工资收款账户
+    pub payout_account: Option<PayoutAccount>,
+    /// 时薪（元/小时）
+    pub hourly_rate: Option<Decimal>,
+    /// 日薪（元/天）
+    pub daily_rate: Option<Decimal>,
+}
+
+/// 设置员工技能分组请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateStaffSkillsRequest {
+    /// 技能分组，传空数组表示清除
+    pub skills: Vec<String>,
+}
+
+/// 设置员工计时工资标准请求，不传表示清除
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateStaffWageRatesRequest {
+    /// 时薪（元/小时），设置后按工时计算
+    pub hourly_rate: Option<Decimal>,
+    /// 日薪（元/天），未设置时薪时按出勤天数计算
+    pub daily_rate: Option<Decimal>,
+}
+
+/// 设置计件自动审核规则请求
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateAutoApproveRulesRequest {
+    /// 规则列表，按顺序匹配，传空数组表示全部人工审核
+    pub rules: Vec<AutoApproveRule>,
 }
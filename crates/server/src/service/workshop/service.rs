@@ -1,16 +1,22 @@
+use rust_decimal::Decimal;
 use sea_orm::{
-    ColumnTrait, DbConn, EntityLoaderTrait, EntityTrait, ExprTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, Set,
+    ActiveModelTrait, ColumnTrait, DbConn, EntityLoaderTrait, EntityTrait, ExprTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
 };
+use std::collections::HashSet;
 use uuid::Uuid;
 
 use crate::common::{ListData, QueryParams};
-use entity::{user, workshop};
+use entity::workshop::AutoApproveRule;
+use entity::order::{self, OrderStatus};
+use entity::{process, user, workshop};
 use crate::error::{AppError, Result};
+use crate::service::notification::{Notification, Notifier};
 use crate::InviteCodes;
 
 use super::dto::{
     BindWorkshopRequest, CreateWorkshopRequest, InviteCodeResponse, StaffResponse,
+    UpdateAutoApproveRulesRequest, UpdateStaffSkillsRequest, UpdateStaffWageRatesRequest,
     UpdateWorkshopRequest, WorkshopResponse,
 };
 
@@ -32,9 +38,21 @@ pub fn to_response(ws: &workshop::ModelEx) -> WorkshopResponse {
         image: ws.image.clone(),
         piece_unit: ws.piece_unit.clone(),
         business_label: ws.business_label.clone(),
+        overproduction_policy: ws.overproduction_policy,
+        overproduction_tolerance: ws.overproduction_tolerance,
+        payroll_divergence_policy: ws.payroll_divergence_policy,
     }
 }
 
+fn validate_tolerance(tolerance: Option<i32>) -> Result<()> {
+    if let Some(t) = tolerance
+        && !(0..=100).contains(&t)
+    {
+        return Err(AppError::BadRequest("超产容差需在 0-100 之间".to_string()));
+    }
+    Ok(())
+}
+
 pub async fn get_workshop(db: &DbConn, owner_id: Uuid) -> Result<Option<WorkshopResponse>> {
     let ws = workshop::Entity::load()
         .filter(workshop::Column::OwnerId.eq(owner_id))
@@ -56,6 +74,7 @@ pub async fn create_workshop(
     {
         return Err(AppError::BadRequest("已创建工坊".to_string()));
     }
+    validate_tolerance(req.overproduction_tolerance)?;
 
     let ws = workshop::ActiveModelEx::new()
         .set_id(Uuid::new_v4())
@@ -66,6 +85,15 @@ pub async fn create_workshop(
         .set_image(req.image)
         .set_piece_unit(req.piece_unit.unwrap_or_else(|| "打".to_string()))
         .set_business_label(req.business_label.unwrap_or_else(|| "工坊".to_string()))
+        .set_overproduction_policy(
+            req.overproduction_policy
+                .unwrap_or(workshop::OverproductionPolicy::Warn),
+        )
+        .set_overproduction_tolerance(req.overproduction_tolerance.unwrap_or(0))
+        .set_payroll_divergence_policy(
+            req.payroll_divergence_policy
+                .unwrap_or(workshop::PayrollDivergencePolicy::Warn),
+        )
         .set_created_at(chrono::Utc::now())
         .insert(db)
         .await?;
@@ -78,6 +106,7 @@ pub async fn update_workshop(
     owner_id: Uuid,
     req: UpdateWorkshopRequest,
 ) -> Result<WorkshopResponse> {
+    validate_tolerance(req.overproduction_tolerance)?;
     let ws = get_boss_workshop(db, owner_id).await?;
 
     let mut active: workshop::ActiveModelEx = ws.into();
@@ -99,6 +128,15 @@ pub async fn update_workshop(
     if let Some(v) = req.business_label {
         active.business_label = Set(v);
     }
+    if let Some(v) = req.overproduction_policy {
+        active.overproduction_policy = Set(v);
+    }
+    if let Some(v) = req.overproduction_tolerance {
+        active.overproduction_tolerance = Set(v);
+    }
+    if let Some(v) = req.payroll_divergence_policy {
+        active.payroll_divergence_policy = Set(v);
+    }
 
     let ws = active.update(db).await?;
     Ok(to_response(&ws))
@@ -191,13 +229,7 @@ pub async fn get_staff_list(
         .fetch_page(params.page.saturating_sub(1))
         .await?
         .into_iter()
-        .map(|s| StaffResponse {
-            id: s.id,
-            username: s.username,
-            display_name: s.display_name,
-            phone: Some(s.phone),
-            avatar: s.avatar,
-        })
+        .map(staff_response)
         .collect();
 
     Ok(ListData { list, total })
@@ -221,3 +253,199 @@ pub async fn remove_staff(db: &DbConn, boss_id: Uuid, staff_id: Uuid) -> Result<
     active.set_workshop_id(None).update(db).await?;
     Ok(())
 }
+
+// 设置员工技能分组
+pub async fn update_staff_skills(
+    db: &DbConn,
+    notifier: &Notifier,
+    boss_id: Uuid,
+    staff_id: Uuid,
+    req: UpdateStaffSkillsRequest,
+) -> Result<StaffResponse> {
+    let ws = get_boss_workshop(db, boss_id).await?;
+
+    let staff = user::Entity::find_by_id(staff_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("员工不存在".to_string()))?;
+
+    if staff.workshop_id != Some(ws.id) {
+        return Err(AppError::BadRequest("该员工不属于您的工坊".to_string()));
+    }
+
+    let mut skills: Vec<String> = Vec::new();
+    for s in req.skills {
+        let s = s.trim().to_string();
+        if !s.is_empty() && !skills.contains(&s) {
+            skills.push(s);
+        }
+    }
+    let skills = if skills.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_value(&skills).map_err(|e| AppError::Internal(e.to_string()))?)
+    };
+
+    let before = staff.clone();
+    let mut active: user::ActiveModel = staff.into();
+    active.skills = Set(skills);
+    let after = active.update(db).await?;
+
+    // 按技能分配的进行中工序，分配结果变化时通知员工
+    let processes = process::Entity::find()
+        .find_also_related(order::Entity)
+        .filter(process::Column::BossId.eq(boss_id))
+        .filter(process::Column::AssignedSkills.is_not_null())
+        .filter(order::Column::Status.is_in([OrderStatus::Pending, OrderStatus::Processing]))
+        .all(db)
+        .await?;
+    for (proc, ord) in processes {
+        let order_name = ord.map(|o| o.product_name).unwrap_or_default();
+        let notification = match (proc.is_assigned_to(&before), proc.is_assigned_to(&after)) {
+            (false, true) => Notification::ProcessAssigned {
+                process_name: proc.name,
+                order_name,
+            },
+            (true, false) => Notification::ProcessUnassigned {
+                process_name: proc.name,
+                order_name,
+            },
+            _ => continue,
+        };
+        notifier.send(after.id, notification);
+    }
+
+    Ok(staff_response(after))
+}
+
+// 设置员工计时工资标准
+pub async fn update_staff_wage_rates(
+    db: &DbConn,
+    boss_id: Uuid,
+    staff_id: Uuid,
+    req: UpdateStaffWageRatesRequest,
+) -> Result<StaffResponse> {
+    let ws = get_boss_workshop(db, boss_id).await?;
+
+    let staff = user::Entity::find_by_id(staff_id)
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("员工不存在".to_string()))?;
+
+    if staff.workshop_id != Some(ws.id) {
+        return Err(AppError::BadRequest("该员工不属于您的工坊".to_string()));
+    }
+    for rate in [req.hourly_rate, req.daily_rate].into_iter().flatten() {
+        if rate <= Decimal::ZERO {
+            return Err(AppError::BadRequest("工资标准必须大于0".to_string()));
+        }
+    }
+
+    let mut active: user::ActiveModel = staff.into();
+    active.hourly_rate = Set(req.hourly_rate.map(|r| r.round_dp(2)));
+    active.daily_rate = Set(req.daily_rate.map(|r| r.round_dp(2)));
+    Ok(staff_response(active.update(db).await?))
+}
+
+fn staff_response(s: user::Model) -> StaffResponse {
+    StaffResponse {
+        id: s.id,
+        username: s.username.clone(),
+        display_name: s.display_name.clone(),
+        phone: Some(s.phone.clone()),
+        avatar: s.avatar.clone(),
+        skills: s.skill_tags(),
+        payout_account: s.payout(),
+        hourly_rate: s.hourly_rate,
+        daily_rate: s.daily_rate,
+    }
+}
+
+// 获取计件自动审核规则
+pub async fn get_auto_approve_rules(db: &DbConn, boss_id: Uuid) -> Result<Vec<AutoApproveRule>> {
+    let ws = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("请先创建工坊".to_string()))?;
+    Ok(ws.approve_rules())
+}
+
+// 设置计件自动审核规则
+pub async fn update_auto_approve_rules(
+    db: &DbConn,
+    boss_id: Uuid,
+    req: UpdateAutoApproveRulesRequest,
+) -> Result<Vec<AutoApproveRule>> {
+    let ws = workshop::Entity::find()
+        .filter(workshop::Column::OwnerId.eq(boss_id))
+        .one(db)
+        .await?
+        .ok_or_else(|| AppError::BadRequest("请先创建工坊".to_string()))?;
+
+    let staff_ids: HashSet<Uuid> = user::Entity::find()
+        .filter(user::Column::WorkshopId.eq(ws.id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|u| u.id)
+        .collect();
+    let process_ids: Vec<Uuid> = req
+        .rules
+        .iter()
+        .flat_map(|r| r.process_ids.iter().copied())
+        .collect();
+    let own_processes: HashSet<Uuid> = process::Entity::find()
+        .filter(process::Column::Id.is_in(process_ids))
+        .filter(process::Column::BossId.eq(boss_id))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|p| p.id)
+        .collect();
+
+    let mut names = HashSet::new();
+    let mut rules = Vec::with_capacity(req.rules.len());
+    for mut rule in req.rules {
+        rule.name = rule.name.trim().to_string();
+        if rule.name.is_empty() {
+            return Err(AppError::BadRequest("规则名称不能为空".to_string()));
+        }
+        if !names.insert(rule.name.clone()) {
+            return Err(AppError::BadRequest(format!("规则名称重复: {}", rule.name)));
+        }
+        if let Some(max) = rule.max_quantity
+            && max <= 0
+        {
+            return Err(AppError::BadRequest("单条数量上限需大于 0".to_string()));
+        }
+        if rule.user_ids.iter().any(|id| !staff_ids.contains(id)) {
+            return Err(AppError::BadRequest(format!(
+                "规则「{}」包含不属于本工坊的员工",
+                rule.name
+            )));
+        }
+        if rule.process_ids.iter().any(|id| !own_processes.contains(id)) {
+            return Err(AppError::BadRequest(format!("规则「{}」包含无效的工序", rule.name)));
+        }
+        rule.user_ids.sort();
+        rule.user_ids.dedup();
+        rule.process_ids.sort();
+        rule.process_ids.dedup();
+        rules.push(rule);
+    }
+
+    let value = if rules.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_value(&rules).map_err(|e| AppError::Internal(e.to_string()))?)
+    };
+    workshop::ActiveModel {
+        id: Set(ws.id),
+        auto_approve_rules: Set(value),
+        ..Default::default()
+    }
+    .update(db)
+    .await?;
+    Ok(rules)
+}
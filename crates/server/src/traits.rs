@@ -53,3 +53,39 @@ impl OwnedByBoss for entity::piece_record::Model {
         self.boss_id
     }
 }
+
+impl OwnedByBoss for entity::bundle::Model {
+    fn boss_id(&self) -> Uuid {
+        self.boss_id
+    }
+}
+
+impl OwnedByBoss for entity::pay_run::Model {
+    fn boss_id(&self) -> Uuid {
+        self.boss_id
+    }
+}
+
+impl OwnedByBoss for entity::advance::Model {
+    fn boss_id(&self) -> Uuid {
+        self.boss_id
+    }
+}
+
+impl OwnedByBoss for entity::attendance::Model {
+    fn boss_id(&self) -> Uuid {
+        self.boss_id
+    }
+}
+
+impl OwnedByBoss for entity::customer_contact::Model {
+    fn boss_id(&self) -> Uuid {
+        self.boss_id
+    }
+}
+
+impl OwnedByBoss for entity::customer_address::Model {
+    fn boss_id(&self) -> Uuid {
+        self.boss_id
+    }
+}
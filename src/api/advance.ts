@@ -0,0 +1,17 @@
+import { client } from "./client";
+import type {
+  ListData,
+  QueryParams,
+  Advance,
+  AdvanceBalance,
+  CreateAdvanceDto,
+} from "@/types";
+
+export const advanceApi = {
+  list: (params?: QueryParams & { userId?: string }) =>
+    client.get<ListData<Advance>>("/api/advances", params),
+  create: (data: CreateAdvanceDto) =>
+    client.post<Advance>("/api/advances", data),
+  delete: (id: string) => client.delete<void>(`/api/advances/${id}`),
+  balances: () => client.get<AdvanceBalance[]>("/api/advances/balances"),
+};
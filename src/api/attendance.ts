@@ -0,0 +1,20 @@
+import { client } from "./client";
+import type {
+  ListData,
+  QueryParams,
+  Attendance,
+  CreateAttendanceDto,
+  UpdateAttendanceDto,
+} from "@/types";
+
+export const attendanceApi = {
+  list: (params?: QueryParams & { userId?: string }) =>
+    client.get<ListData<Attendance>>("/api/attendance", params),
+  create: (data: CreateAttendanceDto) =>
+    client.post<Attendance>("/api/attendance", data),
+  update: (id: string, data: UpdateAttendanceDto) =>
+    client.put<Attendance>(`/api/attendance/${id}`, data),
+  delete: (id: string) => client.delete<void>(`/api/attendance/${id}`),
+  clockIn: () => client.post<Attendance>("/api/attendance/clock-in"),
+  clockOut: () => client.post<Attendance>("/api/attendance/clock-out"),
+};
@@ -10,11 +10,13 @@ import type {
   UpdateProfileRequest,
   ChangePasswordRequest,
   Staff,
+  UpdateStaffWageRatesRequest,
   ListData,
   QueryParams,
   Workshop,
   CreateWorkshopRequest,
   UpdateWorkshopRequest,
+  AutoApproveRule,
 } from "@/types";
 
 export const authApi = {
@@ -41,6 +43,8 @@ export const authApi = {
   getStaffList: (params?: QueryParams) =>
     client.get<ListData<Staff>>("/api/staff", { params }),
   removeStaff: (staffId: string) => client.delete<void>(`/api/staff/${staffId}`),
+  updateStaffWageRates: (staffId: string, data: UpdateStaffWageRatesRequest) =>
+    client.put<Staff>(`/api/staff/${staffId}/wage-rates`, data),
   getProfile: () => client.get<LoginUser>("/api/profile"),
   // Workshop
   getWorkshop: () => client.get<Workshop | null>("/api/workshop"),
@@ -48,4 +52,8 @@ export const authApi = {
     client.post<Workshop>("/api/workshop", data),
   updateWorkshop: (data: UpdateWorkshopRequest) =>
     client.put<Workshop>("/api/workshop", data),
+  getAutoApproveRules: () =>
+    client.get<AutoApproveRule[]>("/api/workshop/auto-approve-rules"),
+  updateAutoApproveRules: (rules: AutoApproveRule[]) =>
+    client.put<AutoApproveRule[]>("/api/workshop/auto-approve-rules", { rules }),
 };
@@ -0,0 +1,20 @@
+import { client } from "./client";
+import type {
+  Bundle,
+  GenerateBundlesDto,
+  ScanBundleDto,
+  PieceRecord,
+} from "@/types";
+
+export const bundleApi = {
+  listByOrder: (orderId: string) =>
+    client.get<Bundle[]>(`/api/orders/${orderId}/bundles`),
+  generate: (orderId: string, data: GenerateBundlesDto) =>
+    client.post<Bundle[]>(`/api/orders/${orderId}/bundles`, data),
+  getOne: (id: string) =>
+    client.get<Bundle>(`/api/bundles/${id}`),
+  delete: (id: string) =>
+    client.delete<void>(`/api/bundles/${id}`),
+  scan: (data: ScanBundleDto) =>
+    client.post<PieceRecord>("/api/bundles/scan", data),
+};
@@ -39,6 +39,24 @@ async function request<T>(path: string, options: RequestInit = {}): Promise<T> {
   return json.data;
 }
 
+/** 下载文件，传 body 时使用 POST；失败时后端返回 JSON 错误 */
+async function download(path: string, body?: unknown): Promise<Blob> {
+  const token = getToken();
+  const res = await fetch(`${BASE_URL}${path}`, {
+    method: body === undefined ? "GET" : "POST",
+    headers: {
+      ...(body === undefined ? {} : { "Content-Type": "application/json" }),
+      ...(token ? { Authorization: `Bearer ${token}` } : {}),
+    },
+    body: body === undefined ? undefined : JSON.stringify(body),
+  });
+  if (res.headers.get("Content-Type")?.includes("application/json")) {
+    const json: ApiResponse<unknown> = await res.json();
+    throw new ApiError(json.code, json.message);
+  }
+  return res.blob();
+}
+
 export const client = {
   get: <T>(path: string, params?: object) => {
     if (!params) return request<T>(path);
@@ -61,4 +79,5 @@ export const client = {
   patch: <T>(path: string, body?: unknown) =>
     request<T>(path, { method: "PATCH", body: JSON.stringify(body) }),
   delete: <T>(path: string) => request<T>(path, { method: "DELETE" }),
+  download,
 };
@@ -1,15 +1,42 @@
 import { client } from "./client";
-import type { ListData, QueryParams, Customer, CreateCustomerDto, UpdateCustomerDto } from "@/types";
+import type {
+  ListData,
+  QueryParams,
+  Customer,
+  CustomerDetail,
+  CreateCustomerDto,
+  UpdateCustomerDto,
+  CustomerContact,
+  CustomerContactDto,
+  CustomerAddress,
+  CustomerAddressDto,
+} from "@/types";
 
 export const customerApi = {
   list: (params?: QueryParams) =>
     client.get<ListData<Customer>>("/api/customers", params),
   getOne: (id: string) =>
-    client.get<Customer>(`/api/customers/${id}`),
+    client.get<CustomerDetail>(`/api/customers/${id}`),
   create: (data: CreateCustomerDto) =>
     client.post<Customer>("/api/customers", data),
   update: (id: string, data: UpdateCustomerDto) =>
     client.put<Customer>(`/api/customers/${id}`, data),
   delete: (id: string) =>
     client.delete<void>(`/api/customers/${id}`),
+  listContacts: (customerId: string) =>
+    client.get<CustomerContact[]>(`/api/customers/${customerId}/contacts`),
+  createContact: (customerId: string, data: CustomerContactDto) =>
+    client.post<CustomerContact>(`/api/customers/${customerId}/contacts`, data),
+  updateContact: (id: string, data: CustomerContactDto) =>
+    client.put<CustomerContact>(`/api/customer-contacts/${id}`, data),
+  deleteContact: (id: string) =>
+    client.delete<void>(`/api/customer-contacts/${id}`),
+  listAddresses: (customerId: string) =>
+    client.get<CustomerAddress[]>(`/api/customers/${customerId}/addresses`),
+  createAddress: (customerId: string, data: CustomerAddressDto) =>
+    client.post<CustomerAddress>(`/api/customers/${customerId}/addresses`, data),
+  updateAddress: (id: string, data: CustomerAddressDto) =>
+    client.put<CustomerAddress>(`/api/customer-addresses/${id}`, data),
+  deleteAddress: (id: string) =>
+    client.delete<void>(`/api/customer-addresses/${id}`),
 };
@@ -1,6 +1,9 @@
 export { client, setToken, clearToken } from "./client";
 export { adminApi } from "./admin";
+export { advanceApi } from "./advance";
+export { attendanceApi } from "./attendance";
 export { authApi } from "./auth";
+export { bundleApi } from "./bundle";
 export { chatApi } from "./chat";
 export { customerApi } from "./customer";
 export { homeApi } from "./home";
@@ -8,6 +11,7 @@ export { orderApi } from "./order";
 export { processApi } from "./process";
 export { pieceRecordApi } from "./piece-record";
 export { payrollApi } from "./payroll";
+export { payRunApi } from "./pay-run";
 export { statsApi } from "./stats";
 export { shareApi } from "./share";
 export { uploadApi, getFileUrl } from "./upload";
@@ -0,0 +1,22 @@
+import { client } from "./client";
+import type {
+  ListData,
+  QueryParams,
+  PayRun,
+  PayRunDetail,
+  CreatePayRunDto,
+  UpdatePayRunEntryDto,
+} from "@/types";
+
+export const payRunApi = {
+  list: (params?: QueryParams) =>
+    client.get<ListData<PayRun>>("/api/pay-runs", params),
+  getOne: (id: string) => client.get<PayRunDetail>(`/api/pay-runs/${id}`),
+  create: (data: CreatePayRunDto) =>
+    client.post<PayRunDetail>("/api/pay-runs", data),
+  updateEntry: (id: string, entryId: string, data: UpdatePayRunEntryDto) =>
+    client.put<PayRunDetail>(`/api/pay-runs/${id}/entries/${entryId}`, data),
+  finalize: (id: string) =>
+    client.post<PayRunDetail>(`/api/pay-runs/${id}/finalize`),
+  delete: (id: string) => client.delete<void>(`/api/pay-runs/${id}`),
+};
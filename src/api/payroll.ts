@@ -6,15 +6,37 @@ import type {
   CreatePayrollDto,
   UpdatePayrollDto,
   PayrollDetail,
+  VoidPayrollDto,
+  ExportPayrollsDto,
+  AcknowledgePayrollDto,
+  DisputePayrollDto,
+  ResolvePayrollDto,
+  PayrollPreview,
+  PayrollPreviewParams,
 } from "@/types";
 
 export const payrollApi = {
   list: (params?: QueryParams) =>
     client.get<ListData<Payroll>>("/api/payrolls", params),
+  preview: (params?: PayrollPreviewParams) =>
+    client.get<PayrollPreview>("/api/payrolls/preview", params),
   getOne: (id: string) => client.get<PayrollDetail>(`/api/payrolls/${id}`),
   create: (data: CreatePayrollDto) =>
     client.post<Payroll>("/api/payrolls", data),
   update: (id: string, data: UpdatePayrollDto) =>
     client.put<Payroll>(`/api/payrolls/${id}`, data),
   delete: (id: string) => client.delete<void>(`/api/payrolls/${id}`),
+  payslip: (id: string) => client.download(`/api/payrolls/${id}/payslip`),
+  exportTransfers: (data: ExportPayrollsDto) =>
+    client.download("/api/payrolls/export", data),
+  markTransferred: (payrollIds: string[]) =>
+    client.post<number>("/api/payrolls/mark-transferred", { payrollIds }),
+  void: (id: string, data: VoidPayrollDto) =>
+    client.post<Payroll>(`/api/payrolls/${id}/void`, data),
+  acknowledge: (id: string, data: AcknowledgePayrollDto) =>
+    client.post<Payroll>(`/api/payrolls/${id}/acknowledge`, data),
+  dispute: (id: string, data: DisputePayrollDto) =>
+    client.post<Payroll>(`/api/payrolls/${id}/dispute`, data),
+  resolve: (id: string, data: ResolvePayrollDto) =>
+    client.post<Payroll>(`/api/payrolls/${id}/resolve`, data),
 };
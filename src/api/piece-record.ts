@@ -5,6 +5,10 @@ import type {
   PieceRecord,
   CreatePieceRecordDto,
   UpdatePieceRecordDto,
+  RejectPieceRecordDto,
+  ResubmitPieceRecordDto,
+  AdjustPieceRecordDto,
+  PieceRecordLog,
 } from "@/types";
 
 export const pieceRecordApi = {
@@ -20,10 +24,18 @@ export const pieceRecordApi = {
     client.delete<void>(`/api/piece-records/${id}`),
   approve: (id: string) =>
     client.post<PieceRecord>(`/api/piece-records/${id}/approve`),
-  reject: (id: string) =>
-    client.post<PieceRecord>(`/api/piece-records/${id}/reject`),
+  reject: (id: string, data: RejectPieceRecordDto = {}) =>
+    client.post<PieceRecord>(`/api/piece-records/${id}/reject`, data),
   batchApprove: (ids: string[]) =>
     client.post<number>("/api/piece-records/batch-approve", { ids }),
-  batchReject: (ids: string[]) =>
-    client.post<number>("/api/piece-records/batch-reject", { ids }),
+  batchReject: (ids: string[], data: RejectPieceRecordDto = {}) =>
+    client.post<number>("/api/piece-records/batch-reject", { ids, ...data }),
+  resubmit: (id: string, data: ResubmitPieceRecordDto) =>
+    client.post<PieceRecord>(`/api/piece-records/${id}/resubmit`, data),
+  adjust: (id: string, data: AdjustPieceRecordDto) =>
+    client.post<PieceRecord>(`/api/piece-records/${id}/adjust`, data),
+  history: (id: string) =>
+    client.get<PieceRecordLog[]>(`/api/piece-records/${id}/history`),
+  rejectReasons: () =>
+    client.get<string[]>("/api/piece-records/reject-reasons"),
 };
@@ -0,0 +1,103 @@
+import { Form, Picker } from "antd-mobile";
+import { useCustomerDetail } from "@/hooks";
+import type { CustomerAddress, CustomerContact } from "@/types";
+
+export function formatAddress(address: CustomerAddress) {
+  return address.label ? `${address.label} · ${address.address}` : address.address;
+}
+
+export function formatContact(contact: CustomerContact) {
+  return contact.role ? `${contact.name}（${contact.role}）` : contact.name;
+}
+
+interface DeliveryPickerProps {
+  customerId: string;
+  addressId?: string;
+  contactId?: string;
+  onAddressChange: (id?: string) => void;
+  onContactChange: (id?: string) => void;
+  /** 编辑订单时可清空，未选择即不指定，不再回落到默认值 */
+  clearable?: boolean;
+}
+
+/** 订单表单中选择客户的收货地址和联系人，未选择时使用客户的默认值 */
+export function DeliveryPicker({
+  customerId,
+  addressId,
+  contactId,
+  onAddressChange,
+  onContactChange,
+  clearable,
+}: DeliveryPickerProps) {
+  const { data: customer } = useCustomerDetail(customerId);
+  const addresses = (customer?.addresses ?? []).filter(
+    (a) => a.kind === "delivery"
+  );
+  const contacts = customer?.contacts ?? [];
+
+  if (!customerId) return null;
+
+  const selectedAddress = addressId
+    ? addresses.find((a) => a.id === addressId)
+    : clearable
+      ? undefined
+      : addresses.find((a) => a.isDefault);
+  const selectedContact = contactId
+    ? contacts.find((c) => c.id === contactId)
+    : clearable
+      ? undefined
+      : contacts.find((c) => c.isDefault);
+  const isDefault = (id?: string) => (id ? "" : "（默认）");
+  const clearOption = clearable ? [{ label: "不指定", value: "" }] : [];
+
+  const handleSelectAddress = async () => {
+    const options = addresses.map((a) => ({
+      label: formatAddress(a),
+      value: a.id,
+    }));
+    const val = await Picker.prompt({ columns: [[...clearOption, ...options]] });
+    if (val) onAddressChange((val[0] as string) || undefined);
+  };
+
+  const handleSelectContact = async () => {
+    const options = contacts.map((c) => ({
+      label: formatContact(c),
+      value: c.id,
+    }));
+    const val = await Picker.prompt({ columns: [[...clearOption, ...options]] });
+    if (val) onContactChange((val[0] as string) || undefined);
+  };
+
+  return (
+    <>
+      <Form.Item
+        label="收货地址"
+        clickable={addresses.length > 0}
+        onClick={addresses.length > 0 ? handleSelectAddress : undefined}
+        extra={
+          selectedAddress
+            ? formatAddress(selectedAddress) + isDefault(addressId)
+            : clearable && addresses.length
+              ? "不指定"
+              : addresses.length
+              ? "请选择收货地址"
+              : "客户暂无收货地址"
+        }
+      />
+      <Form.Item
+        label="联系人"
+        clickable={contacts.length > 0}
+        onClick={contacts.length > 0 ? handleSelectContact : undefined}
+        extra={
+          selectedContact
+            ? formatContact(selectedContact) + isDefault(contactId)
+            : clearable && contacts.length
+              ? "不指定"
+              : contacts.length
+              ? "请选择联系人"
+              : "客户暂无联系人"
+        }
+      />
+    </>
+  );
+}
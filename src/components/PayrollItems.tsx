@@ -0,0 +1,39 @@
+import { PAYROLL_ITEM_KIND_MAP } from "@/constants";
+import type { PayrollItem } from "@/types";
+
+/**
+ * 工资明细列表，按工资单中的顺序展示
+ */
+export function PayrollItems({ items }: { items: PayrollItem[] }) {
+  if (items.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="bg-white mt-2 p-4">
+      <div className="text-sm text-gray-500 mb-3">工资明细</div>
+      <div className="space-y-2">
+        {items.map((item) => {
+          const amount = parseFloat(item.amount);
+          return (
+            <div key={item.id} className="flex justify-between text-sm">
+              <div className="min-w-0">
+                <span>{PAYROLL_ITEM_KIND_MAP[item.kind]}</span>
+                {item.note && (
+                  <span className="text-gray-400 ml-2">{item.note}</span>
+                )}
+              </div>
+              <span
+                className={
+                  amount < 0 ? "text-red-500 shrink-0" : "text-gray-800 shrink-0"
+                }
+              >
+                {amount < 0 ? "-" : ""}¥{Math.abs(amount).toFixed(2)}
+              </span>
+            </div>
+          );
+        })}
+      </div>
+    </div>
+  );
+}
@@ -6,6 +6,7 @@ export { useAvatarCropper } from "./AvatarCropper";
 export { PageHeader } from "./PageHeader";
 export { Chart, MiniChart } from "./charts";
 export { OrderPicker } from "./OrderPicker";
+export { DeliveryPicker, formatAddress, formatContact } from "./DeliveryPicker";
 export { OssImage, getOssUrl, getOssUrls, Image } from "./OssImage";
 
 // 新增共享组件
@@ -14,4 +15,5 @@ export { StatsGrid } from "./StatsGrid";
 export { DateRangeButton } from "./DateRangeButton";
 export { StatusTag } from "./StatusTag";
 export { EmptyState } from "./EmptyState";
+export { PayrollItems } from "./PayrollItems";
 export { BiometricGuard } from "./BiometricGuard";
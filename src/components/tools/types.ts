@@ -161,12 +161,18 @@ interface UnpaidSummaryItem {
   userId: string;
   userName: string;
   totalQuantity: number;
-  totalAmount: string;
+  pieceAmount: string;
+  hours: string;
+  timeAmount: string;
+  outstandingAdvance: string;
+  suggestedRepayment: string;
+  suggestedAmount: string; // 扣除借支后的建议发放金额
 }
 
 // 待发工资汇总响应
 interface UnpaidSummaryResponse {
   list: UnpaidSummaryItem[];
+  totalAmount: string;
 }
 
 export type {
@@ -16,8 +16,19 @@ export function getUnpaidSummaryColumns(): Column[] {
       format: { kind: "number" },
     },
     {
-      key: "totalAmount",
-      label: "待发金额",
+      key: "timeAmount",
+      label: "计时金额",
+      align: "right",
+      priority: "secondary",
+      format: {
+        kind: "currency",
+        currency: "CNY",
+        decimals: 2,
+      },
+    },
+    {
+      key: "suggestedAmount",
+      label: "建议发放",
       align: "right",
       priority: "primary",
       format: {
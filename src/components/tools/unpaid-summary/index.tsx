@@ -12,10 +12,6 @@ function getSummaryStats(data: UnpaidSummaryResponse): StatItem[] {
     (sum, item) => sum + item.totalQuantity,
     0
   );
-  const totalAmount = data.list.reduce(
-    (sum, item) => sum + parseFloat(item.totalAmount),
-    0
-  );
 
   return [
     {
@@ -32,8 +28,8 @@ function getSummaryStats(data: UnpaidSummaryResponse): StatItem[] {
     },
     {
       key: "total-amount",
-      label: "待发总金额",
-      value: totalAmount,
+      label: "建议发放总额",
+      value: parseFloat(data.totalAmount),
       format: { kind: "currency", currency: "CNY" },
     },
   ];
@@ -75,7 +71,8 @@ export const UnpaidSummaryToolUi = makeAssistantToolUI<unknown, Output>({
       userId: item.userId,
       userName: item.userName,
       totalQuantity: item.totalQuantity,
-      totalAmount: item.totalAmount,
+      timeAmount: item.timeAmount,
+      suggestedAmount: item.suggestedAmount,
     }));
 
     return (
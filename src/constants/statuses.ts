@@ -1,4 +1,10 @@
-import type { OrderStatus, PieceRecordStatus } from "@/types";
+import type {
+  OrderStatus,
+  PayrollAckStatus,
+  PayrollItemKind,
+  PayoutMethod,
+  PieceRecordStatus,
+} from "@/types";
 
 // 订单状态映射
 export const ORDER_STATUS_MAP: Record<OrderStatus, { label: string; color: string }> = {
@@ -43,3 +49,45 @@ export const RECORD_STATUS_OPTIONS_NO_ALL = [
   { key: "rejected", title: "已拒绝" },
   { key: "settled", title: "已结算" },
 ] as const;
+
+// 工资单确认状态映射
+export const PAYROLL_ACK_STATUS_MAP: Record<PayrollAckStatus, { label: string; color: string }> = {
+  pending: { label: "待确认", color: "#faad14" },
+  acknowledged: { label: "已确认", color: "#52c41a" },
+  disputed: { label: "有异议", color: "#ff4d4f" },
+  resolved: { label: "已处理", color: "#1890ff" },
+};
+
+// 工资单确认状态选项（用于筛选）
+export const PAYROLL_ACK_STATUS_OPTIONS = [
+  { key: "", title: "全部状态" },
+  { key: "pending", title: "待确认" },
+  { key: "acknowledged", title: "已确认" },
+  { key: "disputed", title: "有异议" },
+  { key: "resolved", title: "已处理" },
+] as const;
+
+// 工资明细类型名称
+export const PAYROLL_ITEM_KIND_MAP: Record<PayrollItemKind, string> = {
+  pieceWork: "计件工资",
+  timeWork: "计时工资",
+  bonus: "奖金",
+  deduction: "扣款",
+  advanceRepayment: "借支还款",
+  rounding: "抹零",
+  manualAdjustment: "手动调整",
+};
+
+// 收款方式名称
+export const PAYOUT_METHOD_MAP: Record<PayoutMethod, string> = {
+  bank: "银行卡",
+  alipay: "支付宝",
+  wechat: "微信",
+};
+
+// 转账文件格式选项
+export const EXPORT_LAYOUT_OPTIONS = [
+  { label: "通用", value: "generic" },
+  { label: "支付宝批量转账", value: "alipay" },
+  { label: "银行代发", value: "bank" },
+] as const;
@@ -7,6 +7,7 @@ export * from "./use-payrolls";
 export * from "./use-stats";
 export * from "./use-shares";
 export * from "./use-staff-list";
+export * from "./use-advance-balance";
 
 // 新增通用 Hooks
 export * from "./use-date-range";
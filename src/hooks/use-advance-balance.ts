@@ -0,0 +1,12 @@
+import { useQuery } from "@tanstack/react-query";
+import { advanceApi } from "@/api";
+
+/** 员工本人的未还借支（元） */
+export const useMyOutstandingAdvance = (enabled = true) => {
+  const { data } = useQuery({
+    queryKey: ["advance-balances"],
+    queryFn: advanceApi.balances,
+    enabled,
+  });
+  return parseFloat(data?.[0]?.outstanding ?? "0");
+};
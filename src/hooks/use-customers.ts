@@ -1,3 +1,4 @@
+import { useQuery } from "@tanstack/react-query";
 import { createCrudHooks } from "./create-crud-hooks";
 import { customerApi } from "@/api";
 import type { Customer, CreateCustomerDto, UpdateCustomerDto } from "@/types";
@@ -9,3 +10,11 @@ export const useCustomer = hooks.useOne;
 export const useCreateCustomer = hooks.useCreate;
 export const useUpdateCustomer = hooks.useUpdate;
 export const useDeleteCustomer = hooks.useDelete;
+
+// 客户详情，包含联系人和地址
+export const useCustomerDetail = (id: string) =>
+  useQuery({
+    queryKey: ["customers", id],
+    queryFn: () => customerApi.getOne(id),
+    enabled: !!id,
+  });
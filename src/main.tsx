@@ -41,6 +41,14 @@ function handleDeepLink(urls: string[]) {
           return;
         }
       }
+      // 处理 stitchwork://bundle?id=xxx，扫扎的二维码计件
+      if (path === "bundle") {
+        const id = url.searchParams.get("id");
+        if (id) {
+          router.navigate({ to: "/bundle", search: { id } });
+          return;
+        }
+      }
     } catch {
       // 忽略无效 URL
     }
@@ -1,9 +1,36 @@
 import { createFileRoute, useNavigate } from "@tanstack/react-router";
-import { Form, Input, Button, NavBar, Dialog, Toast } from "antd-mobile";
-import { ChevronLeft } from "lucide-react";
-import { useCustomer, useUpdateCustomer } from "@/hooks";
-import { BiometricGuard } from "@/components";
-import type { UpdateCustomerDto } from "@/types";
+import {
+  Form,
+  Input,
+  Button,
+  NavBar,
+  Dialog,
+  Toast,
+  List,
+  SwipeAction,
+  Popup,
+  Radio,
+  Checkbox,
+  Tag,
+} from "antd-mobile";
+import { ChevronLeft, Plus } from "lucide-react";
+import { useMutation, useQueryClient } from "@tanstack/react-query";
+import { useState } from "react";
+import { customerApi } from "@/api";
+import { useCustomerDetail, useUpdateCustomer } from "@/hooks";
+import { BiometricGuard, formatAddress, formatContact } from "@/components";
+import type {
+  CustomerAddress,
+  CustomerAddressDto,
+  CustomerContact,
+  CustomerContactDto,
+  UpdateCustomerDto,
+} from "@/types";
+
+const ADDRESS_KIND_LABEL = {
+  delivery: "收货",
+  billing: "账单",
+} as const;
 
 export const Route = createFileRoute("/_auth/_boss/customers/$id")({
   component: EditCustomerPage,
@@ -12,7 +39,7 @@ export const Route = createFileRoute("/_auth/_boss/customers/$id")({
 function EditCustomerPage() {
   const { id } = Route.useParams();
   const navigate = useNavigate();
-  const { data: customer, isLoading } = useCustomer(id);
+  const { data: customer, isLoading } = useCustomerDetail(id);
   const updateMutation = useUpdateCustomer();
 
   const handleSubmit = async (values: UpdateCustomerDto) => {
@@ -73,8 +100,340 @@ function EditCustomerPage() {
               <Input placeholder="请输入备注" clearable />
             </Form.Item>
           </Form>
+          <ContactSection customerId={id} contacts={customer?.contacts ?? []} />
+          <AddressSection
+            customerId={id}
+            addresses={customer?.addresses ?? []}
+          />
         </div>
       </div>
     </BiometricGuard>
   );
 }
+
+function showError(e: unknown, fallback: string) {
+  Dialog.alert({
+    content: e instanceof Error ? e.message : fallback,
+    confirmText: "确定",
+  });
+}
+
+function SectionHeader({ title, onAdd }: { title: string; onAdd: () => void }) {
+  return (
+    <div className="flex items-center justify-between mt-6 mb-2">
+      <span className="font-medium">{title}</span>
+      <Button size="small" color="primary" onClick={onAdd}>
+        <div className="flex items-center">
+          <Plus size={16} className="mr-1" />
+          新增
+        </div>
+      </Button>
+    </div>
+  );
+}
+
+/** 客户联系人，如跟单、质检 */
+function ContactSection({
+  customerId,
+  contacts,
+}: {
+  customerId: string;
+  contacts: CustomerContact[];
+}) {
+  const queryClient = useQueryClient();
+  const [editing, setEditing] = useState<CustomerContact | null>(null);
+  const [showForm, setShowForm] = useState(false);
+
+  const saveMutation = useMutation({
+    mutationFn: (data: CustomerContactDto) =>
+      editing
+        ? customerApi.updateContact(editing.id, data)
+        : customerApi.createContact(customerId, data),
+    onSuccess: () => {
+      Toast.show({ content: "保存成功" });
+      setShowForm(false);
+      queryClient.invalidateQueries({ queryKey: ["customers"] });
+    },
+    onError: (e) => showError(e, "保存失败"),
+  });
+  const deleteMutation = useMutation({
+    mutationFn: customerApi.deleteContact,
+    onSuccess: () => {
+      Toast.show({ content: "删除成功" });
+      queryClient.invalidateQueries({ queryKey: ["customers"] });
+    },
+    onError: (e) => showError(e, "删除失败"),
+  });
+
+  const openForm = (contact: CustomerContact | null) => {
+    setEditing(contact);
+    setShowForm(true);
+  };
+
+  const handleDelete = (contact: CustomerContact) => {
+    Dialog.confirm({
+      content: `确定删除联系人「${contact.name}」？`,
+      confirmText: "删除",
+      cancelText: "取消",
+      onConfirm: () => deleteMutation.mutate(contact.id),
+    });
+  };
+
+  return (
+    <>
+      <SectionHeader title="联系人" onAdd={() => openForm(null)} />
+      {contacts.length === 0 ? (
+        <div className="bg-white rounded-lg p-6 text-center text-gray-400">
+          暂无联系人
+        </div>
+      ) : (
+        <List className="rounded-lg overflow-hidden">
+          {contacts.map((contact) => (
+            <SwipeAction
+              key={contact.id}
+              rightActions={[
+                {
+                  key: "edit",
+                  text: "编辑",
+                  color: "primary",
+                  onClick: () => openForm(contact),
+                },
+                {
+                  key: "delete",
+                  text: "删除",
+                  color: "danger",
+                  onClick: () => handleDelete(contact),
+                },
+              ]}
+            >
+              <List.Item
+                description={contact.note}
+                extra={contact.phone}
+              >
+                {formatContact(contact)}
+                {contact.isDefault && (
+                  <Tag color="primary" fill="outline" className="ml-2">
+                    默认
+                  </Tag>
+                )}
+              </List.Item>
+            </SwipeAction>
+          ))}
+        </List>
+      )}
+
+      <Popup
+        visible={showForm}
+        onMaskClick={() => setShowForm(false)}
+        destroyOnClose
+        bodyStyle={{ height: "60vh" }}
+      >
+        <div className="p-4">
+          <h3 className="text-lg font-medium mb-4">
+            {editing ? "编辑联系人" : "新增联系人"}
+          </h3>
+          <Form
+            initialValues={editing ?? { isDefault: contacts.length === 0 }}
+            onFinish={(values: CustomerContactDto) =>
+              saveMutation.mutate(values)
+            }
+            footer={
+              <Button
+                block
+                type="submit"
+                color="primary"
+                loading={saveMutation.isPending}
+              >
+                保存
+              </Button>
+            }
+          >
+            <Form.Item
+              name="name"
+              label="姓名"
+              rules={[{ required: true, message: "请输入姓名" }]}
+            >
+              <Input placeholder="请输入姓名" clearable />
+            </Form.Item>
+            <Form.Item name="role" label="职务">
+              <Input placeholder="如跟单、质检、财务" clearable />
+            </Form.Item>
+            <Form.Item name="phone" label="电话">
+              <Input placeholder="请输入电话" clearable />
+            </Form.Item>
+            <Form.Item name="note" label="备注">
+              <Input placeholder="请输入备注" clearable />
+            </Form.Item>
+            <Form.Item name="isDefault" valuePropName="checked">
+              <Checkbox>设为默认联系人</Checkbox>
+            </Form.Item>
+          </Form>
+        </div>
+      </Popup>
+    </>
+  );
+}
+
+/** 客户地址，区分收货地址和账单地址 */
+function AddressSection({
+  customerId,
+  addresses,
+}: {
+  customerId: string;
+  addresses: CustomerAddress[];
+}) {
+  const queryClient = useQueryClient();
+  const [editing, setEditing] = useState<CustomerAddress | null>(null);
+  const [showForm, setShowForm] = useState(false);
+
+  const saveMutation = useMutation({
+    mutationFn: (data: CustomerAddressDto) =>
+      editing
+        ? customerApi.updateAddress(editing.id, data)
+        : customerApi.createAddress(customerId, data),
+    onSuccess: () => {
+      Toast.show({ content: "保存成功" });
+      setShowForm(false);
+      queryClient.invalidateQueries({ queryKey: ["customers"] });
+    },
+    onError: (e) => showError(e, "保存失败"),
+  });
+  const deleteMutation = useMutation({
+    mutationFn: customerApi.deleteAddress,
+    onSuccess: () => {
+      Toast.show({ content: "删除成功" });
+      queryClient.invalidateQueries({ queryKey: ["customers"] });
+    },
+    onError: (e) => showError(e, "删除失败"),
+  });
+
+  const openForm = (address: CustomerAddress | null) => {
+    setEditing(address);
+    setShowForm(true);
+  };
+
+  const handleDelete = (address: CustomerAddress) => {
+    Dialog.confirm({
+      content: `确定删除地址「${formatAddress(address)}」？`,
+      confirmText: "删除",
+      cancelText: "取消",
+      onConfirm: () => deleteMutation.mutate(address.id),
+    });
+  };
+
+  return (
+    <>
+      <SectionHeader title="地址" onAdd={() => openForm(null)} />
+      {addresses.length === 0 ? (
+        <div className="bg-white rounded-lg p-6 text-center text-gray-400">
+          暂无地址
+        </div>
+      ) : (
+        <List className="rounded-lg overflow-hidden">
+          {addresses.map((address) => (
+            <SwipeAction
+              key={address.id}
+              rightActions={[
+                {
+                  key: "edit",
+                  text: "编辑",
+                  color: "primary",
+                  onClick: () => openForm(address),
+                },
+                {
+                  key: "delete",
+                  text: "删除",
+                  color: "danger",
+                  onClick: () => handleDelete(address),
+                },
+              ]}
+            >
+              <List.Item
+                prefix={
+                  <Tag
+                    color={address.kind === "delivery" ? "success" : "warning"}
+                    fill="outline"
+                  >
+                    {ADDRESS_KIND_LABEL[address.kind]}
+                  </Tag>
+                }
+                description={[address.receiver, address.phone]
+                  .filter(Boolean)
+                  .join(" ")}
+              >
+                {formatAddress(address)}
+                {address.isDefault && (
+                  <Tag color="primary" fill="outline" className="ml-2">
+                    默认
+                  </Tag>
+                )}
+              </List.Item>
+            </SwipeAction>
+          ))}
+        </List>
+      )}
+
+      <Popup
+        visible={showForm}
+        onMaskClick={() => setShowForm(false)}
+        destroyOnClose
+        bodyStyle={{ height: "70vh" }}
+      >
+        <div className="p-4 h-full overflow-y-auto">
+          <h3 className="text-lg font-medium mb-4">
+            {editing ? "编辑地址" : "新增地址"}
+          </h3>
+          <Form
+            initialValues={
+              editing ?? {
+                kind: "delivery",
+                isDefault: !addresses.some((a) => a.kind === "delivery"),
+              }
+            }
+            onFinish={(values: CustomerAddressDto) =>
+              saveMutation.mutate(values)
+            }
+            footer={
+              <Button
+                block
+                type="submit"
+                color="primary"
+                loading={saveMutation.isPending}
+              >
+                保存
+              </Button>
+            }
+          >
+            <Form.Item name="kind" label="类型">
+              <Radio.Group>
+                <Radio value="delivery" className="mr-4">
+                  收货地址
+                </Radio>
+                <Radio value="billing">账单地址</Radio>
+              </Radio.Group>
+            </Form.Item>
+            <Form.Item name="label" label="名称">
+              <Input placeholder="如总部、二号仓" clearable />
+            </Form.Item>
+            <Form.Item
+              name="address"
+              label="详细地址"
+              rules={[{ required: true, message: "请输入详细地址" }]}
+            >
+              <Input placeholder="请输入详细地址" clearable />
+            </Form.Item>
+            <Form.Item name="receiver" label="收件人">
+              <Input placeholder="请输入收件人" clearable />
+            </Form.Item>
+            <Form.Item name="phone" label="电话">
+              <Input placeholder="请输入电话" clearable />
+            </Form.Item>
+            <Form.Item name="isDefault" valuePropName="checked">
+              <Checkbox>设为该类型的默认地址</Checkbox>
+            </Form.Item>
+          </Form>
+        </div>
+      </Popup>
+    </>
+  );
+}
@@ -13,7 +13,7 @@ import {
 import { ChevronLeft } from "lucide-react";
 import { useOrder, useUpdateOrder, useCustomers } from "@/hooks";
 import type { OrderStatus } from "@/types";
-import { ImageUploader } from "@/components";
+import { DeliveryPicker, ImageUploader } from "@/components";
 import { useState, useEffect } from "react";
 
 export const Route = createFileRoute("/_auth/_boss/orders/$id/edit")({
@@ -38,6 +38,8 @@ function EditOrderPage() {
   const [images, setImages] = useState<string[]>([]);
   const [customerId, setCustomerId] = useState("");
   const [status, setStatus] = useState<OrderStatus>("pending");
+  const [deliveryAddressId, setDeliveryAddressId] = useState<string>();
+  const [contactId, setContactId] = useState<string>();
 
   const customers = customersData?.list ?? [];
   const selectedCustomer = customers.find((c) => c.id === customerId);
@@ -48,6 +50,8 @@ function EditOrderPage() {
       setImages(order.images ?? []);
       setCustomerId(order.customerId);
       setStatus(order.status);
+      setDeliveryAddressId(order.deliveryAddressId);
+      setContactId(order.contactId);
     }
   }, [order]);
 
@@ -73,6 +77,9 @@ function EditOrderPage() {
           unitPrice: String(values.unitPrice),
           images: images.length ? images : undefined,
           status,
+          // 未选择时传 null 清除原有的收货地址和联系人
+          deliveryAddressId: deliveryAddressId ?? null,
+          contactId: contactId ?? null,
         },
       });
       Toast.show({ content: "更新成功" });
@@ -115,6 +122,14 @@ function EditOrderPage() {
             onClick={handleSelectCustomer}
             extra={selectedCustomer?.name || "请选择客户"}
           />
+          <DeliveryPicker
+            customerId={order?.customerId ?? ""}
+            addressId={deliveryAddressId}
+            contactId={contactId}
+            onAddressChange={setDeliveryAddressId}
+            onContactChange={setContactId}
+            clearable
+          />
           <Form.Item
             label="状态"
             clickable
@@ -24,7 +24,13 @@ import {
   useDeleteProcess,
 } from "@/hooks";
 import type { Process, CreateProcessDto, UpdateProcessDto } from "@/types";
-import { Image, RelativeTime, getOssUrls } from "@/components";
+import {
+  Image,
+  RelativeTime,
+  getOssUrls,
+  formatAddress,
+  formatContact,
+} from "@/components";
 import { ORDER_STATUS_MAP } from "@/constants";
 import { useQueryClient } from "@tanstack/react-query";
 import { useState } from "react";
@@ -194,6 +200,38 @@ function OrderDetailPage() {
           </div>
         </div>
 
+        {/* 收货信息 */}
+        {(order.deliveryAddress || order.contact) && (
+          <div className="bg-white rounded-lg p-4 mb-4 shadow-sm text-sm">
+            <div className="font-medium mb-2">收货信息</div>
+            {order.deliveryAddress && (
+              <div className="text-gray-600 mb-1">
+                <div>{formatAddress(order.deliveryAddress)}</div>
+                {(order.deliveryAddress.receiver ||
+                  order.deliveryAddress.phone) && (
+                  <div className="text-gray-400">
+                    {order.deliveryAddress.receiver}{" "}
+                    {order.deliveryAddress.phone}
+                  </div>
+                )}
+              </div>
+            )}
+            {order.contact && (
+              <div className="text-gray-600">
+                联系人: {formatContact(order.contact)}
+                {order.contact.phone && (
+                  <a
+                    className="ml-2 text-blue-500"
+                    href={`tel:${order.contact.phone}`}
+                  >
+                    {order.contact.phone}
+                  </a>
+                )}
+              </div>
+            )}
+          </div>
+        )}
+
         {/* 工序列表 */}
         <div className="flex items-center justify-between mb-2">
           <span className="font-medium">工序列表</span>
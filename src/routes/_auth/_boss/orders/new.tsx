@@ -12,7 +12,7 @@ import {
 } from "antd-mobile";
 import { ChevronLeft } from "lucide-react";
 import { useCreateOrder, useCustomers } from "@/hooks";
-import { ImageUploader } from "@/components";
+import { DeliveryPicker, ImageUploader } from "@/components";
 import { useState } from "react";
 
 export const Route = createFileRoute("/_auth/_boss/orders/new")({
@@ -25,13 +25,19 @@ function NewOrderPage() {
   const { data: customersData } = useCustomers({ pageSize: 100 });
   const [images, setImages] = useState<string[]>([]);
   const [customerId, setCustomerId] = useState("");
+  const [deliveryAddressId, setDeliveryAddressId] = useState<string>();
+  const [contactId, setContactId] = useState<string>();
   const customers = customersData?.list ?? [];
   const selectedCustomer = customers.find((c) => c.id === customerId);
 
   const handleSelectCustomer = async () => {
     const options = customers.map((c) => ({ label: c.name, value: c.id }));
     const val = await Picker.prompt({ columns: [options] });
-    if (val) setCustomerId(val[0] as string);
+    if (val) {
+      setCustomerId(val[0] as string);
+      setDeliveryAddressId(undefined);
+      setContactId(undefined);
+    }
   };
 
   const handleSubmit = async (values: Record<string, unknown>) => {
@@ -47,6 +53,8 @@ function NewOrderPage() {
         unitPrice: String(values.unitPrice),
         customerId,
         images: images.length ? images : undefined,
+        deliveryAddressId,
+        contactId,
       });
       Toast.show({ content: "创建成功" });
       navigate({ to: "/orders" });
@@ -82,6 +90,13 @@ function NewOrderPage() {
             onClick={handleSelectCustomer}
             extra={selectedCustomer?.name || "请选择客户"}
           />
+          <DeliveryPicker
+            customerId={customerId}
+            addressId={deliveryAddressId}
+            contactId={contactId}
+            onAddressChange={setDeliveryAddressId}
+            onContactChange={setContactId}
+          />
           <Form.Item
             name="productName"
             label="产品名称"
@@ -1,10 +1,20 @@
 import { createFileRoute, useNavigate } from "@tanstack/react-router";
-import { NavBar, ImageViewer } from "antd-mobile";
-import { ChevronLeft, ImageIcon } from "lucide-react";
-import { useQuery } from "@tanstack/react-query";
+import {
+  NavBar,
+  ImageViewer,
+  Toast,
+  Tag,
+  Button,
+  Popup,
+  TextArea,
+} from "antd-mobile";
+import { ChevronLeft, Download, ImageIcon } from "lucide-react";
+import { saveBlob } from "@/utils/download";
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { payrollApi, getFileUrl } from "@/api";
-import { Image, RelativeTime, BiometricGuard } from "@/components";
+import { Image, RelativeTime, BiometricGuard, PayrollItems } from "@/components";
 import { useStaffList } from "@/hooks";
+import { PAYROLL_ACK_STATUS_MAP } from "@/constants";
 import type { Staff } from "@/types";
 import { useState } from "react";
 
@@ -15,13 +25,44 @@ export const Route = createFileRoute("/_auth/_boss/payroll/$id")({
 function PayrollDetailPage() {
   const { id } = Route.useParams();
   const navigate = useNavigate();
+  const queryClient = useQueryClient();
   const [imageViewerVisible, setImageViewerVisible] = useState(false);
+  const [resolveVisible, setResolveVisible] = useState(false);
+  const [resolution, setResolution] = useState("");
 
   const { data: payroll, isLoading } = useQuery({
     queryKey: ["payroll", id],
     queryFn: () => payrollApi.getOne(id),
   });
 
+  const resolveMutation = useMutation({
+    mutationFn: () => payrollApi.resolve(id, { resolution: resolution.trim() }),
+    onSuccess: () => {
+      Toast.show({ content: "已处理，等待员工确认" });
+      setResolveVisible(false);
+      setResolution("");
+      queryClient.invalidateQueries({ queryKey: ["payroll", id] });
+      queryClient.invalidateQueries({ queryKey: ["payrolls"] });
+    },
+  });
+
+  const handleResolve = () => {
+    if (!resolution.trim()) {
+      Toast.show({ content: "请填写处理说明" });
+      return;
+    }
+    resolveMutation.mutate();
+  };
+
+  const handleDownloadPayslip = async () => {
+    try {
+      const blob = await payrollApi.payslip(id);
+      saveBlob(blob, `工资条-${id.slice(0, 8)}.pdf`);
+    } catch (err) {
+      Toast.show({ content: (err as Error).message || "下载失败" });
+    }
+  };
+
   const { data: staffList } = useStaffList();
   const staffMap = Object.fromEntries(
     (staffList?.list ?? []).map((s: Staff) => [
@@ -69,6 +110,9 @@ function PayrollDetailPage() {
         <NavBar
           onBack={() => navigate({ to: "/payroll" })}
           backIcon={<ChevronLeft size={24} />}
+          right={
+            <Download size={20} onClick={handleDownloadPayslip} />
+          }
         >
           工资详情
         </NavBar>
@@ -80,6 +124,18 @@ function PayrollDetailPage() {
             <div className="text-4xl font-bold">¥{payroll.amount}</div>
           </div>
 
+          {/* 作废信息 */}
+          {payroll.voidedAt && (
+            <div className="bg-red-50 text-red-600 p-4 text-sm">
+              <div className="font-medium">
+                已作废 · <RelativeTime date={payroll.voidedAt} />
+              </div>
+              {payroll.voidReason && (
+                <div className="mt-1">{payroll.voidReason}</div>
+              )}
+            </div>
+          )}
+
           {/* 员工信息 */}
           <div className="bg-white mt-2 p-4">
             <div className="text-sm text-gray-500 mb-2">员工</div>
@@ -88,6 +144,46 @@ function PayrollDetailPage() {
             </div>
           </div>
 
+          {/* 员工确认 */}
+          {!payroll.voidedAt && (
+            <div className="bg-white mt-2 p-4">
+              <div className="flex justify-between items-center mb-2">
+                <span className="text-sm text-gray-500">员工确认</span>
+                <Tag
+                  color={PAYROLL_ACK_STATUS_MAP[payroll.ackStatus].color}
+                  fill="outline"
+                >
+                  {PAYROLL_ACK_STATUS_MAP[payroll.ackStatus].label}
+                </Tag>
+              </div>
+              {payroll.ackComment && (
+                <div className="text-gray-700">
+                  {payroll.ackComment}
+                  {payroll.ackedAt && (
+                    <span className="text-xs text-gray-400 ml-2">
+                      <RelativeTime date={payroll.ackedAt} />
+                    </span>
+                  )}
+                </div>
+              )}
+              {payroll.resolution && (
+                <div className="text-sm text-gray-500 mt-2">
+                  处理说明：{payroll.resolution}
+                </div>
+              )}
+              {payroll.ackStatus === "disputed" && (
+                <Button
+                  block
+                  color="primary"
+                  className="mt-3"
+                  onClick={() => setResolveVisible(true)}
+                >
+                  处理异议
+                </Button>
+              )}
+            </div>
+          )}
+
           {/* 发放时间 */}
           <div className="bg-white mt-2 p-4">
             <div className="text-sm text-gray-500 mb-2">发放时间</div>
@@ -96,6 +192,9 @@ function PayrollDetailPage() {
             </div>
           </div>
 
+          {/* 工资明细 */}
+          <PayrollItems items={payroll.items} />
+
           {/* 计件记录 */}
           {payroll.records.length > 0 && (
             <div className="bg-white mt-2 p-4">
@@ -174,6 +273,41 @@ function PayrollDetailPage() {
           )}
         </div>
 
+        {/* 处理异议 */}
+        <Popup
+          visible={resolveVisible}
+          onMaskClick={() => setResolveVisible(false)}
+          bodyStyle={{ borderTopLeftRadius: 16, borderTopRightRadius: 16 }}
+        >
+          <div className="p-4">
+            <div className="text-lg font-medium mb-4">处理异议</div>
+            <TextArea
+              value={resolution}
+              onChange={setResolution}
+              placeholder="如已补发差额、已核实无误等"
+              rows={3}
+              className="border border-gray-200 rounded-lg px-3 py-2"
+            />
+            <div className="flex gap-3 mt-4">
+              <Button
+                block
+                fill="outline"
+                onClick={() => setResolveVisible(false)}
+              >
+                取消
+              </Button>
+              <Button
+                block
+                color="primary"
+                onClick={handleResolve}
+                loading={resolveMutation.isPending}
+              >
+                提交
+              </Button>
+            </div>
+          </div>
+        </Popup>
+
         {/* 图片查看器 */}
         {payroll.paymentImage && (
           <ImageViewer
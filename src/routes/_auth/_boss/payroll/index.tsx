@@ -1,7 +1,25 @@
 import { createFileRoute, useNavigate } from "@tanstack/react-router";
-import { Dropdown, NavBar } from "antd-mobile";
-import { Plus, ImageIcon, Calendar, Users, ChevronLeft } from "lucide-react";
-import type { Payroll, Staff } from "@/types";
+import {
+  Button,
+  Checkbox,
+  Dialog,
+  Dropdown,
+  NavBar,
+  Picker,
+  Popup,
+  Tag,
+  Toast,
+} from "antd-mobile";
+import {
+  Plus,
+  ImageIcon,
+  Calendar,
+  Users,
+  ChevronLeft,
+  ListFilter,
+  ListChecks,
+} from "lucide-react";
+import type { ExportLayout, Payroll, PayrollPreviewItem, Staff } from "@/types";
 import {
   RelativeTime,
   VirtualList,
@@ -10,7 +28,15 @@ import {
   Image,
 } from "@/components";
 import { payrollApi } from "@/api";
-import { useRef } from "react";
+import {
+  EXPORT_LAYOUT_OPTIONS,
+  PAYROLL_ACK_STATUS_MAP,
+  PAYROLL_ACK_STATUS_OPTIONS,
+} from "@/constants";
+import { useRef, useState } from "react";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import dayjs from "dayjs";
+import { saveBlob } from "@/utils/download";
 import type { DropdownRef } from "antd-mobile/es/components/dropdown";
 import {
   useStaffList,
@@ -25,9 +51,14 @@ export const Route = createFileRoute("/_auth/_boss/payroll/")({
 
 function PayrollPage() {
   const navigate = useNavigate();
+  const queryClient = useQueryClient();
   const dropdownRef = useRef<DropdownRef>(null);
+  const [selecting, setSelecting] = useState(false);
+  const [selectedIds, setSelectedIds] = useState<string[]>([]);
+  const [previewVisible, setPreviewVisible] = useState(false);
 
   const userFilter = useToggleFilter<string>();
+  const statusFilter = useToggleFilter<string>();
 
   const {
     startDate,
@@ -46,6 +77,7 @@ function PayrollPage() {
       [
         "payrolls",
         userFilter.selected[0] ?? "",
+        statusFilter.selected[0] ?? "",
         startDate?.toISOString(),
         endDate?.toISOString(),
       ],
@@ -53,11 +85,29 @@ function PayrollPage() {
         payrollApi.list({
           ...params,
           userId: userFilter.selected[0] || undefined,
+          status: statusFilter.hasSelected ? statusFilter.selected : undefined,
           startDate: dateParams.startDate,
           endDate: dateParams.endDate,
         })
     );
 
+  // 待发工资：已批准未结算的计件和考勤，跟随员工和日期筛选
+  const { data: preview } = useQuery({
+    queryKey: [
+      "payroll-preview",
+      userFilter.selected[0] ?? "",
+      dateParams.startDate,
+      dateParams.endDate,
+    ],
+    queryFn: () =>
+      payrollApi.preview({
+        userId: userFilter.selected[0] || undefined,
+        startDate: dateParams.startDate,
+        endDate: dateParams.endDate,
+      }),
+  });
+  const pending = preview?.list ?? [];
+
   const staffOptions = (staffList?.list ?? []).map((s: Staff) => ({
     label: s.displayName || s.username,
     value: s.id,
@@ -70,7 +120,55 @@ function PayrollPage() {
     ])
   );
 
-  const hasFilters = userFilter.hasSelected || hasDateFilter;
+  const exitSelecting = () => {
+    setSelecting(false);
+    setSelectedIds([]);
+  };
+
+  const toggleSelected = (id: string) =>
+    setSelectedIds((ids) =>
+      ids.includes(id) ? ids.filter((v) => v !== id) : [...ids, id]
+    );
+
+  const exportMutation = useMutation({
+    mutationFn: (layout: ExportLayout) =>
+      payrollApi.exportTransfers({ payrollIds: selectedIds, layout }),
+    onSuccess: (blob) => {
+      saveBlob(blob, `转账-${dayjs().format("YYYYMMDD-HHmm")}.csv`);
+    },
+    onError: (e) => {
+      Toast.show({ content: e instanceof Error ? e.message : "导出失败" });
+    },
+  });
+
+  const markMutation = useMutation({
+    mutationFn: () => payrollApi.markTransferred(selectedIds),
+    onSuccess: (count) => {
+      Toast.show({ content: `已标记 ${count} 条为已转账` });
+      queryClient.invalidateQueries({ queryKey: ["payrolls"] });
+      exitSelecting();
+    },
+    onError: (e) => {
+      Toast.show({ content: e instanceof Error ? e.message : "操作失败" });
+    },
+  });
+
+  const handleExport = async () => {
+    const val = await Picker.prompt({
+      columns: [EXPORT_LAYOUT_OPTIONS.map((o) => ({ ...o }))],
+    });
+    if (val?.[0]) exportMutation.mutate(val[0] as ExportLayout);
+  };
+
+  const handleMarkTransferred = async () => {
+    const confirmed = await Dialog.confirm({
+      content: `确认已完成选中 ${selectedIds.length} 条工资的转账？`,
+    });
+    if (confirmed) markMutation.mutate();
+  };
+
+  const hasFilters =
+    userFilter.hasSelected || statusFilter.hasSelected || hasDateFilter;
 
   return (
     <BiometricGuard
@@ -82,13 +180,27 @@ function PayrollPage() {
           onBack={() => navigate({ to: "/profile" })}
           backIcon={<ChevronLeft size={24} />}
           right={
-            <div className="flex justify-end">
-              <Plus
-                size={20}
-                className="text-blue-500"
-                onClick={() => navigate({ to: "/payroll/new" })}
-              />
-            </div>
+            selecting ? (
+              <div
+                className="flex justify-end text-blue-500"
+                onClick={exitSelecting}
+              >
+                取消
+              </div>
+            ) : (
+              <div className="flex justify-end gap-4">
+                <ListChecks
+                  size={20}
+                  className="text-blue-500"
+                  onClick={() => setSelecting(true)}
+                />
+                <Plus
+                  size={20}
+                  className="text-blue-500"
+                  onClick={() => navigate({ to: "/payroll/new" })}
+                />
+              </div>
+            )
           }
         >
           工资管理
@@ -138,6 +250,44 @@ function PayrollPage() {
                 ))}
               </div>
             </Dropdown.Item>
+            <Dropdown.Item
+              key="status"
+              title={
+                <ListFilter
+                  size={16}
+                  className={
+                    statusFilter.hasSelected ? "text-blue-500" : "text-gray-500"
+                  }
+                />
+              }
+            >
+              <div className="p-2">
+                {PAYROLL_ACK_STATUS_OPTIONS.map((opt) => (
+                  <div
+                    key={opt.key}
+                    className={`p-3 rounded ${
+                      (
+                        opt.key
+                          ? statusFilter.isSelected(opt.key)
+                          : !statusFilter.hasSelected
+                      )
+                        ? "bg-blue-50 text-blue-500"
+                        : ""
+                    }`}
+                    onClick={() => {
+                      if (opt.key) {
+                        statusFilter.setSelected([opt.key]);
+                      } else {
+                        statusFilter.clear();
+                      }
+                      dropdownRef.current?.close();
+                    }}
+                  >
+                    {opt.title}
+                  </div>
+                ))}
+              </div>
+            </Dropdown.Item>
             <Dropdown.Item
               key="date"
               title={
@@ -151,6 +301,21 @@ function PayrollPage() {
           </Dropdown>
         </div>
 
+        {/* 待发工资 */}
+        {pending.length > 0 && (
+          <div
+            className="bg-orange-50 mx-2 mb-2 p-3 rounded-lg flex justify-between items-center cursor-pointer active:bg-orange-100"
+            onClick={() => setPreviewVisible(true)}
+          >
+            <span className="text-sm text-gray-600">
+              待发工资 · {pending.length} 人
+            </span>
+            <span className="font-medium text-orange-500">
+              ¥{preview?.totalAmount}
+            </span>
+          </div>
+        )}
+
         {/* 列表 */}
         <div className="flex flex-1 overflow-hidden">
           <VirtualList
@@ -166,10 +331,22 @@ function PayrollPage() {
             renderItem={(payroll) => (
               <div
                 className="bg-white p-3 mb-2 mx-2 rounded-lg shadow-sm flex gap-3 cursor-pointer active:bg-gray-50"
-                onClick={() =>
-                  navigate({ to: "/payroll/$id", params: { id: payroll.id } })
-                }
+                onClick={() => {
+                  if (!selecting) {
+                    navigate({ to: "/payroll/$id", params: { id: payroll.id } });
+                  } else if (!payroll.voidedAt) {
+                    toggleSelected(payroll.id);
+                  }
+                }}
               >
+                {selecting && (
+                  <div className="shrink-0 flex items-center">
+                    <Checkbox
+                      checked={selectedIds.includes(payroll.id)}
+                      disabled={!!payroll.voidedAt}
+                    />
+                  </div>
+                )}
                 <div className="shrink-0 h-16 aspect-square rounded-lg overflow-hidden bg-gray-100 flex items-center justify-center">
                   {payroll.paymentImage ? (
                     <Image
@@ -196,8 +373,25 @@ function PayrollPage() {
                       {payroll.note}
                     </div>
                   )}
-                  <div className="text-xs text-gray-400 mt-0.5">
-                    <RelativeTime date={payroll.paidAt} />
+                  <div className="flex justify-between items-center text-xs text-gray-400 mt-0.5">
+                    <span>
+                      <RelativeTime date={payroll.paidAt} />
+                      {payroll.transferredAt && (
+                        <span className="ml-2 text-green-500">已转账</span>
+                      )}
+                    </span>
+                    {payroll.voidedAt ? (
+                      <Tag color="#999" fill="outline">
+                        已作废
+                      </Tag>
+                    ) : (
+                      <Tag
+                        color={PAYROLL_ACK_STATUS_MAP[payroll.ackStatus].color}
+                        fill="outline"
+                      >
+                        {PAYROLL_ACK_STATUS_MAP[payroll.ackStatus].label}
+                      </Tag>
+                    )}
                   </div>
                 </div>
               </div>
@@ -205,6 +399,56 @@ function PayrollPage() {
           />
         </div>
 
+        {/* 批量操作 */}
+        {selecting && (
+          <div className="flex gap-2 p-2 bg-white border-t">
+            <Button
+              className="flex-1"
+              disabled={selectedIds.length === 0}
+              loading={exportMutation.isPending}
+              onClick={handleExport}
+            >
+              导出转账文件
+            </Button>
+            <Button
+              className="flex-1"
+              color="primary"
+              disabled={selectedIds.length === 0}
+              loading={markMutation.isPending}
+              onClick={handleMarkTransferred}
+            >
+              标记已转账 ({selectedIds.length})
+            </Button>
+          </div>
+        )}
+
+        <Popup
+          visible={previewVisible}
+          onMaskClick={() => setPreviewVisible(false)}
+          bodyStyle={{
+            borderTopLeftRadius: 12,
+            borderTopRightRadius: 12,
+            maxHeight: "70vh",
+            overflowY: "auto",
+          }}
+        >
+          <div className="p-4">
+            <div className="font-medium mb-3">待发工资</div>
+            {pending.map((item) => (
+              <PreviewCard
+                key={item.userId}
+                item={item}
+                onSettle={() =>
+                  navigate({
+                    to: "/payroll/new",
+                    search: { userId: item.userId },
+                  })
+                }
+              />
+            ))}
+          </div>
+        </Popup>
+
         {/* 日期选择器 */}
         <DateRangeButton
           startDate={startDate}
@@ -224,3 +468,55 @@ function PayrollPage() {
     </BiometricGuard>
   );
 }
+
+function PreviewCard({
+  item,
+  onSettle,
+}: {
+  item: PayrollPreviewItem;
+  onSettle: () => void;
+}) {
+  const repayment = parseFloat(item.suggestedRepayment);
+  return (
+    <div className="bg-gray-50 rounded-lg p-3 mb-2">
+      <div className="flex justify-between items-center mb-2">
+        <span className="font-medium">{item.userName}</span>
+        <Button size="mini" color="primary" fill="outline" onClick={onSettle}>
+          去结算
+        </Button>
+      </div>
+      {item.groups.map((g) => (
+        <div
+          key={`${g.processId}-${g.piecePrice ?? ""}`}
+          className="flex justify-between text-sm text-gray-600"
+        >
+          <span className="truncate">
+            {g.orderName || "订单"} · {g.processName}
+            {g.piecePrice && ` ¥${g.piecePrice}`} × {g.quantity}
+          </span>
+          <span>¥{g.amount}</span>
+        </div>
+      ))}
+      {item.attendanceIds.length > 0 && (
+        <div className="flex justify-between text-sm text-gray-600">
+          <span>
+            考勤 {item.attendanceIds.length} 条 · {item.hours} 小时
+          </span>
+          <span>¥{item.timeAmount}</span>
+        </div>
+      )}
+      {repayment > 0 && (
+        <div className="flex justify-between text-sm text-gray-600">
+          <span>扣回借支（未还 ¥{item.outstandingAdvance}）</span>
+          <span>-¥{item.suggestedRepayment}</span>
+        </div>
+      )}
+      <div className="flex justify-between mt-1 pt-1 border-t border-gray-200">
+        <span className="text-sm text-gray-500">建议实发</span>
+        <span className="font-medium text-orange-500">
+          ¥{item.suggestedAmount}
+        </span>
+      </div>
+    </div>
+  );
+}
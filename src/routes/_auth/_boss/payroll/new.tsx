@@ -10,24 +10,32 @@ import {
 } from "antd-mobile";
 import { ChevronLeft, ImageIcon } from "lucide-react";
 import type { Staff, PieceRecord } from "@/types";
-import { payrollApi, pieceRecordApi } from "@/api";
+import { advanceApi, attendanceApi, payrollApi, pieceRecordApi } from "@/api";
 import { useQueryClient, useMutation, useQuery } from "@tanstack/react-query";
 import { useState, useMemo } from "react";
 import { useStaffList } from "@/hooks";
 import { ImageUploader, OssImage } from "@/components";
 
 export const Route = createFileRoute("/_auth/_boss/payroll/new")({
+  validateSearch: (search: Record<string, unknown>): { userId?: string } => ({
+    userId: (search.userId as string) || undefined,
+  }),
   component: NewPayrollPage,
 });
 
 function NewPayrollPage() {
   const navigate = useNavigate();
   const queryClient = useQueryClient();
+  const search = Route.useSearch();
 
-  const [userId, setUserId] = useState("");
+  const [userId, setUserId] = useState(search.userId ?? "");
   const [selectedRecordIds, setSelectedRecordIds] = useState<string[]>([]);
+  const [selectedAttendanceIds, setSelectedAttendanceIds] = useState<
+    string[]
+  >([]);
   const [paymentImage, setPaymentImage] = useState("");
   const [note, setNote] = useState("");
+  const [repayAdvance, setRepayAdvance] = useState(true);
 
   const { data: staffList } = useStaffList();
 
@@ -45,12 +53,32 @@ function NewPayrollPage() {
 
   const records = recordsData?.list ?? [];
 
+  // 未结算且已下班的考勤
+  const { data: attendanceData } = useQuery({
+    queryKey: ["attendance-unsettled", userId],
+    queryFn: () => attendanceApi.list({ userId, pageSize: 1000 }),
+    enabled: !!userId,
+  });
+  const attendances = (attendanceData?.list ?? []).filter(
+    (a) => !a.payrollId && !(a.clockIn && !a.clockOut)
+  );
+
+  const { data: advanceBalances } = useQuery({
+    queryKey: ["advance-balances"],
+    queryFn: advanceApi.balances,
+  });
+  const outstanding = parseFloat(
+    advanceBalances?.find((b) => b.userId === userId)?.outstanding ?? "0"
+  );
+
   const createMutation = useMutation({
     mutationFn: payrollApi.create,
-    onSuccess: () => {
-      Toast.show({ content: "创建成功" });
+    onSuccess: (payroll) => {
+      Toast.show({ content: `创建成功，实发 ¥${payroll.amount}` });
       queryClient.invalidateQueries({ queryKey: ["payrolls"] });
       queryClient.invalidateQueries({ queryKey: ["piece-records"] });
+      queryClient.invalidateQueries({ queryKey: ["attendance-unsettled"] });
+      queryClient.invalidateQueries({ queryKey: ["payroll-preview"] });
       navigate({ to: "/payroll" });
     },
     onError: (err: Error) => {
@@ -66,21 +94,40 @@ function NewPayrollPage() {
   const selectedStaff = staffOptions.find((o) => o.value === userId);
 
   // Calculate total amount from selected records
-  const totalAmount = useMemo(() => {
+  const pieceAmount = useMemo(() => {
     return records
       .filter((r) => selectedRecordIds.includes(r.id))
-      .reduce((sum, r) => sum + parseFloat(r.amount), 0)
-      .toFixed(2);
+      .reduce((sum, r) => sum + parseFloat(r.amount), 0);
   }, [records, selectedRecordIds]);
 
+  const timeAmount = useMemo(() => {
+    return attendances
+      .filter((a) => selectedAttendanceIds.includes(a.id))
+      .reduce((sum, a) => sum + parseFloat(a.amount), 0);
+  }, [attendances, selectedAttendanceIds]);
+
+  // 有未还借支时建议从本次工资中扣回，实际金额由服务端按重新计价后的应发金额确定
+  const repayment = Math.min(outstanding, pieceAmount + timeAmount);
+  const deducted = repayAdvance && repayment > 0 ? repayment : 0;
+  const totalAmount = (pieceAmount + timeAmount - deducted).toFixed(2);
+
   const handleSelectStaff = async () => {
     const val = await Picker.prompt({ columns: [staffOptions] });
     if (val) {
       setUserId(val[0] as string);
       setSelectedRecordIds([]);
+      setSelectedAttendanceIds([]);
     }
   };
 
+  const handleToggleAttendance = (attendanceId: string) => {
+    setSelectedAttendanceIds((prev) =>
+      prev.includes(attendanceId)
+        ? prev.filter((id) => id !== attendanceId)
+        : [...prev, attendanceId]
+    );
+  };
+
   const handleToggleRecord = (recordId: string) => {
     setSelectedRecordIds((prev) =>
       prev.includes(recordId)
@@ -102,14 +149,15 @@ function NewPayrollPage() {
       Toast.show({ content: "请选择员工" });
       return;
     }
-    if (selectedRecordIds.length === 0) {
-      Toast.show({ content: "请选择要结算的计件记录" });
+    if (selectedRecordIds.length === 0 && selectedAttendanceIds.length === 0) {
+      Toast.show({ content: "请选择要结算的计件或考勤记录" });
       return;
     }
     createMutation.mutate({
       userId,
-      amount: totalAmount,
       recordIds: selectedRecordIds,
+      attendanceIds: selectedAttendanceIds,
+      repayAdvance: deducted > 0,
       paymentImage: paymentImage || undefined,
       note: note || undefined,
     });
@@ -198,6 +246,45 @@ function NewPayrollPage() {
               )}
             </div>
 
+            {/* Attendance selection */}
+            {attendances.length > 0 && (
+              <div className="bg-white rounded-lg mt-3 p-3">
+                <div className="font-medium mb-3">
+                  待结算考勤 ({attendances.length})
+                </div>
+                <div className="space-y-2">
+                  {attendances.map((a) => (
+                    <div
+                      key={a.id}
+                      className="flex items-center gap-3 p-2 bg-gray-50 rounded-lg"
+                      onClick={() => handleToggleAttendance(a.id)}
+                    >
+                      <Checkbox checked={selectedAttendanceIds.includes(a.id)} />
+                      <div className="flex-1 min-w-0">
+                        <div className="text-sm">{a.workDate}</div>
+                        <div className="text-xs text-gray-500">
+                          {a.hours} 小时 · {a.days} 天
+                        </div>
+                      </div>
+                      <div className="text-orange-500 font-medium">
+                        ¥{a.amount}
+                      </div>
+                    </div>
+                  ))}
+                </div>
+              </div>
+            )}
+
+            {/* Advance repayment */}
+            {repayment > 0 && (
+              <div className="bg-white rounded-lg mt-3 p-4 flex justify-between items-center">
+                <Checkbox checked={repayAdvance} onChange={setRepayAdvance}>
+                  扣回借支（未还 ¥{outstanding.toFixed(2)}）
+                </Checkbox>
+                <span className="text-gray-600">约 -¥{repayment.toFixed(2)}</span>
+              </div>
+            )}
+
             {/* Total amount */}
             <div className="bg-white rounded-lg mt-3 p-4 flex justify-between items-center">
               <span className="text-gray-600">结算金额</span>
@@ -237,7 +324,11 @@ function NewPayrollPage() {
           color="primary"
           onClick={handleSubmit}
           loading={createMutation.isPending}
-          disabled={!userId || selectedRecordIds.length === 0}
+          disabled={
+            !userId ||
+            (selectedRecordIds.length === 0 &&
+              selectedAttendanceIds.length === 0)
+          }
         >
           确认发放 ¥{totalAmount}
         </Button>
@@ -8,6 +8,8 @@ import {
   Swiper,
   Input,
   Popup,
+  Selector,
+  TextArea,
 } from "antd-mobile";
 import { ImageIcon, Edit2, Trash2, ChevronLeft } from "lucide-react";
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
@@ -28,6 +30,9 @@ function RecordDetailPage() {
   const { pieceUnit } = useWorkshopSettings();
   const [editPopupVisible, setEditPopupVisible] = useState(false);
   const [editQuantity, setEditQuantity] = useState("");
+  const [rejectPopupVisible, setRejectPopupVisible] = useState(false);
+  const [rejectPreset, setRejectPreset] = useState<string>();
+  const [rejectReason, setRejectReason] = useState("");
 
   const { data: record, isLoading } = useQuery({
     queryKey: ["piece-record", id],
@@ -50,10 +55,22 @@ function RecordDetailPage() {
     },
   });
 
+  const { data: rejectReasons } = useQuery({
+    queryKey: ["piece-record-reject-reasons"],
+    queryFn: () => pieceRecordApi.rejectReasons(),
+    enabled: rejectPopupVisible,
+    staleTime: Infinity,
+  });
+
   const rejectMutation = useMutation({
-    mutationFn: () => pieceRecordApi.reject(id),
+    mutationFn: () =>
+      pieceRecordApi.reject(id, {
+        preset: rejectPreset,
+        reason: rejectReason.trim() || undefined,
+      }),
     onSuccess: () => {
       Toast.show({ content: "已拒绝" });
+      setRejectPopupVisible(false);
       queryClient.invalidateQueries({ queryKey: ["piece-record", id] });
       queryClient.invalidateQueries({ queryKey: ["piece-records"] });
     },
@@ -88,12 +105,9 @@ function RecordDetailPage() {
   };
 
   const handleReject = () => {
-    Dialog.confirm({
-      content: "确定拒绝该计件记录？",
-      confirmText: "拒绝",
-      cancelText: "取消",
-      onConfirm: () => rejectMutation.mutate(),
-    });
+    setRejectPreset(undefined);
+    setRejectReason("");
+    setRejectPopupVisible(true);
   };
 
   const handleEdit = () => {
@@ -284,6 +298,50 @@ function RecordDetailPage() {
         </div>
       )}
 
+      {/* 驳回弹窗 */}
+      <Popup
+        visible={rejectPopupVisible}
+        onMaskClick={() => setRejectPopupVisible(false)}
+        bodyStyle={{ borderTopLeftRadius: 16, borderTopRightRadius: 16 }}
+      >
+        <div className="p-4">
+          <div className="text-lg font-medium mb-4">拒绝计件</div>
+          {rejectReasons && rejectReasons.length > 0 && (
+            <Selector
+              options={rejectReasons.map((r) => ({ label: r, value: r }))}
+              value={rejectPreset ? [rejectPreset] : []}
+              onChange={(v) => setRejectPreset(v[0])}
+              style={{ "--padding": "6px 12px" }}
+            />
+          )}
+          <TextArea
+            value={rejectReason}
+            onChange={setRejectReason}
+            placeholder="补充说明（选填）"
+            rows={3}
+            maxLength={200}
+            className="border border-gray-200 rounded-lg px-3 py-2 mt-3"
+          />
+          <div className="flex gap-3 mt-4">
+            <Button
+              block
+              fill="outline"
+              onClick={() => setRejectPopupVisible(false)}
+            >
+              取消
+            </Button>
+            <Button
+              block
+              color="danger"
+              onClick={() => rejectMutation.mutate()}
+              loading={rejectMutation.isPending}
+            >
+              拒绝
+            </Button>
+          </div>
+        </div>
+      </Popup>
+
       {/* 编辑弹窗 */}
       <Popup
         visible={editPopupVisible}
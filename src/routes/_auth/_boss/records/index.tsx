@@ -99,7 +99,7 @@ function RecordsPage() {
   });
 
   const rejectMutation = useMutation({
-    mutationFn: pieceRecordApi.reject,
+    mutationFn: (id: string) => pieceRecordApi.reject(id),
     onSuccess: () => {
       Toast.show({ content: "已拒绝" });
       queryClient.invalidateQueries({ queryKey: ["piece-records"] });
@@ -7,6 +7,8 @@ import {
   SwipeAction,
   NavBar,
   Button,
+  Popup,
+  Input,
 } from "antd-mobile";
 import { Search, UserPlus, X, ChevronLeft, Copy } from "lucide-react";
 import { Avatar, VirtualList, RelativeTime } from "@/components";
@@ -15,6 +17,7 @@ import { useState } from "react";
 import { copyToClipboard } from "@/utils/clipboard";
 import { QRCodeSVG } from "qrcode.react";
 import { useInfiniteList, useDebouncedSearch } from "@/hooks";
+import type { Staff } from "@/types";
 import { motion, AnimatePresence } from "motion/react";
 
 export const Route = createFileRoute("/_auth/_boss/staff/")({
@@ -24,6 +27,10 @@ export const Route = createFileRoute("/_auth/_boss/staff/")({
 function StaffPage() {
   const navigate = useNavigate();
   const [showSearch, setShowSearch] = useState(false);
+  const [rateStaff, setRateStaff] = useState<Staff | null>(null);
+  const [hourlyRate, setHourlyRate] = useState("");
+  const [dailyRate, setDailyRate] = useState("");
+  const [savingRates, setSavingRates] = useState(false);
 
   // 搜索
   const { search, debouncedSearch, setSearch, searchInputRef } =
@@ -96,6 +103,32 @@ function StaffPage() {
     });
   };
 
+  const handleEditRates = (staff: Staff) => {
+    setRateStaff(staff);
+    setHourlyRate(staff.hourlyRate ?? "");
+    setDailyRate(staff.dailyRate ?? "");
+  };
+
+  const handleSaveRates = async () => {
+    if (!rateStaff) return;
+    setSavingRates(true);
+    try {
+      await authApi.updateStaffWageRates(rateStaff.id, {
+        hourlyRate: hourlyRate.trim() || undefined,
+        dailyRate: dailyRate.trim() || undefined,
+      });
+      Toast.show({ content: "已保存" });
+      setRateStaff(null);
+      refresh();
+    } catch (e) {
+      Toast.show({
+        content: e instanceof Error ? e.message : "保存失败",
+      });
+    } finally {
+      setSavingRates(false);
+    }
+  };
+
   return (
     <div className="flex flex-col h-full overflow-hidden">
       {/* 顶部栏 */}
@@ -187,6 +220,12 @@ function StaffPage() {
           renderItem={(staff) => (
             <SwipeAction
               rightActions={[
+                {
+                  key: "rates",
+                  text: "计时工资",
+                  color: "primary",
+                  onClick: () => handleEditRates(staff),
+                },
                 {
                   key: "remove",
                   text: "移除",
@@ -210,7 +249,13 @@ function StaffPage() {
                     />
                   </div>
                 }
-                description={staff.username}
+                description={
+                  staff.hourlyRate
+                    ? `${staff.username} · 时薪 ¥${staff.hourlyRate}`
+                    : staff.dailyRate
+                      ? `${staff.username} · 日薪 ¥${staff.dailyRate}`
+                      : staff.username
+                }
                 extra={
                   staff.createdAt && (
                     <span className="text-xs text-gray-400">
@@ -227,6 +272,49 @@ function StaffPage() {
           )}
         />
       </div>
+
+      {/* 计时工资标准 */}
+      <Popup
+        visible={!!rateStaff}
+        onMaskClick={() => setRateStaff(null)}
+        bodyStyle={{ borderTopLeftRadius: 16, borderTopRightRadius: 16 }}
+      >
+        <div className="p-4">
+          <div className="text-lg font-medium mb-1">计时工资标准</div>
+          <div className="text-xs text-gray-400 mb-4">
+            设置时薪按考勤工时计算，否则按日薪和出勤天数计算，都不填则只计件
+          </div>
+          <div className="text-sm text-gray-500 mb-1">时薪（元/小时）</div>
+          <Input
+            type="number"
+            value={hourlyRate}
+            onChange={setHourlyRate}
+            placeholder="不按小时计薪可不填"
+            className="border border-gray-200 rounded-lg px-3 py-2"
+          />
+          <div className="text-sm text-gray-500 mt-3 mb-1">日薪（元/天）</div>
+          <Input
+            type="number"
+            value={dailyRate}
+            onChange={setDailyRate}
+            placeholder="不按天计薪可不填"
+            className="border border-gray-200 rounded-lg px-3 py-2"
+          />
+          <div className="flex gap-3 mt-4">
+            <Button block fill="outline" onClick={() => setRateStaff(null)}>
+              取消
+            </Button>
+            <Button
+              block
+              color="primary"
+              onClick={handleSaveRates}
+              loading={savingRates}
+            >
+              保存
+            </Button>
+          </div>
+        </div>
+      </Popup>
     </div>
   );
 }
@@ -1,11 +1,19 @@
 import { useNavigate } from "@tanstack/react-router";
-import { Card, PullToRefresh } from "antd-mobile";
-import { FileEdit, Store, TrendingUp, Package, ImageIcon } from "lucide-react";
-import { useQuery, useQueryClient } from "@tanstack/react-query";
-import { homeApi, statsApi } from "@/api";
+import { Button, Card, PullToRefresh, Toast } from "antd-mobile";
+import {
+  FileEdit,
+  Store,
+  TrendingUp,
+  Package,
+  ImageIcon,
+  Clock,
+  Wallet,
+} from "lucide-react";
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { attendanceApi, homeApi, statsApi } from "@/api";
 import { useAuthStore } from "@/stores/auth";
 import { MiniChart, RelativeTime, Chart, OssImage, Image } from "@/components";
-import { useWorkshopSettings } from "@/hooks";
+import { useWorkshopSettings, useMyOutstandingAdvance } from "@/hooks";
 import type { StaffOverview, Activity } from "@/types";
 import type { EChartsOption } from "echarts";
 import dayjs from "dayjs";
@@ -16,14 +24,35 @@ export function StaffHome() {
   const queryClient = useQueryClient();
   const user = useAuthStore((s) => s.user);
   const { pieceUnit } = useWorkshopSettings();
+  const outstanding = useMyOutstandingAdvance(!!user?.workshop);
 
   const handleRefresh = async () => {
     await queryClient.invalidateQueries({ queryKey: ["home-overview"] });
     await queryClient.invalidateQueries({ queryKey: ["home-activities"] });
     await queryClient.invalidateQueries({ queryKey: ["daily-stats-mini"] });
     await queryClient.invalidateQueries({ queryKey: ["staff-stats-by-order"] });
+    await queryClient.invalidateQueries({ queryKey: ["attendance-today"] });
+    await queryClient.invalidateQueries({ queryKey: ["advance-balances"] });
   };
 
+  const today = dayjs().format("YYYY-MM-DD");
+  const { data: todayAttendance } = useQuery({
+    queryKey: ["attendance-today", today],
+    queryFn: () =>
+      attendanceApi.list({ startDate: today, endDate: today, pageSize: 1 }),
+    enabled: !!user?.workshop,
+  });
+  const attendance = todayAttendance?.list[0];
+
+  const clockMutation = useMutation({
+    mutationFn: () =>
+      attendance ? attendanceApi.clockOut() : attendanceApi.clockIn(),
+    onSuccess: () => {
+      Toast.show({ content: attendance ? "下班打卡成功" : "上班打卡成功" });
+      queryClient.invalidateQueries({ queryKey: ["attendance-today"] });
+    },
+  });
+
   const { data: overview } = useQuery({
     queryKey: ["home-overview"],
     queryFn: homeApi.overview,
@@ -116,6 +145,38 @@ export function StaffHome() {
           </div>
         )}
 
+        {/* 考勤打卡 */}
+        {user?.workshop && (
+          <Card className="mt-4">
+            <div className="flex items-center justify-between">
+              <div>
+                <div className="flex items-center gap-2 text-sm text-gray-500">
+                  <Clock size={14} />
+                  今日考勤
+                </div>
+                <div className="text-sm mt-1">
+                  {!attendance && "未打卡"}
+                  {attendance?.clockIn &&
+                    `上班 ${dayjs(attendance.clockIn).format("HH:mm")}`}
+                  {attendance?.clockOut &&
+                    ` · 下班 ${dayjs(attendance.clockOut).format("HH:mm")}`}
+                  {attendance && !attendance.clockIn && `已记 ${attendance.hours} 小时`}
+                </div>
+              </div>
+              {(!attendance || (attendance.clockIn && !attendance.clockOut)) && (
+                <Button
+                  size="small"
+                  color="primary"
+                  loading={clockMutation.isPending}
+                  onClick={() => clockMutation.mutate()}
+                >
+                  {attendance ? "下班打卡" : "上班打卡"}
+                </Button>
+              )}
+            </div>
+          </Card>
+        )}
+
         {/* 本月数据卡片 */}
         <div className="mt-4 grid grid-cols-3 gap-3">
           <Card className="p-3!">
@@ -141,6 +202,24 @@ export function StaffHome() {
           </Card>
         </div>
 
+        {/* 未还借支 */}
+        {outstanding > 0 && (
+          <Card
+            className="mt-4 cursor-pointer"
+            onClick={() => navigate({ to: "/my-payrolls" })}
+          >
+            <div className="flex items-center justify-between">
+              <div className="flex items-center gap-2 text-sm text-gray-500">
+                <Wallet size={14} />
+                未还借支
+              </div>
+              <span className="text-lg font-bold text-red-500">
+                ¥{outstanding.toFixed(2)}
+              </span>
+            </div>
+          </Card>
+        )}
+
         {/* 近7日趋势 */}
         {chartData.length > 0 && (
           <Card className="mt-4">
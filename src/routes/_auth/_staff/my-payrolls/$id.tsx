@@ -1,9 +1,21 @@
 import { createFileRoute, useNavigate } from "@tanstack/react-router";
-import { NavBar, ImageViewer } from "antd-mobile";
-import { ChevronLeft, ImageIcon } from "lucide-react";
-import { useQuery } from "@tanstack/react-query";
+import {
+  NavBar,
+  ImageViewer,
+  Toast,
+  Tag,
+  Button,
+  Dialog,
+  Popup,
+  TextArea,
+} from "antd-mobile";
+import { ChevronLeft, Download, ImageIcon } from "lucide-react";
+import { saveBlob } from "@/utils/download";
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { payrollApi, getFileUrl } from "@/api";
-import { Image, RelativeTime, BiometricGuard } from "@/components";
+import { Image, RelativeTime, BiometricGuard, PayrollItems } from "@/components";
+import { PAYROLL_ACK_STATUS_MAP } from "@/constants";
+import { useMyOutstandingAdvance } from "@/hooks";
 import { useState } from "react";
 
 export const Route = createFileRoute("/_auth/_staff/my-payrolls/$id")({
@@ -13,12 +25,65 @@ export const Route = createFileRoute("/_auth/_staff/my-payrolls/$id")({
 function MyPayrollDetailPage() {
   const { id } = Route.useParams();
   const navigate = useNavigate();
+  const queryClient = useQueryClient();
   const [imageViewerVisible, setImageViewerVisible] = useState(false);
+  const [disputeVisible, setDisputeVisible] = useState(false);
+  const [comment, setComment] = useState("");
 
   const { data: payroll, isLoading } = useQuery({
     queryKey: ["my-payroll", id],
     queryFn: () => payrollApi.getOne(id),
   });
+  const outstanding = useMyOutstandingAdvance();
+
+  const onAckSuccess = () => {
+    queryClient.invalidateQueries({ queryKey: ["my-payroll", id] });
+    queryClient.invalidateQueries({ queryKey: ["my-payrolls"] });
+  };
+
+  const acknowledgeMutation = useMutation({
+    mutationFn: () => payrollApi.acknowledge(id, {}),
+    onSuccess: () => {
+      Toast.show({ content: "已确认" });
+      onAckSuccess();
+    },
+  });
+
+  const disputeMutation = useMutation({
+    mutationFn: () => payrollApi.dispute(id, { comment: comment.trim() }),
+    onSuccess: () => {
+      Toast.show({ content: "已提交，等待老板处理" });
+      setDisputeVisible(false);
+      setComment("");
+      onAckSuccess();
+    },
+  });
+
+  const handleAcknowledge = () => {
+    Dialog.confirm({
+      content: "确认工资金额无误？",
+      confirmText: "确认",
+      cancelText: "取消",
+      onConfirm: () => acknowledgeMutation.mutate(),
+    });
+  };
+
+  const handleDispute = () => {
+    if (!comment.trim()) {
+      Toast.show({ content: "请填写异议说明" });
+      return;
+    }
+    disputeMutation.mutate();
+  };
+
+  const handleDownloadPayslip = async () => {
+    try {
+      const blob = await payrollApi.payslip(id);
+      saveBlob(blob, `工资条-${id.slice(0, 8)}.pdf`);
+    } catch (err) {
+      Toast.show({ content: (err as Error).message || "下载失败" });
+    }
+  };
 
   if (isLoading) {
     return (
@@ -50,6 +115,10 @@ function MyPayrollDetailPage() {
     );
   }
 
+  const repayments = payroll.items.filter(
+    (item) => item.kind === "advanceRepayment"
+  );
+
   return (
     <BiometricGuard
       reason="查看工资详情需要验证身份"
@@ -59,6 +128,9 @@ function MyPayrollDetailPage() {
         <NavBar
           onBack={() => navigate({ to: "/my-payrolls" })}
           backIcon={<ChevronLeft size={24} />}
+          right={
+            <Download size={20} onClick={handleDownloadPayslip} />
+          }
         >
           工资详情
         </NavBar>
@@ -70,6 +142,74 @@ function MyPayrollDetailPage() {
             <div className="text-4xl font-bold">+¥{payroll.amount}</div>
           </div>
 
+          {/* 借支 */}
+          {(repayments.length > 0 || outstanding > 0) && (
+            <div className="bg-white mt-2 p-4">
+              <div className="text-sm text-gray-500 mb-2">借支</div>
+              {repayments.map((item) => (
+                <div key={item.id} className="flex justify-between text-sm mb-1">
+                  <span>
+                    本单扣回
+                    {item.note && (
+                      <span className="text-gray-400 ml-2">{item.note}</span>
+                    )}
+                  </span>
+                  <span className="text-red-500">
+                    -¥{Math.abs(parseFloat(item.amount)).toFixed(2)}
+                  </span>
+                </div>
+              ))}
+              <div className="flex justify-between text-sm">
+                <span>当前未还</span>
+                <span className="font-medium">¥{outstanding.toFixed(2)}</span>
+              </div>
+            </div>
+          )}
+
+          {/* 确认状态 */}
+          {!payroll.voidedAt && (
+            <div className="bg-white mt-2 p-4">
+              <div className="flex justify-between items-center mb-2">
+                <span className="text-sm text-gray-500">确认状态</span>
+                <Tag
+                  color={PAYROLL_ACK_STATUS_MAP[payroll.ackStatus].color}
+                  fill="outline"
+                >
+                  {PAYROLL_ACK_STATUS_MAP[payroll.ackStatus].label}
+                </Tag>
+              </div>
+              {payroll.ackComment && (
+                <div className="text-gray-700">{payroll.ackComment}</div>
+              )}
+              {payroll.resolution && (
+                <div className="text-sm text-gray-500 mt-2">
+                  老板处理：{payroll.resolution}
+                </div>
+              )}
+              {(payroll.ackStatus === "pending" ||
+                payroll.ackStatus === "resolved") && (
+                <div className="flex gap-3 mt-3">
+                  <Button
+                    block
+                    fill="outline"
+                    color="danger"
+                    onClick={() => setDisputeVisible(true)}
+                  >
+                    有异议
+                  </Button>
+                  <Button
+                    block
+                    color="primary"
+                    onClick={handleAcknowledge}
+                    loading={acknowledgeMutation.isPending}
+                  >
+                    确认无误
+                  </Button>
+                </div>
+              )}
+            </div>
+          )}
+
           {/* 发放时间 */}
           <div className="bg-white mt-2 p-4">
             <div className="text-sm text-gray-500 mb-2">发放时间</div>
@@ -78,6 +218,9 @@ function MyPayrollDetailPage() {
             </div>
           </div>
 
+          {/* 工资明细 */}
+          <PayrollItems items={payroll.items} />
+
           {/* 计件记录 */}
           {payroll.records.length > 0 && (
             <div className="bg-white mt-2 p-4">
@@ -156,6 +299,41 @@ function MyPayrollDetailPage() {
           )}
         </div>
 
+        {/* 提出异议 */}
+        <Popup
+          visible={disputeVisible}
+          onMaskClick={() => setDisputeVisible(false)}
+          bodyStyle={{ borderTopLeftRadius: 16, borderTopRightRadius: 16 }}
+        >
+          <div className="p-4">
+            <div className="text-lg font-medium mb-4">提出异议</div>
+            <TextArea
+              value={comment}
+              onChange={setComment}
+              placeholder="请说明哪里有问题，如少算的计件"
+              rows={3}
+              className="border border-gray-200 rounded-lg px-3 py-2"
+            />
+            <div className="flex gap-3 mt-4">
+              <Button
+                block
+                fill="outline"
+                onClick={() => setDisputeVisible(false)}
+              >
+                取消
+              </Button>
+              <Button
+                block
+                color="primary"
+                onClick={handleDispute}
+                loading={disputeMutation.isPending}
+              >
+                提交
+              </Button>
+            </div>
+          </div>
+        </Popup>
+
         {/* 图片查看器 */}
         {payroll.paymentImage && (
           <ImageViewer
@@ -12,7 +12,11 @@ import {
 import { payrollApi } from "@/api";
 import { useRef } from "react";
 import type { DropdownRef } from "antd-mobile/es/components/dropdown";
-import { useInfiniteList, useDateRange } from "@/hooks";
+import {
+  useInfiniteList,
+  useDateRange,
+  useMyOutstandingAdvance,
+} from "@/hooks";
 
 export const Route = createFileRoute("/_auth/_staff/my-payrolls/")({
   component: MyPayrollsPage,
@@ -43,6 +47,8 @@ function MyPayrollsPage() {
         })
     );
 
+  const outstanding = useMyOutstandingAdvance();
+
   // 计算总金额
   const totalAmount = list.reduce(
     (sum, p) => sum + parseFloat(p.amount || "0"),
@@ -85,7 +91,12 @@ function MyPayrollsPage() {
           <div className="bg-linear-to-r from-green-500 to-green-600 rounded-lg p-4 text-white">
             <div className="text-sm opacity-80 mb-1">累计收入</div>
             <div className="text-3xl font-bold">¥{totalAmount.toFixed(2)}</div>
-            <div className="text-xs opacity-70 mt-1">共 {list.length} 笔</div>
+            <div className="flex justify-between text-xs opacity-70 mt-1">
+              <span>共 {list.length} 笔</span>
+              {outstanding > 0 && (
+                <span>未还借支 ¥{outstanding.toFixed(2)}</span>
+              )}
+            </div>
           </div>
         </div>
 
@@ -6,7 +6,7 @@ import { pieceRecordApi, orderApi, processApi } from "@/api";
 import { useQueryClient, useMutation, useQuery } from "@tanstack/react-query";
 import { useState } from "react";
 import { useAuthStore } from "@/stores/auth";
-import { OrderPicker } from "@/components";
+import { ImageUploader, OrderPicker } from "@/components";
 
 export const Route = createFileRoute("/_auth/_staff/my-records/new")({
   component: StaffNewRecordPage,
@@ -20,6 +20,7 @@ function StaffNewRecordPage() {
   const [orderId, setOrderId] = useState("");
   const [processId, setProcessId] = useState("");
   const [quantity, setQuantity] = useState(1);
+  const [images, setImages] = useState<string[]>([]);
   const [orderSheetVisible, setOrderSheetVisible] = useState(false);
 
   const { data: ordersData } = useQuery({
@@ -52,6 +53,12 @@ function StaffNewRecordPage() {
     (p) => p.id === processId
   );
 
+  const photoRequired =
+    !!selectedProcess &&
+    (!!selectedProcess.photoRequired ||
+      (selectedProcess.photoThreshold != null &&
+        quantity >= selectedProcess.photoThreshold));
+
   const totalAmount = selectedProcess
     ? (parseFloat(selectedProcess.piecePrice) * quantity).toFixed(2)
     : "0.00";
@@ -80,10 +87,15 @@ function StaffNewRecordPage() {
       Toast.show({ content: "请填写完整信息" });
       return;
     }
+    if (photoRequired && images.length === 0) {
+      Toast.show({ content: "该工序需上传计件照片" });
+      return;
+    }
     createMutation.mutate({
       userId: user.id,
       processId,
       quantity,
+      images: images.length > 0 ? images : undefined,
     });
   };
 
@@ -128,6 +140,9 @@ function StaffNewRecordPage() {
           <Form.Item label="数量" required>
             <Stepper min={1} value={quantity} onChange={setQuantity} />
           </Form.Item>
+          <Form.Item label="照片" required={photoRequired}>
+            <ImageUploader value={images} onChange={setImages} maxCount={9} />
+          </Form.Item>
           <Form.Item label="金额">
             <span className="text-lg font-medium text-orange-500">
               ¥{totalAmount}
@@ -0,0 +1,179 @@
+import { createFileRoute, useNavigate } from "@tanstack/react-router";
+import { NavBar, Toast, Form, Button, Picker } from "antd-mobile";
+import { ChevronLeft } from "lucide-react";
+import type { Process, Staff } from "@/types";
+import { authApi, bundleApi, processApi } from "@/api";
+import { useQueryClient, useMutation, useQuery } from "@tanstack/react-query";
+import { useState } from "react";
+import { useAuthStore, selectIsBoss } from "@/stores/auth";
+import { ImageUploader } from "@/components";
+import { useWorkshopSettings } from "@/hooks";
+
+export const Route = createFileRoute("/_auth/bundle")({
+  validateSearch: (search: Record<string, unknown>) => ({
+    id: (search.id as string) || "",
+  }),
+  component: BundleScanPage,
+});
+
+function BundleScanPage() {
+  const { id } = Route.useSearch();
+  const navigate = useNavigate();
+  const queryClient = useQueryClient();
+  const isBoss = useAuthStore(selectIsBoss);
+  const { pieceUnit } = useWorkshopSettings();
+
+  const [processId, setProcessId] = useState("");
+  const [userId, setUserId] = useState("");
+  const [images, setImages] = useState<string[]>([]);
+
+  const { data: bundle, isLoading } = useQuery({
+    queryKey: ["bundle", id],
+    queryFn: () => bundleApi.getOne(id),
+    enabled: !!id,
+  });
+
+  const { data: processesData } = useQuery({
+    queryKey: ["processes", bundle?.orderId],
+    queryFn: () => processApi.list({ orderId: bundle!.orderId, pageSize: 1000 }),
+    enabled: !!bundle?.orderId,
+  });
+
+  const { data: staffList } = useQuery({
+    queryKey: ["staff-list"],
+    queryFn: () => authApi.getStaffList(),
+    enabled: isBoss,
+  });
+
+  const goBack = () => navigate({ to: isBoss ? "/records" : "/my-records" });
+
+  const scanMutation = useMutation({
+    mutationFn: bundleApi.scan,
+    onSuccess: () => {
+      Toast.show({ content: isBoss ? "已录入" : "已提交" });
+      queryClient.invalidateQueries({ queryKey: ["piece-records"] });
+      queryClient.invalidateQueries({ queryKey: ["bundle", id] });
+      goBack();
+    },
+  });
+
+  // 已扫码的工序不再可选
+  const scanned = new Set(bundle?.scannedProcessIds ?? []);
+  const processes = (processesData?.list ?? []).filter(
+    (p: Process) => !scanned.has(p.id)
+  );
+  const selectedProcess = processes.find((p) => p.id === processId);
+  const staffOptions = (staffList?.list ?? []).map((s: Staff) => ({
+    label: s.displayName || s.username,
+    value: s.id,
+  }));
+  const selectedStaff = staffOptions.find((o) => o.value === userId);
+
+  const photoRequired =
+    !!selectedProcess &&
+    !!bundle &&
+    (!!selectedProcess.photoRequired ||
+      (selectedProcess.photoThreshold != null &&
+        bundle.quantity >= selectedProcess.photoThreshold));
+
+  const handleSelectProcess = async () => {
+    if (processes.length === 0) {
+      Toast.show({ content: "该扎的工序均已扫码" });
+      return;
+    }
+    const val = await Picker.prompt({
+      columns: [
+        processes.map((p) => ({
+          label: `${p.name} (¥${p.piecePrice})`,
+          value: p.id,
+        })),
+      ],
+    });
+    if (val) setProcessId(val[0] as string);
+  };
+
+  const handleSelectStaff = async () => {
+    const val = await Picker.prompt({ columns: [staffOptions] });
+    if (val) setUserId(val[0] as string);
+  };
+
+  const handleSubmit = () => {
+    if (!processId || (isBoss && !userId)) {
+      Toast.show({ content: "请填写完整信息" });
+      return;
+    }
+    if (photoRequired && images.length === 0) {
+      Toast.show({ content: "该工序需上传计件照片" });
+      return;
+    }
+    scanMutation.mutate({
+      payload: id,
+      processId,
+      userId: isBoss ? userId : undefined,
+      images: images.length > 0 ? images : undefined,
+    });
+  };
+
+  return (
+    <div>
+      <NavBar onBack={goBack} backIcon={<ChevronLeft size={24} />}>
+        扫码计件
+      </NavBar>
+      {isLoading ? (
+        <div className="p-8 text-center text-gray-500">加载中...</div>
+      ) : !bundle ? (
+        <div className="p-8 text-center text-gray-500">扎不存在</div>
+      ) : (
+        <div className="p-4">
+          <div className="bg-white rounded-lg p-4 mb-3">
+            <div className="font-medium text-lg">
+              {bundle.orderName || "未知订单"} · 第{bundle.bundleNo}扎
+            </div>
+            <div className="text-sm text-gray-500 mt-1">
+              {[bundle.color, bundle.size].filter(Boolean).join(" / ")}
+              {(bundle.color || bundle.size) && " · "}
+              {bundle.quantity}
+              {pieceUnit}
+            </div>
+          </div>
+          <Form
+            footer={
+              <Button
+                block
+                color="primary"
+                onClick={handleSubmit}
+                loading={scanMutation.isPending}
+              >
+                提交
+              </Button>
+            }
+          >
+            {isBoss && (
+              <Form.Item
+                label="员工"
+                required
+                clickable
+                onClick={handleSelectStaff}
+                extra={selectedStaff?.label || "请选择员工"}
+              />
+            )}
+            <Form.Item
+              label="工序"
+              required
+              clickable
+              onClick={handleSelectProcess}
+              extra={
+                selectedProcess
+                  ? `${selectedProcess.name} (¥${selectedProcess.piecePrice})`
+                  : "请选择工序"
+              }
+            />
+            <Form.Item label="照片" required={photoRequired}>
+              <ImageUploader value={images} onChange={setImages} maxCount={9} />
+            </Form.Item>
+          </Form>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -1,10 +1,12 @@
 import { createFileRoute, useNavigate } from "@tanstack/react-router";
-import { List, Button, Dialog, Toast, Input, PullToRefresh } from "antd-mobile";
-import { Users, UserPlus, LogOut, Camera, Store, Phone, User as UserIcon, AtSign, Lock, ClipboardList, Wallet, Share2, Shield } from "lucide-react";
+import { List, Button, Dialog, Toast, Input, PullToRefresh, Selector } from "antd-mobile";
+import { Users, UserPlus, LogOut, Camera, Store, Phone, User as UserIcon, AtSign, Lock, ClipboardList, Wallet, Share2, Shield, CreditCard } from "lucide-react";
 import { useAuthStore, selectIsBoss, selectIsSuperAdmin } from "@/stores/auth";
 import { authApi } from "@/api";
 import { Avatar, useAvatarCropper } from "@/components";
 import { uploadImage } from "@/utils/upload";
+import { PAYOUT_METHOD_MAP } from "@/constants";
+import type { PayoutAccount, PayoutMethod } from "@/types";
 
 export const Route = createFileRoute("/_auth/profile")({
   component: ProfilePage,
@@ -85,6 +87,67 @@ function ProfilePage() {
     }
   };
 
+  const handleEditPayout = async () => {
+    const account = await new Promise<PayoutAccount | null>((resolve) => {
+      const current = user?.payoutAccount;
+      let method: PayoutMethod = current?.method || "bank";
+      let accountName = current?.accountName || "";
+      let accountNo = current?.accountNo || "";
+      let bankName = current?.bankName || "";
+      Dialog.confirm({
+        title: "收款账户",
+        content: (
+          <div className="space-y-3">
+            <Selector
+              columns={3}
+              defaultValue={[method]}
+              options={(Object.keys(PAYOUT_METHOD_MAP) as PayoutMethod[]).map((m) => ({
+                label: PAYOUT_METHOD_MAP[m],
+                value: m,
+              }))}
+              onChange={(v) => {
+                if (v[0]) method = v[0];
+              }}
+            />
+            <Input
+              placeholder="收款人姓名"
+              defaultValue={accountName}
+              onChange={(v) => (accountName = v)}
+            />
+            <Input
+              placeholder="卡号 / 账号"
+              defaultValue={accountNo}
+              onChange={(v) => (accountNo = v)}
+            />
+            <Input
+              placeholder="开户行（银行卡填写）"
+              defaultValue={bankName}
+              onChange={(v) => (bankName = v)}
+            />
+          </div>
+        ),
+        onConfirm: () =>
+          resolve({
+            method,
+            accountName: accountName.trim(),
+            accountNo: accountNo.trim(),
+            bankName: bankName.trim() || undefined,
+          }),
+        onCancel: () => resolve(null),
+      });
+    });
+    if (!account) return;
+    try {
+      // 姓名或账号留空即清除收款账户
+      const cleared = !account.accountName || !account.accountNo;
+      await authApi.updateProfile({ payoutAccount: account });
+      updateUser({ payoutAccount: cleared ? undefined : account });
+      Toast.show({ content: cleared ? "已清除收款账户" : "修改成功" });
+    } catch (e) {
+      Toast.show({ content: e instanceof Error ? e.message : "修改失败" });
+    }
+  };
+
   const handleChangePassword = async () => {
     const result = await new Promise<{ oldPassword: string; newPassword: string } | null>((resolve) => {
       let oldPassword = "";
@@ -210,6 +273,19 @@ function ProfilePage() {
           >
             用户名
           </List.Item>
+          {!isBoss && (
+            <List.Item
+              prefix={<CreditCard size={20} />}
+              onClick={handleEditPayout}
+              extra={
+                user?.payoutAccount
+                  ? `${PAYOUT_METHOD_MAP[user.payoutAccount.method]} ${user.payoutAccount.accountNo.slice(-4)}`
+                  : "未设置"
+              }
+            >
+              收款账户
+            </List.Item>
+          )}
           <List.Item
             prefix={<Lock size={20} />}
             onClick={handleChangePassword}
@@ -32,10 +32,18 @@ function ScanPage() {
           return true;
         }
       }
+      // 扎的二维码：stitchwork://bundle?id=xxx
+      if (path === "bundle") {
+        const id = url.searchParams.get("id");
+        if (id) {
+          navigate({ to: "/bundle", search: { id } });
+          return true;
+        }
+      }
     } catch {
       // 不是有效 URL
     }
-    Toast.show({ content: "无效的邀请码", icon: "fail" });
+    Toast.show({ content: "无法识别的二维码", icon: "fail" });
     return false;
   };
 
@@ -0,0 +1,26 @@
+export interface Advance {
+  id: string;
+  userId: string;
+  bossId: string;
+  amount: string;
+  advancedAt: string;
+  note?: string;
+  image?: string;
+  createdAt: string;
+}
+
+export interface AdvanceBalance {
+  userId: string;
+  userName?: string;
+  advanced: string;
+  repaid: string;
+  outstanding: string;
+}
+
+export interface CreateAdvanceDto {
+  userId: string;
+  amount: string;
+  advancedAt?: string;
+  note?: string;
+  image?: string;
+}
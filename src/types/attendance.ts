@@ -0,0 +1,28 @@
+export interface Attendance {
+  id: string;
+  userId: string;
+  bossId: string;
+  workDate: string;
+  clockIn?: string;
+  clockOut?: string;
+  hours: string;
+  days: string;
+  amount: string;
+  note?: string;
+  payrollId?: string;
+  createdAt: string;
+}
+
+export interface CreateAttendanceDto {
+  userId: string;
+  workDate: string;
+  hours?: string;
+  days?: string;
+  note?: string;
+}
+
+export interface UpdateAttendanceDto {
+  hours?: string;
+  days?: string;
+  note?: string;
+}
@@ -15,6 +15,23 @@ export interface Workshop {
   businessLabel: string;
 }
 
+export interface AutoApproveRule {
+  name: string;
+  userIds: string[];
+  processIds: string[];
+  maxQuantity?: number;
+  withinRemaining: boolean;
+}
+
+export type PayoutMethod = "bank" | "alipay" | "wechat";
+
+export interface PayoutAccount {
+  method: PayoutMethod;
+  accountName: string;
+  accountNo: string;
+  bankName?: string;
+}
+
 export interface LoginUser {
   id: string;
   username: string;
@@ -24,6 +41,7 @@ export interface LoginUser {
   avatar?: string;
   isSuperAdmin: boolean;
   workshop?: Workshop;
+  payoutAccount?: PayoutAccount;
 }
 
 export interface LoginResponse {
@@ -58,6 +76,7 @@ export interface UpdateProfileRequest {
   displayName?: string;
   phone?: string;
   avatar?: string;
+  payoutAccount?: PayoutAccount;
 }
 
 export interface ChangePasswordRequest {
@@ -90,4 +109,12 @@ export interface Staff {
   phone?: string;
   avatar?: string;
   createdAt?: string;
+  payoutAccount?: PayoutAccount;
+  hourlyRate?: string;
+  dailyRate?: string;
+}
+
+export interface UpdateStaffWageRatesRequest {
+  hourlyRate?: string;
+  dailyRate?: string;
 }
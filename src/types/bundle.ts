@@ -0,0 +1,30 @@
+export interface Bundle {
+  id: string;
+  orderId: string;
+  orderName?: string;
+  bundleNo: number;
+  size?: string;
+  color?: string;
+  quantity: number;
+  qrPayload: string;
+  scannedProcessIds: string[];
+  createdAt: string;
+}
+
+export interface BundleSpec {
+  size?: string;
+  color?: string;
+  quantity: number;
+  bundleSize?: number;
+}
+
+export interface GenerateBundlesDto {
+  items: BundleSpec[];
+}
+
+export interface ScanBundleDto {
+  payload: string;
+  processId: string;
+  userId?: string;
+  images?: string[];
+}
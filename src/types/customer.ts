@@ -19,3 +19,52 @@ export interface UpdateCustomerDto {
   phone?: string;
   description?: string;
 }
+
+export type CustomerAddressKind = "delivery" | "billing";
+
+export interface CustomerContact {
+  id: string;
+  customerId: string;
+  bossId: string;
+  name: string;
+  role?: string;
+  phone?: string;
+  note?: string;
+  isDefault: boolean;
+  createdAt: string;
+}
+
+export interface CustomerAddress {
+  id: string;
+  customerId: string;
+  bossId: string;
+  kind: CustomerAddressKind;
+  label?: string;
+  address: string;
+  receiver?: string;
+  phone?: string;
+  isDefault: boolean;
+  createdAt: string;
+}
+
+export interface CustomerDetail extends Customer {
+  contacts: CustomerContact[];
+  addresses: CustomerAddress[];
+}
+
+export interface CustomerContactDto {
+  name: string;
+  role?: string;
+  phone?: string;
+  note?: string;
+  isDefault?: boolean;
+}
+
+export interface CustomerAddressDto {
+  kind: CustomerAddressKind;
+  label?: string;
+  address: string;
+  receiver?: string;
+  phone?: string;
+  isDefault?: boolean;
+}
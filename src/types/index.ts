@@ -1,11 +1,15 @@
 export * from "./admin";
+export * from "./advance";
+export * from "./attendance";
 export * from "./api";
 export * from "./auth";
+export * from "./bundle";
 export * from "./customer";
 export * from "./home";
 export * from "./order";
 export * from "./process";
 export * from "./piece-record";
 export * from "./payroll";
+export * from "./pay-run";
 export * from "./stats";
 export * from "./share";
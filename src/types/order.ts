@@ -1,3 +1,5 @@
+import type { CustomerAddress, CustomerContact } from "./customer";
+
 export type OrderStatus = "pending" | "processing" | "completed" | "delivered" | "cancelled";
 
 export interface Order {
@@ -9,6 +11,10 @@ export interface Order {
   quantity: number;
   unitPrice: string;
   status: OrderStatus;
+  deliveryAddressId?: string;
+  contactId?: string;
+  deliveryAddress?: CustomerAddress;
+  contact?: CustomerContact;
   createdAt: string;
   updatedAt: string;
 }
@@ -20,6 +26,8 @@ export interface CreateOrderDto {
   images?: string[];
   quantity: number;
   unitPrice: string;
+  deliveryAddressId?: string;
+  contactId?: string;
 }
 
 export interface UpdateOrderDto {
@@ -29,6 +37,9 @@ export interface UpdateOrderDto {
   quantity?: number;
   unitPrice?: string;
   status?: string;
+  /** 传 null 清除 */
+  deliveryAddressId?: string | null;
+  contactId?: string | null;
 }
 
 export interface UpdateOrderStatusDto {
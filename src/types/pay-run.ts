@@ -0,0 +1,55 @@
+import type { PayrollItemDto } from "./payroll";
+
+export type PayRunStatus = "draft" | "finalized";
+
+export interface PayRun {
+  id: string;
+  bossId: string;
+  startDate: string;
+  endDate: string;
+  status: PayRunStatus;
+  note?: string;
+  createdAt: string;
+  finalizedAt?: string;
+}
+
+export interface PayRunEntry {
+  id: string;
+  userId: string;
+  userName?: string;
+  recordIds: string[];
+  quantity: number;
+  pieceAmount: string;
+  attendanceIds: string[];
+  hours: string;
+  timeAmount: string;
+  items: PayrollItemDto[];
+  repayAdvance: boolean;
+  suggestedRepayment: string;
+  amount?: string;
+  estimatedAmount: string;
+  excluded: boolean;
+  note?: string;
+  payrollId?: string;
+}
+
+export interface PayRunDetail extends Omit<PayRun, "bossId"> {
+  totalAmount: string;
+  entries: PayRunEntry[];
+}
+
+export interface CreatePayRunDto {
+  startDate: string;
+  endDate: string;
+  note?: string;
+}
+
+export interface UpdatePayRunEntryDto {
+  recordIds: string[];
+  attendanceIds?: string[];
+  items?: PayrollItemDto[];
+  repayAdvance?: boolean;
+  amount?: string;
+  excluded?: boolean;
+  note?: string;
+}
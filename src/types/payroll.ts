@@ -1,3 +1,11 @@
+import type { Attendance } from "./attendance";
+
+export type PayrollAckStatus =
+  | "pending"
+  | "acknowledged"
+  | "disputed"
+  | "resolved";
+
 export interface Payroll {
   id: string;
   userId: string;
@@ -6,6 +14,13 @@ export interface Payroll {
   paymentImage?: string;
   note?: string;
   paidAt: string;
+  transferredAt?: string;
+  ackStatus: PayrollAckStatus;
+  ackComment?: string;
+  ackedAt?: string;
+  resolution?: string;
+  voidedAt?: string;
+  voidReason?: string;
 }
 
 export interface PayrollRecord {
@@ -17,16 +32,87 @@ export interface PayrollRecord {
   orderName?: string;
   orderImages?: string[];
   piecePrice?: string;
+  adjustReason?: string;
+}
+
+export type PayrollItemKind =
+  | "pieceWork"
+  | "timeWork"
+  | "bonus"
+  | "deduction"
+  | "advanceRepayment"
+  | "rounding"
+  | "manualAdjustment";
+
+export interface PayrollItem {
+  id: string;
+  payrollId: string;
+  kind: PayrollItemKind;
+  amount: string;
+  note?: string;
+  position: number;
+  createdAt: string;
 }
 
 export interface PayrollDetail extends Payroll {
   records: PayrollRecord[];
+  items: PayrollItem[];
+  attendances: Attendance[];
 }
 
-export interface CreatePayrollDto {
+export interface PayrollPreviewParams {
+  startDate?: string;
+  endDate?: string;
+  userId?: string;
+  userName?: string;
+}
+
+export interface PayrollPreviewGroup {
+  orderId?: string;
+  orderName?: string;
+  processId: string;
+  processName?: string;
+  piecePrice?: string;
+  quantity: number;
+  amount: string;
+  recordIds: string[];
+}
+
+export interface PayrollPreviewItem {
   userId: string;
+  userName: string;
+  totalQuantity: number;
+  pieceAmount: string;
+  groups: PayrollPreviewGroup[];
+  attendanceIds: string[];
+  hours: string;
+  timeAmount: string;
+  outstandingAdvance: string;
+  suggestedRepayment: string;
+  suggestedAmount: string;
+}
+
+export interface PayrollPreview {
+  list: PayrollPreviewItem[];
+  totalAmount: string;
+}
+
+export interface PayrollItemDto {
+  kind: "bonus" | "deduction" | "advanceRepayment";
   amount: string;
+  note?: string;
+}
+
+export type PayrollRounding = "jiao" | "yuan";
+
+export interface CreatePayrollDto {
+  userId: string;
+  amount?: string;
   recordIds: string[];
+  attendanceIds?: string[];
+  items?: PayrollItemDto[];
+  repayAdvance?: boolean;
+  rounding?: PayrollRounding;
   paymentImage?: string;
   note?: string;
 }
@@ -36,3 +122,38 @@ export interface UpdatePayrollDto {
   paymentImage?: string;
   note?: string;
 }
+
+export interface VoidPayrollDto {
+  reason: string;
+}
+
+export interface AcknowledgePayrollDto {
+  comment?: string;
+}
+
+export interface DisputePayrollDto {
+  comment: string;
+}
+
+export interface ResolvePayrollDto {
+  resolution: string;
+}
+
+export type ExportColumn =
+  | "staffName"
+  | "payeeName"
+  | "phone"
+  | "method"
+  | "account"
+  | "bankName"
+  | "amount"
+  | "memo";
+
+export type ExportLayout = "generic" | "alipay" | "bank";
+
+export interface ExportPayrollsDto {
+  payrollIds: string[];
+  layout?: ExportLayout;
+  columns?: ExportColumn[];
+  includeHeader?: boolean;
+}
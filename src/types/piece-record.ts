@@ -18,14 +18,57 @@ export interface PieceRecord {
   orderId?: string;
   orderImage?: string;
   piecePrice?: string;
+  rejectReason?: string;
+  autoApproveRule?: string;
+  images?: string[];
+  adjustsRecordId?: string;
+  adjustReason?: string;
+  bundleId?: string;
+  originalQuantity?: number;
 }
 
 export interface CreatePieceRecordDto {
   processId: string;
   userId: string;
   quantity: number;
+  images?: string[];
 }
 
 export interface UpdatePieceRecordDto {
   quantity?: number;
+  images?: string[];
+}
+
+export interface RejectPieceRecordDto {
+  preset?: string;
+  reason?: string;
+}
+
+export interface ResubmitPieceRecordDto {
+  quantity?: number;
+  images?: string[];
+}
+
+export interface AdjustPieceRecordDto {
+  quantity?: number;
+  amount?: string;
+  reason: string;
+}
+
+export type PieceRecordAction =
+  | "rejected"
+  | "resubmitted"
+  | "edited"
+  | "adjusted"
+  | "unsettled";
+
+export interface PieceRecordLog {
+  id: string;
+  pieceRecordId: string;
+  operatorId: string;
+  action: PieceRecordAction;
+  previousQuantity: number;
+  quantity: number;
+  reason?: string;
+  createdAt: string;
 }
@@ -4,6 +4,8 @@ export interface Process {
   name: string;
   description?: string;
   piecePrice: string;
+  photoRequired?: boolean;
+  photoThreshold?: number;
   createdAt: string;
   updatedAt: string;
 }
@@ -13,12 +15,16 @@ export interface CreateProcessDto {
   name: string;
   description?: string;
   piecePrice: string;
+  photoRequired?: boolean;
+  photoThreshold?: number;
 }
 
 export interface UpdateProcessDto {
   name?: string;
   description?: string;
   piecePrice?: string;
+  photoRequired?: boolean;
+  photoThreshold?: number;
 }
 
 export interface ProcessQueryParams {
@@ -30,6 +30,8 @@ export interface WorkerProduction {
   userName: string;
   totalQuantity: number;
   totalAmount: string;
+  totalHours: string;
+  timeAmount: string;
 }
 
 export interface WorkerProductionList {